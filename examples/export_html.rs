@@ -0,0 +1,41 @@
+//! HTML Export Example
+//!
+//! Writes `report.html`, a single file embedding the WASM viewer and the
+//! dashboard. It opens offline (from disk or as an email attachment) with full
+//! pan and zoom.
+//!
+//! Build the viewer first with `scripts/build-wasm.sh`, or point
+//! `VIDI_VIEWER_DIR` at a directory holding `vidi.js` and `vidi_bg.wasm`.
+//!
+//! Run with: cargo run --example export_html
+
+use glam::Vec2;
+use vidi::core::Style;
+use vidi::prelude::*;
+
+fn main() {
+    let builder = dash()
+        .add_2d(|p| {
+            let points: Vec<Vec2> = (0..500)
+                .map(|i| {
+                    let x = i as f32 * 0.05;
+                    Vec2::new(x, x.sin() * (-x / 10.0).exp())
+                })
+                .collect();
+            p.line(points, Style::default().rgb(0.3, 0.6, 0.9))
+                .title("Damped oscillation")
+                .x_label("t")
+                .y_label("amplitude")
+        })
+        .add_distribution(|d| {
+            let values: Vec<f32> = (0..1000)
+                .map(|i| ((i * 7919) % 1000) as f32 / 250.0 + ((i * 31) % 13) as f32 * 0.2)
+                .collect();
+            d.histogram(values).title("Samples")
+        });
+
+    match builder.save_html("report.html") {
+        Ok(()) => println!("Wrote report.html"),
+        Err(e) => eprintln!("Failed to write report.html: {}", e),
+    }
+}
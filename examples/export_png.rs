@@ -0,0 +1,86 @@
+//! Headless PNG Export Example
+//!
+//! Renders a dashboard with every plot type to `dashboard.png`, then each plot
+//! on its own to `plot_<n>.png`, without opening a window. Works on CPU-only
+//! machines through a software adapter (e.g. Mesa llvmpipe).
+//!
+//! Run with: cargo run --example export_png
+
+use glam::{UVec2, Vec2, Vec3};
+use vidi::core::{Color, Colormap, Style};
+use vidi::prelude::*;
+
+fn main() {
+    let size = UVec2::new(1280, 960);
+
+    let builder = dash()
+        .columns(3)
+        .add_2d(|p| {
+            let wave: Vec<Vec2> = (0..200)
+                .map(|i| {
+                    let x = i as f32 * 0.05;
+                    Vec2::new(x, x.sin())
+                })
+                .collect();
+            p.line(wave, Style::default().rgb(0.3, 0.6, 0.9))
+                .title("Line")
+                .x_label("t")
+                .y_label("sin(t)")
+        })
+        .add_3d(|p| {
+            let spiral: Vec<Vec3> = (0..300)
+                .map(|i| {
+                    let t = i as f32 * 0.05;
+                    Vec3::new(t.cos(), t.sin(), t * 0.1)
+                })
+                .collect();
+            p.points(spiral, Style::default().rgb(0.9, 0.5, 0.2))
+                .title("Scatter 3D")
+        })
+        .add_distribution(|d| {
+            let values: Vec<f32> = (0..500)
+                .map(|i| ((i * 37) % 100) as f32 / 10.0 + ((i * 13) % 7) as f32)
+                .collect();
+            d.histogram(values)
+                .bins(20)
+                .style(Style::default().color(Color::rgb(0.4, 0.8, 0.5)))
+                .title("Histogram")
+        })
+        .add_candlestick(|c| {
+            let candles = (0..30)
+                .map(|i| {
+                    let t = i as f32;
+                    let open = 100.0 + (t * 0.4).sin() * 5.0;
+                    let close = 100.0 + (t * 0.4 + 0.3).sin() * 5.0;
+                    (t, open, open.max(close) + 1.5, open.min(close) - 1.5, close)
+                })
+                .collect();
+            c.data(candles).title("Candlestick")
+        })
+        .add_heatmap(|h| {
+            let values = (0..100).map(|i| ((i % 10) * (i / 10)) as f32).collect();
+            h.data(10, 10, values)
+                .colormap(Colormap::Viridis)
+                .title("Heatmap")
+        })
+        .add_radial(|r| {
+            r.pie(vec![("A", 40.0), ("B", 25.0), ("C", 20.0), ("D", 15.0)])
+                .title("Pie")
+        });
+
+    match builder.save_png("dashboard.png", size) {
+        Ok(()) => println!("Wrote dashboard.png"),
+        Err(e) => eprintln!("Failed to export dashboard: {}", e),
+    }
+
+    for (i, plot) in builder.build().plots.iter().enumerate() {
+        let path = format!("plot_{}.png", i);
+        match render_plot_png(plot, UVec2::new(640, 480)) {
+            Ok(png) => {
+                std::fs::write(&path, png).expect("Failed to write PNG");
+                println!("Wrote {}", path);
+            }
+            Err(e) => eprintln!("Failed to export plot {}: {}", i, e),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Vector Export Example
+//!
+//! Writes a dashboard of 2D plots to `figure.svg` and `figure.pdf`, ready to be
+//! included in a paper. No window or GPU is needed.
+//!
+//! Run with: cargo run --example export_vector
+
+use glam::{UVec2, Vec2};
+use vidi::core::{Color, Colormap, Style};
+use vidi::prelude::*;
+
+fn main() {
+    let size = UVec2::new(1200, 800);
+
+    let builder = dash()
+        .columns(2)
+        .add_2d(|p| {
+            let train: Vec<Vec2> = (0..100)
+                .map(|i| Vec2::new(i as f32, 2.0 * (-(i as f32) / 30.0).exp() + 0.1))
+                .collect();
+            let val: Vec<Vec2> = (0..100)
+                .map(|i| Vec2::new(i as f32, 2.2 * (-(i as f32) / 35.0).exp() + 0.2))
+                .collect();
+            p.line(train, Style::default().rgb(0.3, 0.6, 0.9))
+                .line(val, Style::default().rgb(0.9, 0.5, 0.2))
+                .title("Loss")
+                .x_label("epoch")
+                .y_label("loss")
+        })
+        .add_distribution(|d| {
+            let values: Vec<f32> = (0..1000)
+                .map(|i| ((i * 7919) % 1000) as f32 / 250.0 + ((i * 31) % 13) as f32 * 0.2)
+                .collect();
+            d.pdf(values)
+                .style(Style::default().color(Color::rgb(0.4, 0.8, 0.5)))
+                .title("Weight distribution")
+        })
+        .add_heatmap(|h| {
+            let values = (0..64)
+                .map(|i| (((i % 8) as f32 - 3.5).powi(2) + ((i / 8) as f32 - 3.5).powi(2)).sqrt())
+                .collect();
+            h.data(8, 8, values)
+                .colormap(Colormap::Magma)
+                .title("Attention")
+        })
+        .add_radial(|r| {
+            r.radar(
+                vec!["speed", "accuracy", "memory", "latency", "cost"],
+                vec![0.8, 0.9, 0.4, 0.6, 0.5],
+            )
+            .title("Model profile")
+        });
+
+    for (path, result) in [
+        ("figure.svg", builder.save_svg("figure.svg", size)),
+        ("figure.pdf", builder.save_pdf("figure.pdf", size)),
+    ] {
+        match result {
+            Ok(()) => println!("Wrote {}", path),
+            Err(e) => eprintln!("Failed to write {}: {}", path, e),
+        }
+    }
+}
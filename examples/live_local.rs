@@ -0,0 +1,42 @@
+//! Live Local Dashboard Example
+//!
+//! Demonstrates streaming data into a native window with `spawn_local()`:
+//! a background thread simulates a training loop and appends loss values
+//! to a line plot while the dashboard is open.
+//!
+//! Run with: cargo run --example live_local
+
+use std::thread;
+use std::time::Duration;
+
+use glam::Vec2;
+use vidi::core::{Color, Style};
+use vidi::prelude::*;
+
+fn main() {
+    let dashboard = dash().add_2d(|p| {
+        p.line(
+            vec![Vec2::new(0.0, 1.0)],
+            Style::default().color(Color::RED),
+        )
+        .title("Training Loss")
+        .x_label("Step")
+        .y_label("Loss")
+        .key("loss")
+    });
+
+    // Keys give plots a stable handle without tracking numeric IDs
+    let loss_plot = dashboard.plot_id("loss").expect("plot keyed 'loss'");
+
+    dashboard.spawn_local(move |handle| {
+        for step in 1..2000 {
+            let t = step as f32;
+            let loss = (-t * 0.004).exp() + 0.05 * (t * 0.3).sin().abs() * (-t * 0.002).exp();
+
+            if handle.append_points_2d(loss_plot, 0, &[(t, loss)]).is_err() {
+                break; // Window closed
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+    });
+}
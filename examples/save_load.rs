@@ -0,0 +1,38 @@
+//! Save/Load Example
+//!
+//! Saves a dashboard as JSON (`run.vidi`) and in the compact binary encoding
+//! (`run.vidib`), then loads both back. Open either file in a window with
+//! `vidi open run.vidib`.
+//!
+//! Run with: cargo run --example save_load
+
+use glam::Vec2;
+use vidi::core::{Dashboard, Style};
+use vidi::prelude::*;
+
+fn main() -> Result<(), String> {
+    let builder = dash().add_2d(|p| {
+        let points: Vec<Vec2> = (0..10_000)
+            .map(|i| {
+                let x = i as f32 * 0.01;
+                Vec2::new(x, (x * 3.0).sin() * (-x / 40.0).exp())
+            })
+            .collect();
+        p.line(points, Style::default().rgb(0.3, 0.6, 0.9))
+            .title("Signal")
+            .x_label("t")
+    });
+
+    for path in ["run.vidi", "run.vidib"] {
+        builder.save(path)?;
+        let size = std::fs::metadata(path).map_err(|e| e.to_string())?.len();
+        let loaded = Dashboard::load(path)?;
+        println!(
+            "{}: {} bytes, {} plot(s) loaded back",
+            path,
+            size,
+            loaded.plots.len()
+        );
+    }
+    Ok(())
+}
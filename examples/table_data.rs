@@ -0,0 +1,43 @@
+//! Tabular Data Example
+//!
+//! Parses a CSV of training logs from several runs and plots it by column name:
+//! one loss line per run, a histogram of gradient norms and a box per run.
+//!
+//! Run with: cargo run --example table_data
+
+use vidi::data::Table;
+use vidi::prelude::*;
+
+fn main() -> Result<(), String> {
+    let mut csv = String::from("run,step,time,loss,grad_norm\n");
+    for (r, run) in ["baseline", "warmup", "large-lr"].iter().enumerate() {
+        for step in 0..200 {
+            let decay = 0.01 + 0.005 * r as f32;
+            let loss = 2.5 * (-(step as f32) * decay).exp() + 0.1 * ((step * 7 % 11) as f32 / 11.0);
+            let grad = 1.0 + ((step * 31 + r * 17) % 23) as f32 / 10.0;
+            let time = format!("2024-05-01T12:{:02}:{:02}Z", step / 60, step % 60);
+            csv.push_str(&format!("{run},{step},{time},{loss},{grad}\n"));
+        }
+    }
+    let table = Table::from_csv(&csv, ',')?;
+
+    dash()
+        .add_2d(|p| {
+            p.line_group_by(&table, "step", "loss", "run")
+                .title("Loss by run")
+        })
+        .add_2d(|p| {
+            p.scatter_from(&table, "time", "grad_norm")
+                .title("Gradient norm over time (s)")
+        })
+        .add_distribution(|d| {
+            d.histogram_from(&table, "grad_norm")
+                .title("Gradient norms")
+        })
+        .add_distribution(|d| {
+            d.boxplot_group_by(&table, "loss", "run")
+                .title("Loss spread")
+        })
+        .run_local();
+    Ok(())
+}
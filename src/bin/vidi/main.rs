@@ -0,0 +1,486 @@
+//! Vidi command line tool
+//!
+//! Quick looks at tabular data without writing a program:
+//!
+//! ```text
+//! vidi line data.csv --x step --y loss,val_loss
+//! vidi hist samples.jsonl --col latency --bins 50
+//! vidi heatmap matrix.csv --colormap magma --out matrix.png
+//! vidi tail train.csv --x step --y loss --push http://localhost:8080
+//! vidi open run.vidi
+//! ```
+//!
+//! Dashboards open in a window by default, are exported with `--out` or posted
+//! to a vidi-server with `--push`. Build with `--features cli`.
+
+mod tail;
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use glam::{UVec2, Vec2};
+use vidi_charts::core::{Color, Colormap, Dashboard, Style};
+use vidi_charts::dash::{DashBuilder, WebConfig, dash};
+use vidi_charts::data::{ColumnType, Format, RowReader, Table};
+
+#[derive(Parser, Debug)]
+#[command(name = "vidi")]
+#[command(about = "Open and produce Vidi dashboards from the command line")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Plot one or more columns as lines against an x column
+    Line {
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        series: SeriesArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Histogram of one or more columns (one tile each)
+    Hist {
+        #[command(flatten)]
+        input: InputArgs,
+        /// Columns to plot, comma separated (default: the first numeric column)
+        #[arg(long = "col", value_delimiter = ',')]
+        columns: Vec<String>,
+        /// Number of bins
+        #[arg(long, default_value_t = 30)]
+        bins: usize,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Heatmap of a numeric matrix. A non-numeric header row and first column
+    /// become column and row labels.
+    Heatmap {
+        #[command(flatten)]
+        input: InputArgs,
+        /// viridis, plasma, inferno, magma, coolwarm, rdbu, blues, reds or greens
+        #[arg(long, default_value = "viridis", value_parser = parse_colormap)]
+        colormap: Colormap,
+        /// Print the value in each cell
+        #[arg(long)]
+        show_values: bool,
+        /// Plot title
+        #[arg(long)]
+        title: Option<String>,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Follow a growing CSV or JSON-lines file (`-` for stdin) and stream new
+    /// rows into a live line plot
+    Tail {
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        series: SeriesArgs,
+        /// How often to check the file for new rows, in milliseconds
+        #[arg(long, default_value_t = 250)]
+        interval: u64,
+        #[command(flatten)]
+        push: PushArgs,
+    },
+    /// Open a saved dashboard file (`.vidi` or `.vidib`) in a window
+    Open {
+        /// Dashboard file written by `Dashboard::save`
+        path: PathBuf,
+    },
+}
+
+/// Input file format
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum FormatArg {
+    /// Delimiter-separated values with a header row
+    Csv,
+    /// One JSON object per line
+    Jsonl,
+}
+
+#[derive(Args, Debug)]
+struct InputArgs {
+    /// CSV or JSON-lines file (`-` for stdin)
+    path: PathBuf,
+    /// Input format (default: from the file extension, CSV unless .jsonl/.ndjson)
+    #[arg(long, value_enum)]
+    format: Option<FormatArg>,
+    /// CSV field delimiter (default: tab for .tsv, otherwise comma)
+    #[arg(long)]
+    delimiter: Option<char>,
+}
+
+impl InputArgs {
+    fn format(&self) -> Format {
+        let detected = Format::from_path(&self.path);
+        let format = match (self.format, detected) {
+            (Some(FormatArg::Jsonl), _) => Format::JsonLines,
+            (Some(FormatArg::Csv), Format::JsonLines) => Format::Csv { delimiter: ',' },
+            (_, detected) => detected,
+        };
+        match (format, self.delimiter) {
+            (Format::Csv { .. }, Some(delimiter)) => Format::Csv { delimiter },
+            (format, _) => format,
+        }
+    }
+
+    /// Header and text rows, before any type inference
+    fn read_rows(&self) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+        let text = read_input(&self.path)?;
+        let mut reader = RowReader::new(self.format());
+        let mut rows = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if let Some(row) = reader
+                .push_line(line)
+                .map_err(|e| format!("{}:{}: {}", self.path.display(), i + 1, e))?
+            {
+                rows.push(row);
+            }
+        }
+        Ok((reader.headers().to_vec(), rows))
+    }
+
+    fn read(&self) -> Result<Table, String> {
+        let (headers, rows) = self.read_rows()?;
+        Ok(Table::from_rows(headers, &rows))
+    }
+}
+
+#[derive(Args, Debug)]
+struct SeriesArgs {
+    /// Column for the x axis (default: the row number)
+    #[arg(long)]
+    x: Option<String>,
+    /// Columns for the y axis, comma separated
+    #[arg(long, value_delimiter = ',', required = true)]
+    y: Vec<String>,
+    /// Draw points instead of lines
+    #[arg(long)]
+    scatter: bool,
+    /// Plot title (default: the y column names)
+    #[arg(long)]
+    title: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct OutputArgs {
+    /// Write to a file instead of opening a window
+    /// (.png, .svg, .pdf, .html, .vidi or .vidib)
+    #[arg(short, long, conflicts_with = "url")]
+    out: Option<PathBuf>,
+    /// Image size for --out, as WIDTHxHEIGHT
+    #[arg(long, default_value = "1280x960", value_parser = parse_size)]
+    size: UVec2,
+    #[command(flatten)]
+    push: PushArgs,
+}
+
+#[derive(Args, Debug)]
+struct PushArgs {
+    /// Post to a vidi-server (e.g. http://localhost:8080) instead of opening a window
+    #[arg(long = "push", value_name = "URL", id = "url")]
+    url: Option<String>,
+    /// Experiment name for --push
+    #[arg(long, requires = "url")]
+    name: Option<String>,
+    /// Tag for --push (repeatable)
+    #[arg(long = "tag", requires = "url")]
+    tags: Vec<String>,
+    /// Keep the pushed dashboard instead of letting it expire
+    #[arg(long, requires = "url")]
+    permanent: bool,
+    /// Don't open the pushed dashboard in the browser
+    #[arg(long, requires = "url")]
+    no_browser: bool,
+}
+
+impl PushArgs {
+    fn config(&self) -> WebConfig {
+        let mut config = WebConfig::new().tags(self.tags.clone());
+        if let Some(name) = &self.name {
+            config = config.xp_name(name.clone());
+        }
+        if self.permanent {
+            config = config.permanent();
+        }
+        if self.no_browser {
+            config = config.no_browser();
+        }
+        config
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Line {
+            input,
+            series,
+            output,
+        } => input
+            .read()
+            .and_then(|table| line(&table, &series))
+            .and_then(|builder| emit(builder, &output)),
+        Command::Hist {
+            input,
+            columns,
+            bins,
+            output,
+        } => input
+            .read()
+            .and_then(|table| hist(&table, &columns, bins))
+            .and_then(|builder| emit(builder, &output)),
+        Command::Heatmap {
+            input,
+            colormap,
+            show_values,
+            title,
+            output,
+        } => input
+            .read_rows()
+            .and_then(|(headers, rows)| heatmap(headers, rows, colormap, show_values, title))
+            .and_then(|builder| emit(builder, &output)),
+        Command::Tail {
+            input,
+            series,
+            interval,
+            push,
+        } => tail::run(&input, &series, interval, &push),
+        Command::Open { path } => open(&path),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("vidi: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Show, export or push a finished dashboard
+fn emit(builder: DashBuilder, output: &OutputArgs) -> Result<(), String> {
+    if let Some(path) = &output.out {
+        let size = output.size;
+        return match extension(path).as_deref() {
+            Some("png") => builder.save_png(path, size),
+            Some("svg") => builder.save_svg(path, size),
+            Some("pdf") => builder.save_pdf(path, size),
+            Some("html") => builder.save_html(path),
+            Some("vidi" | "vidib") => builder.save(path),
+            _ => Err(format!(
+                "Unsupported output {} (use .png, .svg, .pdf, .html, .vidi or .vidib)",
+                path.display()
+            )),
+        };
+    }
+    if let Some(url) = &output.push.url {
+        let web = builder.run_web(url, output.push.config())?;
+        println!("{}", web.view_url);
+        return Ok(());
+    }
+    builder.run_local();
+    Ok(())
+}
+
+fn line(table: &Table, series: &SeriesArgs) -> Result<DashBuilder, String> {
+    let xs = match &series.x {
+        Some(x) => table.f32_column(x)?,
+        None => (0..table.len()).map(|i| Some(i as f32)).collect(),
+    };
+    let layers = series
+        .y
+        .iter()
+        .map(|y| {
+            let ys = table.f32_column(y)?;
+            Ok(xs
+                .iter()
+                .zip(ys)
+                .filter_map(|(x, y)| Some(Vec2::new((*x)?, y?)))
+                .collect())
+        })
+        .collect::<Result<Vec<Vec<Vec2>>, String>>()?;
+
+    Ok(dash().add_2d(|p| series_plot(p, series, layers)))
+}
+
+/// Line (or scatter) plot with one palette-colored layer per y column
+fn series_plot(
+    mut p: vidi_charts::dash::Plot2DBuilder,
+    series: &SeriesArgs,
+    layers: Vec<Vec<Vec2>>,
+) -> vidi_charts::dash::Plot2DBuilder {
+    for (i, points) in layers.into_iter().enumerate() {
+        let style = Style::default().color(Color::palette(i));
+        p = if series.scatter {
+            p.scatter(points, style)
+        } else {
+            p.line(points, style)
+        };
+    }
+    p.title(series.title.clone().unwrap_or_else(|| series.y.join(", ")))
+        .x_label(series.x.as_deref().unwrap_or("row"))
+}
+
+fn hist(table: &Table, columns: &[String], bins: usize) -> Result<DashBuilder, String> {
+    let columns = match columns {
+        [] => vec![
+            table
+                .column_names()
+                .iter()
+                .find(|name| {
+                    table
+                        .column(name)
+                        .is_ok_and(|c| matches!(c.column_type(), ColumnType::F32 | ColumnType::I64))
+                })
+                .ok_or("No numeric column to plot")?
+                .clone(),
+        ],
+        columns => columns.to_vec(),
+    };
+
+    let mut builder = dash();
+    for (i, column) in columns.iter().enumerate() {
+        let values = table.values(column)?;
+        builder = builder.add_distribution(|d| {
+            d.histogram(values)
+                .bins(bins)
+                .style(Style::default().color(Color::palette(i)))
+                .title(column.clone())
+                .x_label(column.clone())
+        });
+    }
+    Ok(builder)
+}
+
+fn heatmap(
+    headers: Vec<String>,
+    mut rows: Vec<Vec<String>>,
+    colormap: Colormap,
+    show_values: bool,
+    title: Option<String>,
+) -> Result<DashBuilder, String> {
+    let mut col_labels = Some(headers);
+    if col_labels
+        .as_ref()
+        .is_some_and(|h| h.iter().all(|c| parse_number(c).is_some()))
+    {
+        // No header row, just data
+        rows.insert(0, col_labels.take().unwrap_or_default());
+    }
+
+    let mut row_labels = None;
+    if rows
+        .iter()
+        .any(|row| row.first().is_some_and(|c| parse_number(c).is_none()))
+    {
+        row_labels = Some(
+            rows.iter_mut()
+                .map(|row| {
+                    if row.is_empty() {
+                        String::new()
+                    } else {
+                        row.remove(0)
+                    }
+                })
+                .collect::<Vec<_>>(),
+        );
+        if let Some(labels) = &mut col_labels {
+            if !labels.is_empty() {
+                labels.remove(0);
+            }
+        }
+    }
+
+    let cols = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if rows.is_empty() || cols == 0 {
+        return Err("Heatmap input has no values".to_string());
+    }
+    let mut values = Vec::with_capacity(rows.len() * cols);
+    for (r, row) in rows.iter().enumerate() {
+        for c in 0..cols {
+            let cell = row.get(c).map(String::as_str).unwrap_or("");
+            values.push(parse_number(cell).ok_or_else(|| {
+                format!(
+                    "Non-numeric cell '{}' at row {}, column {}",
+                    cell,
+                    r + 1,
+                    c + 1
+                )
+            })?);
+        }
+    }
+
+    let rows_len = rows.len();
+    Ok(dash().add_heatmap(|mut h| {
+        h = h
+            .data(rows_len, cols, values)
+            .colormap(colormap)
+            .show_values(show_values);
+        if let Some(labels) = row_labels {
+            h = h.row_labels(labels);
+        }
+        if let Some(labels) = col_labels.filter(|l| l.len() == cols) {
+            h = h.col_labels(labels);
+        }
+        match title {
+            Some(title) => h.title(title),
+            None => h,
+        }
+    }))
+}
+
+fn open(path: &Path) -> Result<(), String> {
+    let dashboard = Dashboard::load(path)?;
+    vidi_charts::runtime::run_dashboard(dashboard);
+    Ok(())
+}
+
+fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+}
+
+fn parse_number(cell: &str) -> Option<f32> {
+    cell.trim().parse::<f32>().ok().filter(|v| v.is_finite())
+}
+
+/// Read a file, or stdin for `-`
+fn read_input(path: &Path) -> Result<String, String> {
+    if path.as_os_str() == "-" {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| format!("Failed to read stdin: {}", e))?;
+        return Ok(text);
+    }
+    std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+fn parse_size(s: &str) -> Result<UVec2, String> {
+    let (w, h) = s
+        .split_once(['x', 'X'])
+        .ok_or("expected WIDTHxHEIGHT, e.g. 1280x960")?;
+    let parse = |v: &str| v.trim().parse::<u32>().map_err(|e| e.to_string());
+    Ok(UVec2::new(parse(w)?, parse(h)?))
+}
+
+fn parse_colormap(s: &str) -> Result<Colormap, String> {
+    Ok(match s.to_ascii_lowercase().as_str() {
+        "viridis" => Colormap::Viridis,
+        "plasma" => Colormap::Plasma,
+        "inferno" => Colormap::Inferno,
+        "magma" => Colormap::Magma,
+        "coolwarm" => Colormap::Coolwarm,
+        "rdbu" => Colormap::RdBu,
+        "blues" => Colormap::Blues,
+        "reds" => Colormap::Reds,
+        "greens" => Colormap::Greens,
+        other => return Err(format!("unknown colormap '{}'", other)),
+    })
+}
@@ -0,0 +1,251 @@
+//! `vidi tail`: follow a growing file and stream new rows into a live plot
+
+use std::fs::File;
+use std::io::{BufRead, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use glam::Vec2;
+use vidi_charts::dash::dash;
+
+use vidi_charts::data::{Format, RowReader};
+
+use crate::{InputArgs, PushArgs, SeriesArgs, parse_number, series_plot};
+
+/// Key of the streamed plot
+const PLOT_KEY: &str = "tail";
+
+/// How often a pushed, expiring dashboard has its TTL extended
+const TOUCH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// What a poll of the source found
+enum Poll {
+    /// Points to append, one list per y column
+    Append(Vec<Vec<Vec2>>),
+    /// The file was truncated or replaced; the plot starts over
+    Restart,
+    /// Stdin was closed
+    Closed,
+}
+
+pub fn run(
+    input: &InputArgs,
+    series: &SeriesArgs,
+    interval: u64,
+    push: &PushArgs,
+) -> Result<(), String> {
+    let interval = Duration::from_millis(interval.max(10));
+    let mut follower = Follower::open(input, series)?;
+
+    // Whatever the file already holds becomes the initial plot
+    let layers = match follower.poll()? {
+        Poll::Append(layers) => layers,
+        Poll::Restart | Poll::Closed => vec![Vec::new(); series.y.len()],
+    };
+    let builder = dash().add_2d(|p| series_plot(p, series, layers).key(PLOT_KEY));
+    let plot_id = builder
+        .plot_id(PLOT_KEY)
+        .ok_or("Streamed plot is missing its key")?;
+
+    if let Some(url) = &push.url {
+        let web = builder.run_web(url, push.config())?;
+        println!("{}", web.view_url);
+        let mut touched = Instant::now();
+        return follow(&mut follower, interval, |layer, points, replace| {
+            if !push.permanent && touched.elapsed() >= TOUCH_INTERVAL {
+                web.touch()?;
+                touched = Instant::now();
+            }
+            if replace {
+                web.replace_trace_2d(plot_id, layer, points)
+            } else {
+                web.append_points_2d(plot_id, layer, points)
+            }
+        });
+    }
+
+    builder.spawn_local(move |handle| {
+        let mut closed = false;
+        let result = follow(&mut follower, interval, |layer, points, replace| {
+            let sent = if replace {
+                handle.replace_trace_2d(plot_id, layer, points)
+            } else {
+                handle.append_points_2d(plot_id, layer, points)
+            };
+            closed = sent.is_err();
+            sent
+        });
+        // A closed window ends the stream; read errors are worth reporting
+        if let (Err(e), false) = (result, closed) {
+            eprintln!("vidi: {}", e);
+        }
+    });
+    Ok(())
+}
+
+/// Poll the source until it closes, sending `(layer, points, replace)` updates
+fn follow(
+    follower: &mut Follower,
+    interval: Duration,
+    mut send: impl FnMut(usize, &[(f32, f32)], bool) -> Result<(), String>,
+) -> Result<(), String> {
+    loop {
+        std::thread::sleep(interval);
+        match follower.poll()? {
+            Poll::Append(layers) => {
+                for (layer, points) in layers.iter().enumerate() {
+                    if !points.is_empty() {
+                        let points: Vec<(f32, f32)> = points.iter().map(|p| (p.x, p.y)).collect();
+                        send(layer, &points, false)?;
+                    }
+                }
+            }
+            Poll::Restart => {
+                for layer in 0..follower.y.len() {
+                    send(layer, &[], true)?;
+                }
+            }
+            Poll::Closed => return Ok(()),
+        }
+    }
+}
+
+/// Where new lines come from
+enum Source {
+    File {
+        path: PathBuf,
+        file: File,
+        offset: u64,
+    },
+    /// Lines read from stdin by a background thread
+    Stdin(mpsc::Receiver<String>),
+}
+
+/// Incremental reader turning appended lines into plot points
+struct Follower {
+    source: Source,
+    format: Format,
+    parser: RowReader,
+    /// Bytes after the last complete line
+    partial: Vec<u8>,
+    x: Option<String>,
+    y: Vec<String>,
+    /// Rows seen so far, the x value when no x column is given
+    rows: usize,
+}
+
+impl Follower {
+    fn open(input: &InputArgs, series: &SeriesArgs) -> Result<Self, String> {
+        let source = if input.path.as_os_str() == "-" {
+            let (tx, rx) = mpsc::channel();
+            std::thread::spawn(move || {
+                for line in std::io::stdin().lock().lines() {
+                    let Ok(line) = line else { break };
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+            Source::Stdin(rx)
+        } else {
+            Source::File {
+                file: open_file(&input.path)?,
+                path: input.path.clone(),
+                offset: 0,
+            }
+        };
+        Ok(Self {
+            source,
+            format: input.format(),
+            parser: RowReader::new(input.format()),
+            partial: Vec::new(),
+            x: series.x.clone(),
+            y: series.y.clone(),
+            rows: 0,
+        })
+    }
+
+    fn poll(&mut self) -> Result<Poll, String> {
+        let lines = match &mut self.source {
+            Source::Stdin(rx) => {
+                let mut lines: Vec<String> = rx.try_iter().collect();
+                if lines.is_empty() {
+                    // Distinguish "nothing yet" from "stdin closed"
+                    match rx.recv_timeout(Duration::ZERO) {
+                        Ok(line) => lines.push(line),
+                        Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(Poll::Closed),
+                        Err(mpsc::RecvTimeoutError::Timeout) => {}
+                    }
+                }
+                lines
+            }
+            Source::File { path, file, offset } => {
+                let len = std::fs::metadata(&*path)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+                    .len();
+                if len < *offset {
+                    // Truncated or rotated: start again from the top
+                    *file = open_file(path)?;
+                    *offset = 0;
+                    self.partial.clear();
+                    self.parser = RowReader::new(self.format);
+                    self.rows = 0;
+                    return Ok(Poll::Restart);
+                }
+
+                file.seek(SeekFrom::Start(*offset))
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                let read = file
+                    .read_to_end(&mut self.partial)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                *offset += read as u64;
+
+                // Only complete lines; a half-written one waits for the next poll
+                let Some(end) = self.partial.iter().rposition(|&b| b == b'\n') else {
+                    return Ok(Poll::Append(vec![Vec::new(); self.y.len()]));
+                };
+                let rest = self.partial.split_off(end + 1);
+                let complete = std::mem::replace(&mut self.partial, rest);
+                String::from_utf8_lossy(&complete)
+                    .lines()
+                    .map(str::to_string)
+                    .collect()
+            }
+        };
+
+        let mut layers = vec![Vec::new(); self.y.len()];
+        for line in lines {
+            let Some(row) = self.parser.push_line(&line)? else {
+                continue;
+            };
+            let x = match &self.x {
+                Some(x) => self.cell(&row, x)?,
+                None => Some(self.rows as f32),
+            };
+            self.rows += 1;
+            let Some(x) = x else { continue };
+            for (layer, y) in self.y.iter().enumerate() {
+                if let Some(y) = self.cell(&row, y)? {
+                    layers[layer].push(Vec2::new(x, y));
+                }
+            }
+        }
+        Ok(Poll::Append(layers))
+    }
+
+    /// Numeric value of a named column in a row
+    fn cell(&self, row: &[String], column: &str) -> Result<Option<f32>, String> {
+        let index = match self.parser.column_index(column) {
+            Ok(index) => index,
+            // JSON-lines columns can show up later in the stream
+            Err(_) if self.format == Format::JsonLines => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        Ok(row.get(index).and_then(|cell| parse_number(cell)))
+    }
+}
+
+fn open_file(path: &Path) -> Result<File, String> {
+    File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+}
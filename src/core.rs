@@ -1,6 +1,7 @@
 use crate::prelude::components::PlotId;
 use bevy_math::{UVec2, Vec2, Vec3};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 /// Common metadata for all plot types
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -9,6 +10,106 @@ pub struct PlotMeta {
     pub title: Option<String>,
     /// Optional description displayed below the title
     pub description: Option<String>,
+    /// Optional user-chosen key for addressing the plot instead of its numeric ID
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Where the plot sits in its dashboard's grid
+    #[serde(default)]
+    pub cell: GridCell,
+}
+
+/// Placement of a plot in the dashboard grid
+///
+/// Plots in a named area take its cells; plots with a `row` and/or `column`
+/// are pinned there; the rest fill the first free cells in reading order.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GridCell {
+    /// Name of a [`GridLayout::areas`] area to fill
+    pub area: Option<String>,
+    /// Zero-based row of the top edge
+    pub row: Option<usize>,
+    /// Zero-based column of the left edge
+    pub column: Option<usize>,
+    /// Number of rows covered
+    pub row_span: usize,
+    /// Number of columns covered
+    pub column_span: usize,
+}
+
+impl Default for GridCell {
+    fn default() -> Self {
+        Self {
+            area: None,
+            row: None,
+            column: None,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+}
+
+impl GridCell {
+    /// Fill a named area
+    pub fn area(name: impl Into<String>) -> Self {
+        Self {
+            area: Some(name.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Pin the top-left corner at a row and column
+    pub fn at(row: usize, column: usize) -> Self {
+        Self {
+            row: Some(row),
+            column: Some(column),
+            ..Self::default()
+        }
+    }
+
+    /// Cover `rows` x `columns` cells
+    pub fn span(mut self, rows: usize, columns: usize) -> Self {
+        self.row_span = rows.max(1);
+        self.column_span = columns.max(1);
+        self
+    }
+}
+
+/// Grid tracks and named areas for a dashboard or tab
+///
+/// Sizes are relative: `column_widths: [2.0, 1.0]` makes the first column
+/// twice as wide as the second. Tracks without a size get 1.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GridLayout {
+    /// Relative width of each column; also sets the column count
+    pub column_widths: Vec<f32>,
+    /// Relative height of each row
+    pub row_heights: Vec<f32>,
+    /// Rows of space-separated area names, as in CSS `grid-template-areas`,
+    /// e.g. `["main main stats", "main main log"]`; `.` leaves a cell empty
+    pub areas: Vec<String>,
+}
+
+impl GridLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn column_widths(mut self, widths: impl IntoIterator<Item = f32>) -> Self {
+        self.column_widths = widths.into_iter().collect();
+        self
+    }
+
+    pub fn row_heights(mut self, heights: impl IntoIterator<Item = f32>) -> Self {
+        self.row_heights = heights.into_iter().collect();
+        self
+    }
+
+    pub fn areas<S: Into<String>>(mut self, rows: impl IntoIterator<Item = S>) -> Self {
+        self.areas = rows.into_iter().map(Into::into).collect();
+        self
+    }
 }
 
 // #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -47,6 +148,21 @@ impl Color {
     pub const GREEN: Self = Self::rgb(0.0, 1.0, 0.0);
     pub const BLUE: Self = Self::rgb(0.0, 0.0, 1.0);
     pub const YELLOW: Self = Self::rgb(1.0, 1.0, 0.0);
+
+    /// Categorical palette for series without an explicit color (wraps after 8)
+    pub const fn palette(i: usize) -> Self {
+        const COLORS: [Color; 8] = [
+            Color::rgb(0.3, 0.6, 0.9),
+            Color::rgb(0.9, 0.4, 0.3),
+            Color::rgb(0.4, 0.8, 0.4),
+            Color::rgb(0.9, 0.7, 0.2),
+            Color::rgb(0.7, 0.4, 0.9),
+            Color::rgb(0.3, 0.8, 0.8),
+            Color::rgb(0.9, 0.5, 0.7),
+            Color::rgb(0.6, 0.6, 0.6),
+        ];
+        COLORS[i % COLORS.len()]
+    }
 }
 
 impl From<Color> for bevy::prelude::Color {
@@ -107,11 +223,249 @@ impl Default for Scale {
     }
 }
 
+/// How a 2D plot draws its axes, gridlines and tick labels
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AxesStyle {
+    /// Gridlines at every tick
+    pub major_grid: bool,
+    /// Fainter gridlines between ticks
+    pub minor_grid: bool,
+    pub spines: Spines,
+    pub x_ticks: TickSpec,
+    pub y_ticks: TickSpec,
+    /// Ticks of the secondary y axis
+    pub y2_ticks: TickSpec,
+}
+
+/// Where a 2D plot draws its axis lines (and with them its ticks)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Spines {
+    /// Axes through the data origin, kept in view at the tile edge
+    #[default]
+    Origin,
+    /// A frame around the plot area, ticks on its left and bottom sides
+    Box,
+    /// Left and bottom sides of the plot area only
+    LeftBottom,
+    /// No axis lines; ticks along the left and bottom of the plot area
+    Hidden,
+}
+
+/// Tick placement and labelling for one axis
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TickSpec {
+    /// Rough number of ticks to aim for (about 8 on x and 6 on y by default)
+    pub count: Option<usize>,
+    pub format: TickFormat,
+}
+
+/// How tick values are turned into labels
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TickFormat {
+    /// Integers as-is, decimals as needed, scientific for very large or
+    /// small values
+    #[default]
+    Auto,
+    /// SI prefixes: 1.2k, 3.4M, 5µ
+    Si,
+    /// Fractions as percentages: 0.25 becomes "25%"
+    Percent { decimals: usize },
+    /// Amounts with a currency symbol and thousands separators: "$1,234.50"
+    Currency { symbol: String, decimals: usize },
+    /// A fixed number of decimals
+    Fixed { decimals: usize },
+    /// Scientific notation: "1.5e3"
+    Scientific { decimals: usize },
+    /// Text with `{}` (automatic), `{:.N}` (N decimals) or `{:.Ne}`
+    /// (scientific) replaced by the value, e.g. `"{:.1} ms"`
+    Pattern(String),
+    /// A Rust closure; it is not serialized, so saved or streamed dashboards
+    /// fall back to `Auto`
+    Custom {
+        #[serde(skip)]
+        formatter: Option<TickFormatter>,
+    },
+}
+
+impl TickFormat {
+    /// Format ticks with a closure
+    pub fn custom(f: impl Fn(f32) -> String + Send + Sync + 'static) -> Self {
+        Self::Custom {
+            formatter: Some(TickFormatter(std::sync::Arc::new(f))),
+        }
+    }
+}
+
+/// Shared closure behind `TickFormat::Custom`
+#[derive(Clone)]
+pub struct TickFormatter(pub std::sync::Arc<dyn Fn(f32) -> String + Send + Sync>);
+
+impl std::fmt::Debug for TickFormatter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TickFormatter")
+    }
+}
+
+/// What the hover tooltip of a 2D plot reports
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HoverMode {
+    /// The point nearest the cursor in data units
+    #[default]
+    Nearest,
+    /// The point nearest the cursor on screen, whatever the axis scales
+    NearestScreen,
+    /// Every layer's value at the cursor x in one tooltip: interpolated on
+    /// lines and areas, the nearest point otherwise
+    XUnified,
+}
+
+/// A marker drawn over a 2D or candlestick plot, such as an event line or a
+/// threshold band; positions in data units pan and zoom with the data
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Annotation {
+    #[serde(flatten)]
+    pub shape: AnnotationShape,
+    /// Caption; for `Text` this is the text itself
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Line and text color; spans are filled with a faint version of it
+    #[serde(default = "Annotation::default_color")]
+    pub color: Color,
+}
+
+/// Geometry of an annotation, in primary-axis data units unless a
+/// [`Position`] says otherwise
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnnotationShape {
+    /// Horizontal reference line across the plot
+    #[serde(rename = "hline")]
+    HLine { y: f32 },
+    /// Vertical reference line across the plot
+    #[serde(rename = "vline")]
+    VLine { x: f32 },
+    /// Shaded band between two x values
+    XSpan { x0: f32, x1: f32 },
+    /// Shaded band between two y values
+    YSpan { y0: f32, y1: f32 },
+    /// The label on its own
+    Text { at: Position },
+    /// Arrow pointing at `to`, labelled at its tail
+    Arrow { from: Position, to: Position },
+}
+
+/// A point on a plot, either in data units or as a fraction of the tile
+/// (`0, 0` bottom-left, `1, 1` top-right) that stays put while panning
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Position {
+    Data { x: f32, y: f32 },
+    Screen { x: f32, y: f32 },
+}
+
+impl Position {
+    pub const fn data(x: f32, y: f32) -> Self {
+        Self::Data { x, y }
+    }
+
+    pub const fn screen(x: f32, y: f32) -> Self {
+        Self::Screen { x, y }
+    }
+}
+
+impl From<(f32, f32)> for Position {
+    fn from((x, y): (f32, f32)) -> Self {
+        Self::Data { x, y }
+    }
+}
+
+impl Annotation {
+    fn default_color() -> Color {
+        Color::rgba(0.95, 0.75, 0.3, 0.9)
+    }
+
+    fn new(shape: AnnotationShape) -> Self {
+        Self {
+            shape,
+            label: None,
+            color: Self::default_color(),
+        }
+    }
+
+    /// Horizontal line at `y`
+    pub fn hline(y: f32) -> Self {
+        Self::new(AnnotationShape::HLine { y })
+    }
+
+    /// Vertical line at `x`
+    pub fn vline(x: f32) -> Self {
+        Self::new(AnnotationShape::VLine { x })
+    }
+
+    /// Shaded band from `x0` to `x1`
+    pub fn x_span(x0: f32, x1: f32) -> Self {
+        Self::new(AnnotationShape::XSpan { x0, x1 })
+    }
+
+    /// Shaded band from `y0` to `y1`
+    pub fn y_span(y0: f32, y1: f32) -> Self {
+        Self::new(AnnotationShape::YSpan { y0, y1 })
+    }
+
+    /// Text centered on `at`
+    pub fn text(text: impl Into<String>, at: impl Into<Position>) -> Self {
+        Self::new(AnnotationShape::Text { at: at.into() }).label(text)
+    }
+
+    /// Arrow from `from` to `to`
+    pub fn arrow(from: impl Into<Position>, to: impl Into<Position>) -> Self {
+        Self::new(AnnotationShape::Arrow {
+            from: from.into(),
+            to: to.into(),
+        })
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+/// How a plot responds to the mouse and keyboard
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Interaction {
     pub pan: bool,
     pub zoom: bool,
     pub rotate: bool,
+    /// Furthest zoom out, relative to the fitted view (2D default 0.5,
+    /// 3D default allows a camera distance of 50)
+    #[serde(deserialize_with = "zoom_limit")]
+    pub min_zoom: Option<f32>,
+    /// Furthest zoom in, relative to the fitted view (2D default 4.0,
+    /// 3D default allows a camera distance of 2)
+    #[serde(deserialize_with = "zoom_limit")]
+    pub max_zoom: Option<f32>,
+    /// Multiplier for the zoom applied per scroll-wheel step
+    pub wheel_sensitivity: f32,
+    /// Axis kept fixed while panning and zooming (2D only)
+    pub lock: Option<AxisLock>,
+    /// Keyboard shortcuts on the hovered plot: arrows pan, +/- zoom,
+    /// `r` resets the view and `f` fits it to the data
+    pub keyboard: bool,
+    /// Select points by clicking, Ctrl-dragging a box or Alt-dragging a
+    /// lasso (2D and 3D point layers)
+    pub select: bool,
 }
 
 impl Default for Interaction {
@@ -120,11 +474,71 @@ impl Default for Interaction {
             pan: true,
             zoom: true,
             rotate: true,
+            min_zoom: None,
+            max_zoom: None,
+            wheel_sensitivity: 1.0,
+            lock: None,
+            keyboard: true,
+            select: true,
         }
     }
 }
 
+impl Interaction {
+    /// Set both zoom limits, swapping them if given in the wrong order;
+    /// a limit that is not positive and finite falls back to the default
+    pub(crate) fn set_zoom_limits(&mut self, min: f32, max: f32) {
+        let valid = |zoom: f32| Some(zoom).filter(|z| z.is_finite() && *z > 0.0);
+        self.min_zoom = valid(min.min(max));
+        self.max_zoom = valid(min.max(max));
+    }
+
+    /// Furthest zoom out and in, with `default` filling unset limits and
+    /// the pair always in order
+    pub(crate) fn zoom_range(&self, default: (f32, f32)) -> (f32, f32) {
+        let valid = |zoom: Option<f32>| zoom.filter(|z| z.is_finite() && *z > 0.0);
+        let min = valid(self.min_zoom).unwrap_or(default.0);
+        let max = valid(self.max_zoom).unwrap_or(default.1);
+        (min.min(max), min.max(max))
+    }
+}
+
+/// Reject zoom limits that are not positive and finite
+fn zoom_limit<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<f32>, D::Error> {
+    let zoom = Option::<f32>::deserialize(deserializer)?;
+    match zoom {
+        Some(z) if !(z.is_finite() && z > 0.0) => Err(serde::de::Error::custom(format!(
+            "zoom limit must be positive and finite, got {z}"
+        ))),
+        zoom => Ok(zoom),
+    }
+}
+
+/// Points picked on a plot by clicking, box or lasso selection
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Selection {
+    pub plot_id: u64,
+    /// Selected points of each layer that has any; empty once the selection
+    /// is cleared
+    pub layers: Vec<SelectedPoints>,
+}
+
+/// Indices into one layer's points
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectedPoints {
+    pub layer: usize,
+    pub indices: Vec<usize>,
+}
+
+/// An axis of a 2D plot that can be locked against panning and zooming
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AxisLock {
+    X,
+    Y,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
 pub enum Plot {
     Graph2D(Graph2D),
     Graph3D(Graph3D),
@@ -135,6 +549,90 @@ pub enum Plot {
     Heatmap(Heatmap),
 }
 
+impl Plot {
+    /// Stable identifier of this plot
+    pub fn id(&self) -> PlotId {
+        match self {
+            Plot::Graph2D(g) => g.id,
+            Plot::Graph3D(g) => g.id,
+            Plot::Distribution(d) => match d {
+                Distribution::Histogram { id, .. }
+                | Distribution::Pdf { id, .. }
+                | Distribution::BoxPlot { id, .. }
+                | Distribution::ECDF { id, .. } => *id,
+            },
+            Plot::Field(f) => f.id,
+            Plot::Radial(r) => match r {
+                Radial::Pie { id, .. } | Radial::Radar { id, .. } => *id,
+            },
+            Plot::Candlestick(c) => c.id,
+            Plot::Heatmap(h) => h.id,
+        }
+    }
+
+    /// Pan/zoom/rotate settings; plots without their own use the defaults
+    pub fn interaction(&self) -> Interaction {
+        match self {
+            Plot::Graph2D(g) => g.interaction,
+            Plot::Graph3D(g) => g.interaction,
+            _ => Interaction::default(),
+        }
+    }
+
+    /// Common metadata (title, description, key)
+    pub fn meta(&self) -> &PlotMeta {
+        match self {
+            Plot::Graph2D(g) => &g.meta,
+            Plot::Graph3D(g) => &g.meta,
+            Plot::Distribution(d) => match d {
+                Distribution::Histogram { meta, .. }
+                | Distribution::Pdf { meta, .. }
+                | Distribution::BoxPlot { meta, .. }
+                | Distribution::ECDF { meta, .. } => meta,
+            },
+            Plot::Field(f) => &f.meta,
+            Plot::Radial(r) => match r {
+                Radial::Pie { meta, .. } | Radial::Radar { meta, .. } => meta,
+            },
+            Plot::Candlestick(c) => &c.meta,
+            Plot::Heatmap(h) => &h.meta,
+        }
+    }
+
+    /// Mutable access to the common metadata
+    pub fn meta_mut(&mut self) -> &mut PlotMeta {
+        match self {
+            Plot::Graph2D(g) => &mut g.meta,
+            Plot::Graph3D(g) => &mut g.meta,
+            Plot::Distribution(d) => d.meta_mut(),
+            Plot::Field(f) => &mut f.meta,
+            Plot::Radial(r) => r.meta_mut(),
+            Plot::Candlestick(c) => &mut c.meta,
+            Plot::Heatmap(h) => &mut h.meta,
+        }
+    }
+
+    /// Change the identifier of this plot (e.g. when copying it into another dashboard)
+    pub fn set_id(&mut self, new_id: PlotId) {
+        match self {
+            Plot::Graph2D(g) => g.id = new_id,
+            Plot::Graph3D(g) => g.id = new_id,
+            Plot::Distribution(d) => match d {
+                Distribution::Histogram { id, .. }
+                | Distribution::Pdf { id, .. }
+                | Distribution::BoxPlot { id, .. }
+                | Distribution::ECDF { id, .. } => *id = new_id,
+            },
+            Plot::Field(f) => f.id = new_id,
+            Plot::Radial(r) => match r {
+                Radial::Pie { id, .. } | Radial::Radar { id, .. } => *id = new_id,
+            },
+            Plot::Candlestick(c) => c.id = new_id,
+            Plot::Heatmap(h) => h.id = new_id,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Graph2D {
     pub id: PlotId,
@@ -145,6 +643,54 @@ pub struct Graph2D {
     pub interaction: Interaction,
     pub x_label: Option<String>,
     pub y_label: Option<String>,
+    /// Link group for the x axis: plots in the same group pan and zoom
+    /// their x axis together and share the hover cursor
+    #[serde(default)]
+    pub link_x: Option<String>,
+    /// Link group for the y axis
+    #[serde(default)]
+    pub link_y: Option<String>,
+    /// Selection link group: selecting points here selects the same point
+    /// indices on other plots in the group
+    #[serde(default)]
+    pub link_selection: Option<String>,
+    /// Fixed x-axis bounds `[min, max]`; a `None` bound is fitted to the data
+    #[serde(default)]
+    pub x_range: [Option<f32>; 2],
+    /// Fixed y-axis bounds `[min, max]`; a `None` bound is fitted to the data
+    #[serde(default)]
+    pub y_range: [Option<f32>; 2],
+    /// Share of the tile left empty on each side of the fitted data (axes
+    /// with both bounds fixed are not padded)
+    #[serde(default = "Graph2D::default_padding")]
+    pub padding: f32,
+    /// Draw x increasing to the left
+    #[serde(default)]
+    pub invert_x: bool,
+    /// Draw y increasing downwards
+    #[serde(default)]
+    pub invert_y: bool,
+    /// Keep one data unit the same length on both axes (for spatial data)
+    #[serde(default)]
+    pub equal_aspect: bool,
+    /// Fit the view again whenever live updates change the data
+    #[serde(default)]
+    pub refit_on_update: bool,
+    /// Secondary (right) y-axis label
+    #[serde(default)]
+    pub y2_label: Option<String>,
+    /// Fixed secondary y-axis bounds `[min, max]`
+    #[serde(default)]
+    pub y2_range: [Option<f32>; 2],
+    /// Gridlines, spines and tick labels
+    #[serde(default)]
+    pub axes: AxesStyle,
+    /// Reference lines, spans, text and arrows drawn over the data
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    /// What the hover tooltip shows
+    #[serde(default)]
+    pub hover: HoverMode,
 }
 
 impl Default for Graph2D {
@@ -158,11 +704,35 @@ impl Default for Graph2D {
             interaction: Interaction::default(),
             x_label: None,
             y_label: None,
+            link_x: None,
+            link_y: None,
+            link_selection: None,
+            x_range: [None, None],
+            y_range: [None, None],
+            padding: Self::default_padding(),
+            invert_x: false,
+            invert_y: false,
+            equal_aspect: false,
+            refit_on_update: false,
+            y2_label: None,
+            y2_range: [None, None],
+            axes: AxesStyle::default(),
+            annotations: Vec::new(),
+            hover: HoverMode::default(),
         }
     }
 }
 
 impl Graph2D {
+    fn default_padding() -> f32 {
+        0.075
+    }
+
+    /// Whether any layer is plotted against the secondary y axis
+    pub fn has_secondary_y(&self) -> bool {
+        self.layers.iter().any(|l| l.y_axis == YAxis::Secondary)
+    }
+
     pub fn new() -> Self {
         Self::default()
     }
@@ -224,6 +794,12 @@ pub struct Layer2D {
     pub lower_line: Option<Vec<Vec2>>,
     /// For bubble charts: size of each point (optional, uses style.size if None)
     pub sizes: Option<Vec<f32>>,
+    /// Name shown in the plot's legend (unlabelled layers are left out)
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Y axis the layer is plotted against
+    #[serde(default)]
+    pub y_axis: YAxis,
 }
 
 impl Layer2D {
@@ -234,10 +810,22 @@ impl Layer2D {
             style: Style::default(),
             lower_line: None,
             sizes: None,
+            label: None,
+            y_axis: YAxis::Primary,
         }
     }
 }
 
+/// Y axis of a 2D plot: the primary one on the left, or a secondary one on
+/// the right with its own range
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum YAxis {
+    #[default]
+    Primary,
+    Secondary,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Graph3D {
     pub id: PlotId,
@@ -250,6 +838,9 @@ pub struct Graph3D {
     pub y_label: Option<String>,
     /// Z-axis label (e.g., "Momentum")
     pub z_label: Option<String>,
+    /// Selection link group, as for 2D plots
+    #[serde(default)]
+    pub link_selection: Option<String>,
 }
 
 impl Default for Graph3D {
@@ -265,6 +856,7 @@ impl Default for Graph3D {
             x_label: None,
             y_label: None,
             z_label: None,
+            link_selection: None,
         }
     }
 }
@@ -286,11 +878,16 @@ pub struct Layer3D {
     pub geometry: Geometry3D,
     pub xyz: Vec<Vec3>,
     pub style: Style,
+    /// Name shown in the plot's legend (unlabelled layers are left out)
+    #[serde(default)]
+    pub label: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Distribution {
     Histogram {
+        #[serde(default)]
+        id: PlotId,
         meta: PlotMeta,
         values: Vec<f32>,
         bins: usize,
@@ -299,6 +896,8 @@ pub enum Distribution {
         y_label: Option<String>,
     },
     Pdf {
+        #[serde(default)]
+        id: PlotId,
         meta: PlotMeta,
         values: Vec<f32>,
         style: Style,
@@ -306,6 +905,8 @@ pub enum Distribution {
         y_label: Option<String>,
     },
     BoxPlot {
+        #[serde(default)]
+        id: PlotId,
         meta: PlotMeta,
         /// Each group is (label, values)
         groups: Vec<(String, Vec<f32>)>,
@@ -315,6 +916,8 @@ pub enum Distribution {
     },
     /// Empirical Cumulative Distribution Function (step function)
     ECDF {
+        #[serde(default)]
+        id: PlotId,
         meta: PlotMeta,
         values: Vec<f32>,
         style: Style,
@@ -323,10 +926,24 @@ pub enum Distribution {
     },
 }
 
+impl Distribution {
+    /// Mutable access to the common metadata
+    pub(crate) fn meta_mut(&mut self) -> &mut PlotMeta {
+        match self {
+            Distribution::Histogram { meta, .. }
+            | Distribution::Pdf { meta, .. }
+            | Distribution::BoxPlot { meta, .. }
+            | Distribution::ECDF { meta, .. } => meta,
+        }
+    }
+}
+
 // Field plots (heatmap, image, tensors)
 // heatmaps, attention matrices, correlation matrices, scalar fields
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Field {
+    #[serde(default)]
+    pub id: PlotId,
     pub meta: PlotMeta,
     pub dims: UVec2,      // (nx, ny)
     pub values: Vec<f32>, // nx*ny
@@ -337,10 +954,14 @@ pub struct Field {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Radial {
     Pie {
+        #[serde(default)]
+        id: PlotId,
         meta: PlotMeta,
         slices: Vec<(String, f32)>, // label, value
     },
     Radar {
+        #[serde(default)]
+        id: PlotId,
         meta: PlotMeta,
         axes: Vec<String>, // axis labels
         values: Vec<f32>,  // values for each axis (0-1 normalized)
@@ -348,15 +969,29 @@ pub enum Radial {
     },
 }
 
+impl Radial {
+    /// Mutable access to the common metadata
+    pub(crate) fn meta_mut(&mut self) -> &mut PlotMeta {
+        match self {
+            Radial::Pie { meta, .. } | Radial::Radar { meta, .. } => meta,
+        }
+    }
+}
+
 /// OHLC candlestick data for financial time series
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Candlestick {
+    #[serde(default)]
+    pub id: PlotId,
     pub meta: PlotMeta,
     pub candles: Vec<OHLC>,
     pub up_color: Color,
     pub down_color: Color,
     pub x_label: Option<String>,
     pub y_label: Option<String>,
+    /// Reference lines, spans, text and arrows drawn over the candles
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
 }
 
 /// Single OHLC candle
@@ -384,6 +1019,8 @@ impl OHLC {
 /// 2D heatmap with labeled axes
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Heatmap {
+    #[serde(default)]
+    pub id: PlotId,
     pub meta: PlotMeta,
     pub dims: UVec2,       // (cols, rows)
     pub values: Vec<f32>,  // row-major: values[row * cols + col]
@@ -498,6 +1135,9 @@ pub struct Tab {
     pub name: String,
     pub plots: Vec<Plot>,
     pub columns: Option<usize>,
+    /// Grid tracks and areas for this tab (default: the dashboard's)
+    #[serde(default)]
+    pub layout: Option<GridLayout>,
 }
 
 impl Tab {
@@ -506,6 +1146,7 @@ impl Tab {
             name: name.into(),
             plots: vec![],
             columns: None,
+            layout: None,
         }
     }
 }
@@ -517,6 +1158,9 @@ pub struct Dashboard {
     pub plots: Vec<Plot>,
     /// Number of columns per row (default: auto based on plot count)
     pub columns: Option<usize>,
+    /// Grid tracks and areas (default: equal tiles)
+    #[serde(default)]
+    pub layout: Option<GridLayout>,
     /// Tabs (alternative to direct plots)
     pub tabs: Vec<Tab>,
     /// Currently active tab index
@@ -529,6 +1173,7 @@ impl Default for Dashboard {
             background: Color::rgba(0.05, 0.05, 0.09, 1.0),
             plots: vec![],
             columns: None,
+            layout: None,
             tabs: vec![],
             active_tab: 0,
         }
@@ -553,6 +1198,18 @@ impl Dashboard {
         }
     }
 
+    /// Get the grid layout for the active view
+    pub fn active_layout(&self) -> Option<&GridLayout> {
+        if self.has_tabs() {
+            self.tabs
+                .get(self.active_tab)
+                .and_then(|t| t.layout.as_ref())
+                .or(self.layout.as_ref())
+        } else {
+            self.layout.as_ref()
+        }
+    }
+
     /// Get the columns setting for the active view
     pub fn active_columns(&self) -> Option<usize> {
         if self.has_tabs() {
@@ -569,4 +1226,210 @@ impl Dashboard {
     pub fn tab_names(&self) -> Vec<&str> {
         self.tabs.iter().map(|t| t.name.as_str()).collect()
     }
+
+    /// Find a plot by ID, searching direct plots and all tabs
+    pub fn plot(&self, plot_id: u64) -> Option<&Plot> {
+        self.plots
+            .iter()
+            .chain(self.tabs.iter().flat_map(|t| t.plots.iter()))
+            .find(|p| p.id().0 == plot_id)
+    }
+
+    /// Find a plot by ID, searching direct plots and all tabs
+    pub fn plot_mut(&mut self, plot_id: u64) -> Option<&mut Plot> {
+        self.plots
+            .iter_mut()
+            .chain(self.tabs.iter_mut().flat_map(|t| t.plots.iter_mut()))
+            .find(|p| p.id().0 == plot_id)
+    }
+
+    /// Find the ID of the plot with the given user key
+    pub fn plot_id_by_key(&self, key: &str) -> Option<u64> {
+        self.plots
+            .iter()
+            .chain(self.tabs.iter().flat_map(|t| t.plots.iter()))
+            .find(|p| p.meta().key.as_deref() == Some(key))
+            .map(|p| p.id().0)
+    }
+
+    /// Reassign duplicate plot IDs (e.g. from hand-written JSON) so every plot is addressable
+    pub fn ensure_unique_ids(&mut self) {
+        let mut taken: HashSet<PlotId> = self
+            .plots
+            .iter()
+            .chain(self.tabs.iter().flat_map(|t| t.plots.iter()))
+            .map(|p| p.id())
+            .collect();
+        let mut seen = HashSet::new();
+
+        for plot in self
+            .plots
+            .iter_mut()
+            .chain(self.tabs.iter_mut().flat_map(|t| t.plots.iter_mut()))
+        {
+            if seen.insert(plot.id()) {
+                continue;
+            }
+            let fresh = std::iter::repeat_with(PlotId::new)
+                .find(|id| !taken.contains(id))
+                .unwrap();
+            taken.insert(fresh);
+            seen.insert(fresh);
+            plot.set_id(fresh);
+        }
+    }
+
+    /// Apply a live update to this dashboard
+    pub fn apply_update(&mut self, cmd: UpdateCommand) -> Result<(), String> {
+        match cmd {
+            UpdateCommand::AppendPoints2D {
+                plot_id,
+                layer_idx,
+                points,
+            } => {
+                let layer = self.layer_2d_mut(plot_id, layer_idx)?;
+                layer
+                    .xy
+                    .extend(points.iter().map(|p| Vec2::new(p[0], p[1])));
+            }
+            UpdateCommand::AppendPoints3D {
+                plot_id,
+                layer_idx,
+                points,
+            } => {
+                let layer = self.layer_3d_mut(plot_id, layer_idx)?;
+                layer
+                    .xyz
+                    .extend(points.iter().map(|p| Vec3::new(p[0], p[1], p[2])));
+            }
+            UpdateCommand::ReplaceTrace2D {
+                plot_id,
+                layer_idx,
+                points,
+            } => {
+                let layer = self.layer_2d_mut(plot_id, layer_idx)?;
+                layer.xy = points.iter().map(|p| Vec2::new(p[0], p[1])).collect();
+            }
+            UpdateCommand::ReplaceTrace3D {
+                plot_id,
+                layer_idx,
+                points,
+            } => {
+                let layer = self.layer_3d_mut(plot_id, layer_idx)?;
+                layer.xyz = points.iter().map(|p| Vec3::new(p[0], p[1], p[2])).collect();
+            }
+            UpdateCommand::AddAnnotation {
+                plot_id,
+                annotation,
+            } => match self.plot_mut(plot_id) {
+                Some(Plot::Graph2D(g)) => g.annotations.push(annotation),
+                Some(Plot::Candlestick(c)) => c.annotations.push(annotation),
+                Some(_) => return Err(format!("Plot {} does not take annotations", plot_id)),
+                None => return Err(format!("Plot {} not found", plot_id)),
+            },
+            UpdateCommand::UpdatePlot { plot_id, mut plot } => {
+                let existing = self
+                    .plot_mut(plot_id)
+                    .ok_or_else(|| format!("Plot {} not found", plot_id))?;
+                // Keep the replacement addressable under the same ID
+                plot.set_id(PlotId(plot_id));
+                *existing = plot;
+            }
+            UpdateCommand::RefreshAll { dashboard } => {
+                *self = dashboard;
+                self.ensure_unique_ids();
+            }
+        }
+        Ok(())
+    }
+
+    fn layer_2d_mut(&mut self, plot_id: u64, layer_idx: usize) -> Result<&mut Layer2D, String> {
+        match self.plot_mut(plot_id) {
+            Some(Plot::Graph2D(g)) => g
+                .layers
+                .get_mut(layer_idx)
+                .ok_or_else(|| format!("Layer {} not found", layer_idx)),
+            Some(_) => Err(format!("Plot {} is not a 2D graph", plot_id)),
+            None => Err(format!("Plot {} not found", plot_id)),
+        }
+    }
+
+    fn layer_3d_mut(&mut self, plot_id: u64, layer_idx: usize) -> Result<&mut Layer3D, String> {
+        match self.plot_mut(plot_id) {
+            Some(Plot::Graph3D(g)) => g
+                .layers
+                .get_mut(layer_idx)
+                .ok_or_else(|| format!("Layer {} not found", layer_idx)),
+            Some(_) => Err(format!("Plot {} is not a 3D graph", plot_id)),
+            None => Err(format!("Plot {} not found", plot_id)),
+        }
+    }
+}
+
+/// Live mutation of a dashboard, shared by the local handle, the WASM API
+/// and vidi-server's streaming endpoints
+// Whole-plot variants are rare next to point appends; boxing them is not
+// worth the noisier API
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UpdateCommand {
+    /// Append points to a 2D layer
+    #[serde(rename = "append_points_2d")]
+    AppendPoints2D {
+        plot_id: u64,
+        layer_idx: usize,
+        points: Vec<[f32; 2]>,
+    },
+
+    /// Append points to a 3D layer
+    #[serde(rename = "append_points_3d")]
+    AppendPoints3D {
+        plot_id: u64,
+        layer_idx: usize,
+        points: Vec<[f32; 3]>,
+    },
+
+    /// Replace all points in a 2D layer
+    #[serde(rename = "replace_trace_2d")]
+    ReplaceTrace2D {
+        plot_id: u64,
+        layer_idx: usize,
+        points: Vec<[f32; 2]>,
+    },
+
+    /// Replace all points in a 3D layer
+    #[serde(rename = "replace_trace_3d")]
+    ReplaceTrace3D {
+        plot_id: u64,
+        layer_idx: usize,
+        points: Vec<[f32; 3]>,
+    },
+
+    /// Add an annotation to a 2D or candlestick plot
+    AddAnnotation {
+        plot_id: u64,
+        annotation: Annotation,
+    },
+
+    /// Update an entire plot
+    UpdatePlot { plot_id: u64, plot: Plot },
+
+    /// Replace the entire dashboard
+    RefreshAll { dashboard: Dashboard },
+}
+
+impl UpdateCommand {
+    /// ID of the plot this command targets (`None` for a full refresh)
+    pub fn plot_id(&self) -> Option<u64> {
+        match self {
+            UpdateCommand::AppendPoints2D { plot_id, .. }
+            | UpdateCommand::AppendPoints3D { plot_id, .. }
+            | UpdateCommand::ReplaceTrace2D { plot_id, .. }
+            | UpdateCommand::ReplaceTrace3D { plot_id, .. }
+            | UpdateCommand::AddAnnotation { plot_id, .. }
+            | UpdateCommand::UpdatePlot { plot_id, .. } => Some(*plot_id),
+            UpdateCommand::RefreshAll { .. } => None,
+        }
+    }
 }
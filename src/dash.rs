@@ -1,7 +1,13 @@
 use crate::core::{
-    Candlestick, Color, Colormap, Dashboard, Distribution, Field, Geometry2D, Geometry3D, Graph2D,
-    Graph3D, Heatmap, Layer2D, Layer3D, OHLC, Plot, PlotMeta, Radial, Style, Tab,
+    Annotation, AxesStyle, AxisLock, Candlestick, Color, Colormap, Dashboard, Distribution, Field,
+    Geometry2D, Geometry3D, Graph2D, Graph3D, GridCell, GridLayout, Heatmap, HoverMode,
+    Interaction, Layer2D, Layer3D, OHLC, Plot, PlotMeta, Radial, Spines, Style, Tab, TickFormat,
+    YAxis,
 };
+#[cfg(not(target_arch = "wasm32"))]
+use crate::core::{Selection, UpdateCommand};
+use crate::data::Table;
+use crate::render::components::PlotId;
 use bevy_math::{UVec2, Vec2, Vec3};
 
 pub fn dash() -> DashBuilder {
@@ -26,6 +32,12 @@ impl DashBuilder {
         self
     }
 
+    /// Set relative column widths, row heights and named areas for the grid
+    pub fn layout(mut self, layout: GridLayout) -> Self {
+        self.dash.layout = Some(layout);
+        self
+    }
+
     pub fn add_2d<F>(mut self, f: F) -> Self
     where
         F: FnOnce(Plot2DBuilder) -> Plot2DBuilder,
@@ -55,6 +67,7 @@ impl DashBuilder {
 
     pub fn add_field(mut self, dims: UVec2, values: Vec<f32>, vmin: f32, vmax: f32) -> Self {
         self.dash.plots.push(Plot::Field(Field {
+            id: PlotId::new(),
             meta: PlotMeta::default(),
             dims,
             values,
@@ -106,12 +119,87 @@ impl DashBuilder {
         self.dash
     }
 
+    /// Look up the ID of a plot by the key given to its builder
+    pub fn plot_id(&self, key: &str) -> Option<u64> {
+        self.dash.plot_id_by_key(key)
+    }
+
     /// Run the dashboard locally using Bevy (native only)
     #[cfg(not(target_arch = "wasm32"))]
     pub fn run_local(self) {
         crate::runtime::run_dashboard(self.dash);
     }
 
+    /// Run the dashboard locally while `producer` streams live updates into it
+    ///
+    /// The window's event loop must own the main thread, so `producer` is run on a
+    /// background thread with a [`LocalDashboardHandle`] connected to the app.
+    /// This call blocks until the window is closed.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn_local<F>(self, producer: F)
+    where
+        F: FnOnce(LocalDashboardHandle) + Send + 'static,
+    {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let updates = crate::render::LiveUpdates::new(receiver);
+        let handle = LocalDashboardHandle {
+            sender,
+            listeners: updates.on_select.clone(),
+        };
+        std::thread::spawn(move || producer(handle));
+        crate::runtime::run_dashboard_live(self.dash, updates);
+    }
+
+    /// Render the dashboard offscreen and write it to `path` as a PNG (native only)
+    ///
+    /// No window is opened; see [`crate::runtime::render_dashboard_png`].
+    /// `size` is the image size in pixels.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_png(&self, path: impl AsRef<std::path::Path>, size: UVec2) -> Result<(), String> {
+        let png = crate::runtime::render_dashboard_png(self.dash.clone(), size)?;
+        std::fs::write(path.as_ref(), png)
+            .map_err(|e| format!("Failed to write {}: {}", path.as_ref().display(), e))
+    }
+
+    /// Export the dashboard as an SVG file
+    ///
+    /// See [`crate::export::render_dashboard_svg`]. `size` is the page size in pixels.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_svg(&self, path: impl AsRef<std::path::Path>, size: UVec2) -> Result<(), String> {
+        let svg = crate::export::render_dashboard_svg(&self.dash, size)?;
+        std::fs::write(path.as_ref(), svg)
+            .map_err(|e| format!("Failed to write {}: {}", path.as_ref().display(), e))
+    }
+
+    /// Export the dashboard as a single-page PDF file
+    ///
+    /// See [`crate::export::render_dashboard_pdf`]. `size` is the page size in points.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_pdf(&self, path: impl AsRef<std::path::Path>, size: UVec2) -> Result<(), String> {
+        let pdf = crate::export::render_dashboard_pdf(&self.dash, size)?;
+        std::fs::write(path.as_ref(), pdf)
+            .map_err(|e| format!("Failed to write {}: {}", path.as_ref().display(), e))
+    }
+
+    /// Export the dashboard as a single self-contained HTML file
+    ///
+    /// The page embeds the WASM viewer and opens offline with full pan and zoom.
+    /// The viewer is read from `$VIDI_VIEWER_DIR`, or from `vidi-server/wasm`
+    /// (built by `scripts/build-wasm.sh`); see [`crate::export::render_dashboard_html`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_html(&self, path: impl AsRef<std::path::Path>) -> Result<(), String> {
+        let viewer = crate::export::Viewer::locate()?;
+        let html = crate::export::render_dashboard_html(&self.dash, &viewer)?;
+        std::fs::write(path.as_ref(), html)
+            .map_err(|e| format!("Failed to write {}: {}", path.as_ref().display(), e))
+    }
+
+    /// Save the dashboard to a versioned file; see [`Dashboard::save`]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), String> {
+        self.dash.save(path)
+    }
+
     /// Deprecated: Use `run_local()` instead
     #[cfg(not(target_arch = "wasm32"))]
     #[deprecated(since = "0.2.0", note = "Use `run_local()` instead")]
@@ -329,6 +417,25 @@ impl WebDashboard {
         Ok(())
     }
 
+    /// Add an annotation to a 2D or candlestick plot
+    pub fn add_annotation(&self, plot_id: u64, annotation: Annotation) -> Result<(), String> {
+        let url = format!("{}/api/v1/dashboards/{}/update", self.server_url, self.id);
+
+        let body_str = serde_json::to_string(&UpdateCommand::AddAnnotation {
+            plot_id,
+            annotation,
+        })
+        .map_err(|e| format!("Failed to serialize payload: {}", e))?;
+
+        self.client
+            .post(&url)
+            .content_type("application/json")
+            .send(body_str.as_bytes())
+            .map_err(|e| format!("Failed to add annotation: {}", e))?;
+
+        Ok(())
+    }
+
     /// Delete the dashboard from the server
     pub fn delete(self) -> Result<(), String> {
         let url = format!("{}/api/v1/dashboards/{}", self.server_url, self.id);
@@ -354,6 +461,122 @@ impl WebDashboard {
     }
 }
 
+/// Handle to a dashboard started with [`DashBuilder::spawn_local`] for real-time updates
+///
+/// Cloneable and usable from any thread. Sends fail once the window has been closed.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone)]
+pub struct LocalDashboardHandle {
+    sender: std::sync::mpsc::Sender<UpdateCommand>,
+    listeners: crate::render::SelectionListeners,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl LocalDashboardHandle {
+    /// Send a raw update command to the running dashboard
+    pub fn send(&self, cmd: UpdateCommand) -> Result<(), String> {
+        self.sender
+            .send(cmd)
+            .map_err(|_| "Dashboard window has been closed".to_string())
+    }
+
+    /// Call `callback` whenever points are selected on a plot, or a
+    /// selection is cleared
+    ///
+    /// Runs on the render thread, so it should return quickly; send the
+    /// selection elsewhere for heavy work. Returns an ID for
+    /// [`Self::remove_select_listener`].
+    pub fn on_select(&self, callback: impl FnMut(&Selection) + Send + 'static) -> u64 {
+        self.listeners.add(callback)
+    }
+
+    /// Stop calling a callback registered with [`Self::on_select`]; returns
+    /// whether it was registered
+    pub fn remove_select_listener(&self, id: u64) -> bool {
+        self.listeners.remove(id)
+    }
+
+    /// Append points to a 2D scatter/line plot layer
+    ///
+    /// # Arguments
+    /// * `plot_id` - The plot ID (from Graph2D.id.0)
+    /// * `layer_idx` - Layer index within the plot
+    /// * `points` - Points as (x, y) tuples
+    pub fn append_points_2d(
+        &self,
+        plot_id: u64,
+        layer_idx: usize,
+        points: &[(f32, f32)],
+    ) -> Result<(), String> {
+        self.send(UpdateCommand::AppendPoints2D {
+            plot_id,
+            layer_idx,
+            points: points.iter().map(|(x, y)| [*x, *y]).collect(),
+        })
+    }
+
+    /// Replace all points in a 2D layer
+    pub fn replace_trace_2d(
+        &self,
+        plot_id: u64,
+        layer_idx: usize,
+        points: &[(f32, f32)],
+    ) -> Result<(), String> {
+        self.send(UpdateCommand::ReplaceTrace2D {
+            plot_id,
+            layer_idx,
+            points: points.iter().map(|(x, y)| [*x, *y]).collect(),
+        })
+    }
+
+    /// Add an annotation to a 2D or candlestick plot, e.g. a marker for an
+    /// event in a running job
+    pub fn add_annotation(&self, plot_id: u64, annotation: Annotation) -> Result<(), String> {
+        self.send(UpdateCommand::AddAnnotation {
+            plot_id,
+            annotation,
+        })
+    }
+
+    /// Append points to a 3D layer
+    pub fn append_points_3d(
+        &self,
+        plot_id: u64,
+        layer_idx: usize,
+        points: &[(f32, f32, f32)],
+    ) -> Result<(), String> {
+        self.send(UpdateCommand::AppendPoints3D {
+            plot_id,
+            layer_idx,
+            points: points.iter().map(|(x, y, z)| [*x, *y, *z]).collect(),
+        })
+    }
+
+    /// Replace all points in a 3D layer
+    pub fn replace_trace_3d(
+        &self,
+        plot_id: u64,
+        layer_idx: usize,
+        points: &[(f32, f32, f32)],
+    ) -> Result<(), String> {
+        self.send(UpdateCommand::ReplaceTrace3D {
+            plot_id,
+            layer_idx,
+            points: points.iter().map(|(x, y, z)| [*x, *y, *z]).collect(),
+        })
+    }
+
+    /// Replace an entire plot
+    pub fn update_plot(&self, plot_id: u64, plot: Plot) -> Result<(), String> {
+        self.send(UpdateCommand::UpdatePlot { plot_id, plot })
+    }
+
+    /// Replace the entire dashboard
+    pub fn refresh_all(&self, dashboard: Dashboard) -> Result<(), String> {
+        self.send(UpdateCommand::RefreshAll { dashboard })
+    }
+}
+
 /* -------------------- TAB BUILDER -------------------- */
 
 pub struct TabBuilder {
@@ -373,6 +596,12 @@ impl TabBuilder {
         self
     }
 
+    /// Set the grid layout for this tab (default: the dashboard's)
+    pub fn layout(mut self, layout: GridLayout) -> Self {
+        self.tab.layout = Some(layout);
+        self
+    }
+
     pub fn add_2d<F>(mut self, f: F) -> Self
     where
         F: FnOnce(Plot2DBuilder) -> Plot2DBuilder,
@@ -402,6 +631,7 @@ impl TabBuilder {
 
     pub fn add_field(mut self, dims: UVec2, values: Vec<f32>, vmin: f32, vmax: f32) -> Self {
         self.tab.plots.push(Plot::Field(Field {
+            id: PlotId::new(),
             meta: PlotMeta::default(),
             dims,
             values,
@@ -439,6 +669,70 @@ impl TabBuilder {
     }
 }
 
+/* -------------------- GRID PLACEMENT -------------------- */
+
+/// Grid placement methods shared by all plot builders
+pub trait GridPlacement: Sized {
+    /// Metadata of the plot being built
+    fn meta_mut(&mut self) -> &mut PlotMeta;
+
+    /// Place the plot in a named area of the grid layout
+    fn grid_area(mut self, name: impl Into<String>) -> Self {
+        self.meta_mut().cell.area = Some(name.into());
+        self
+    }
+
+    /// Cover `rows` x `columns` cells of the grid
+    fn grid_span(mut self, rows: usize, columns: usize) -> Self {
+        let cell = &mut self.meta_mut().cell;
+        cell.row_span = rows.max(1);
+        cell.column_span = columns.max(1);
+        self
+    }
+
+    /// Set the plot's whole grid placement at once
+    fn grid_cell(mut self, cell: GridCell) -> Self {
+        self.meta_mut().cell = cell;
+        self
+    }
+}
+
+impl GridPlacement for Plot2DBuilder {
+    fn meta_mut(&mut self) -> &mut PlotMeta {
+        &mut self.graph.meta
+    }
+}
+
+impl GridPlacement for Plot3DBuilder {
+    fn meta_mut(&mut self) -> &mut PlotMeta {
+        &mut self.graph.meta
+    }
+}
+
+impl GridPlacement for DistBuilder {
+    fn meta_mut(&mut self) -> &mut PlotMeta {
+        self.dist.meta_mut()
+    }
+}
+
+impl GridPlacement for RadialBuilder {
+    fn meta_mut(&mut self) -> &mut PlotMeta {
+        self.rad.meta_mut()
+    }
+}
+
+impl GridPlacement for CandlestickBuilder {
+    fn meta_mut(&mut self) -> &mut PlotMeta {
+        &mut self.candle.meta
+    }
+}
+
+impl GridPlacement for HeatmapBuilder {
+    fn meta_mut(&mut self) -> &mut PlotMeta {
+        &mut self.heatmap.meta
+    }
+}
+
 /* -------------------- PLOT 2D BUILDER -------------------- */
 
 pub struct Plot2DBuilder {
@@ -497,6 +791,22 @@ impl Plot2DBuilder {
         self
     }
 
+    /// Name the most recently added layer in the plot's legend
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        if let Some(layer) = self.graph.layers.last_mut() {
+            layer.label = Some(label.into());
+        }
+        self
+    }
+
+    /// Plot the most recently added layer against the secondary (right) y axis
+    pub fn secondary_y(mut self) -> Self {
+        if let Some(layer) = self.graph.layers.last_mut() {
+            layer.y_axis = YAxis::Secondary;
+        }
+        self
+    }
+
     /// Set the X-axis label
     pub fn x_label(mut self, label: impl Into<String>) -> Self {
         self.graph.x_label = Some(label.into());
@@ -509,6 +819,12 @@ impl Plot2DBuilder {
         self
     }
 
+    /// Set the secondary (right) Y-axis label
+    pub fn y2_label(mut self, label: impl Into<String>) -> Self {
+        self.graph.y2_label = Some(label.into());
+        self
+    }
+
     /// Set the plot title
     pub fn title(mut self, title: impl Into<String>) -> Self {
         self.graph.meta.title = Some(title.into());
@@ -521,6 +837,207 @@ impl Plot2DBuilder {
         self
     }
 
+    /// Set a key for addressing this plot in live updates
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.graph.meta.key = Some(key.into());
+        self
+    }
+
+    /// Replace all interaction settings at once
+    pub fn interaction(mut self, interaction: Interaction) -> Self {
+        self.graph.interaction = interaction;
+        self
+    }
+
+    /// Enable or disable panning
+    pub fn pan(mut self, enabled: bool) -> Self {
+        self.graph.interaction.pan = enabled;
+        self
+    }
+
+    /// Enable or disable zooming
+    pub fn zoom(mut self, enabled: bool) -> Self {
+        self.graph.interaction.zoom = enabled;
+        self
+    }
+
+    /// Limit zooming to between `min` and `max` times the fitted view
+    ///
+    /// The limits are swapped if `min > max`; one that is not positive and
+    /// finite keeps its default.
+    pub fn zoom_limits(mut self, min: f32, max: f32) -> Self {
+        self.graph.interaction.set_zoom_limits(min, max);
+        self
+    }
+
+    /// Scale how far each scroll-wheel step zooms (1.0 is the default)
+    pub fn wheel_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.graph.interaction.wheel_sensitivity = sensitivity;
+        self
+    }
+
+    /// Enable or disable keyboard shortcuts
+    pub fn keyboard(mut self, enabled: bool) -> Self {
+        self.graph.interaction.keyboard = enabled;
+        self
+    }
+
+    /// Enable or disable selecting points
+    pub fn select(mut self, enabled: bool) -> Self {
+        self.graph.interaction.select = enabled;
+        self
+    }
+
+    /// Share selections with other plots in `group` whose points line up by
+    /// index, so selecting here highlights the same rows there
+    pub fn link_selection(mut self, group: impl Into<String>) -> Self {
+        self.graph.link_selection = Some(group.into());
+        self
+    }
+
+    /// Keep an axis fixed while panning and zooming
+    pub fn lock_axis(mut self, axis: AxisLock) -> Self {
+        self.graph.interaction.lock = Some(axis);
+        self
+    }
+
+    /// Fix the x-axis bounds; pass `None` for a bound to fit it to the data
+    pub fn x_range(mut self, min: impl Into<Option<f32>>, max: impl Into<Option<f32>>) -> Self {
+        self.graph.x_range = [min.into(), max.into()];
+        self
+    }
+
+    /// Fix the y-axis bounds; pass `None` for a bound to fit it to the data
+    pub fn y_range(mut self, min: impl Into<Option<f32>>, max: impl Into<Option<f32>>) -> Self {
+        self.graph.y_range = [min.into(), max.into()];
+        self
+    }
+
+    /// Fix the secondary y-axis bounds; pass `None` for a bound to fit it
+    pub fn y2_range(mut self, min: impl Into<Option<f32>>, max: impl Into<Option<f32>>) -> Self {
+        self.graph.y2_range = [min.into(), max.into()];
+        self
+    }
+
+    /// Share of the tile left empty around fitted data on each side
+    /// (default 0.075)
+    pub fn padding(mut self, padding: f32) -> Self {
+        self.graph.padding = padding;
+        self
+    }
+
+    /// Draw x increasing to the left
+    pub fn invert_x(mut self) -> Self {
+        self.graph.invert_x = true;
+        self
+    }
+
+    /// Draw y increasing downwards
+    pub fn invert_y(mut self) -> Self {
+        self.graph.invert_y = true;
+        self
+    }
+
+    /// Keep one data unit the same length on both axes
+    pub fn equal_aspect(mut self) -> Self {
+        self.graph.equal_aspect = true;
+        self
+    }
+
+    /// Fit the view again whenever live updates change the data
+    pub fn refit_on_update(mut self, refit: bool) -> Self {
+        self.graph.refit_on_update = refit;
+        self
+    }
+
+    /// Pan and zoom the x axis together with other plots in `group`
+    pub fn link_x(mut self, group: impl Into<String>) -> Self {
+        self.graph.link_x = Some(group.into());
+        self
+    }
+
+    /// Pan and zoom the y axis together with other plots in `group`
+    pub fn link_y(mut self, group: impl Into<String>) -> Self {
+        self.graph.link_y = Some(group.into());
+        self
+    }
+
+    /// Mark an event, threshold or range on the plot
+    pub fn annotate(mut self, annotation: Annotation) -> Self {
+        self.graph.annotations.push(annotation);
+        self
+    }
+
+    /// Choose what the hover tooltip shows
+    pub fn hover(mut self, mode: HoverMode) -> Self {
+        self.graph.hover = mode;
+        self
+    }
+
+    /// Replace all axis, gridline and tick settings at once
+    pub fn axes(mut self, axes: AxesStyle) -> Self {
+        self.graph.axes = axes;
+        self
+    }
+
+    /// Draw gridlines at every tick
+    pub fn grid(mut self) -> Self {
+        self.graph.axes.major_grid = true;
+        self
+    }
+
+    /// Draw fainter gridlines between ticks
+    pub fn minor_grid(mut self) -> Self {
+        self.graph.axes.minor_grid = true;
+        self
+    }
+
+    /// Choose where the axis lines are drawn
+    pub fn spines(mut self, spines: Spines) -> Self {
+        self.graph.axes.spines = spines;
+        self
+    }
+
+    /// Aim for about `count` ticks along the x axis
+    pub fn x_tick_count(mut self, count: usize) -> Self {
+        self.graph.axes.x_ticks.count = Some(count.max(1));
+        self
+    }
+
+    /// Aim for about `count` ticks along the y axis
+    pub fn y_tick_count(mut self, count: usize) -> Self {
+        self.graph.axes.y_ticks.count = Some(count.max(1));
+        self
+    }
+
+    /// Set how x tick labels are formatted
+    pub fn x_tick_format(mut self, format: TickFormat) -> Self {
+        self.graph.axes.x_ticks.format = format;
+        self
+    }
+
+    /// Set how y tick labels are formatted
+    pub fn y_tick_format(mut self, format: TickFormat) -> Self {
+        self.graph.axes.y_ticks.format = format;
+        self
+    }
+
+    /// Set how secondary y tick labels are formatted
+    pub fn y2_tick_format(mut self, format: TickFormat) -> Self {
+        self.graph.axes.y2_ticks.format = format;
+        self
+    }
+
+    /// Format x tick labels with a closure (not kept when serialized)
+    pub fn x_tick_formatter(self, f: impl Fn(f32) -> String + Send + Sync + 'static) -> Self {
+        self.x_tick_format(TickFormat::custom(f))
+    }
+
+    /// Format y tick labels with a closure (not kept when serialized)
+    pub fn y_tick_formatter(self, f: impl Fn(f32) -> String + Send + Sync + 'static) -> Self {
+        self.y_tick_format(TickFormat::custom(f))
+    }
+
     /// Fill between two lines (for confidence intervals, ranges, etc.)
     ///
     /// # Arguments
@@ -542,6 +1059,105 @@ impl Plot2DBuilder {
         self.graph.layers.push(layer);
         self
     }
+
+    /// Line through the `x` and `y` columns of a table, colored from the palette
+    ///
+    /// Rows missing either value are skipped; axis labels default to the column
+    /// names.
+    ///
+    /// # Panics
+    ///
+    /// If a column does not exist or is not numeric; [`Self::try_line_from`]
+    /// returns the error instead.
+    pub fn line_from(self, table: &Table, x: &str, y: &str) -> Self {
+        expect_column(self.try_line_from(table, x, y))
+    }
+
+    /// Scatter of the `x` and `y` columns of a table; see [`Self::line_from`]
+    pub fn scatter_from(self, table: &Table, x: &str, y: &str) -> Self {
+        expect_column(self.try_scatter_from(table, x, y))
+    }
+
+    /// One line per distinct value of the `by` column, each colored from the palette
+    /// and labelled with the value in the legend; panics like [`Self::line_from`]
+    pub fn line_group_by(self, table: &Table, x: &str, y: &str, by: &str) -> Self {
+        expect_column(self.try_line_group_by(table, x, y, by))
+    }
+
+    /// One scatter layer per distinct value of the `by` column; panics like
+    /// [`Self::line_from`]
+    pub fn scatter_group_by(self, table: &Table, x: &str, y: &str, by: &str) -> Self {
+        expect_column(self.try_scatter_group_by(table, x, y, by))
+    }
+
+    /// [`Self::line_from`], failing on a missing or non-numeric column
+    pub fn try_line_from(self, table: &Table, x: &str, y: &str) -> Result<Self, String> {
+        self.groups_from(Geometry2D::Line, table, x, y, None)
+    }
+
+    /// [`Self::scatter_from`], failing on a missing or non-numeric column
+    pub fn try_scatter_from(self, table: &Table, x: &str, y: &str) -> Result<Self, String> {
+        self.groups_from(Geometry2D::Points, table, x, y, None)
+    }
+
+    /// [`Self::line_group_by`], failing on a missing or non-numeric column
+    pub fn try_line_group_by(
+        self,
+        table: &Table,
+        x: &str,
+        y: &str,
+        by: &str,
+    ) -> Result<Self, String> {
+        self.groups_from(Geometry2D::Line, table, x, y, Some(by))
+    }
+
+    /// [`Self::scatter_group_by`], failing on a missing or non-numeric column
+    pub fn try_scatter_group_by(
+        self,
+        table: &Table,
+        x: &str,
+        y: &str,
+        by: &str,
+    ) -> Result<Self, String> {
+        self.groups_from(Geometry2D::Points, table, x, y, Some(by))
+    }
+
+    fn groups_from(
+        mut self,
+        geometry: Geometry2D,
+        table: &Table,
+        x: &str,
+        y: &str,
+        by: Option<&str>,
+    ) -> Result<Self, String> {
+        // Convert whole columns so every group shares the same timestamp origin
+        let xs = table.f32_column(x)?;
+        let ys = table.f32_column(y)?;
+        let groups = match by {
+            Some(by) => table.group_indices(by)?,
+            None => vec![(String::new(), (0..table.len()).collect())],
+        };
+
+        for (name, rows) in groups {
+            let points = rows
+                .into_iter()
+                .filter_map(|r| Some(Vec2::new(xs[r]?, ys[r]?)))
+                .collect();
+            let style = Style::default().color(Color::palette(self.graph.layers.len()));
+            self = self.push_layer(geometry, points, Some(style));
+            if by.is_some() {
+                self = self.label(name);
+            }
+        }
+        self.graph.x_label.get_or_insert_with(|| x.to_string());
+        self.graph.y_label.get_or_insert_with(|| y.to_string());
+        Ok(self)
+    }
+}
+
+/// Unwrap a table adapter for its panicking builder form
+fn expect_column<T>(result: Result<T, String>) -> T {
+    result.unwrap_or_else(|e| panic!("{}", e))
 }
 
 // Allow passing &Style into the `impl Into<Option<Style>>` slot.
@@ -571,6 +1187,7 @@ impl Plot3DBuilder {
             geometry: Geometry3D::Points,
             xyz,
             style: Style::default(),
+            label: None,
         };
         if let Some(st) = style.into() {
             layer.style = st;
@@ -592,6 +1209,7 @@ impl Plot3DBuilder {
             },
             xyz,
             style: Style::default(),
+            label: None,
         };
         if let Some(st) = style.into() {
             layer.style = st;
@@ -600,6 +1218,14 @@ impl Plot3DBuilder {
         self
     }
 
+    /// Name the most recently added layer in the plot's legend
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        if let Some(layer) = self.graph.layers.last_mut() {
+            layer.label = Some(label.into());
+        }
+        self
+    }
+
     /// Set the plot title
     pub fn title(mut self, title: impl Into<String>) -> Self {
         self.graph.meta.title = Some(title.into());
@@ -612,6 +1238,12 @@ impl Plot3DBuilder {
         self
     }
 
+    /// Set a key for addressing this plot in live updates
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.graph.meta.key = Some(key.into());
+        self
+    }
+
     /// Set the X-axis label
     pub fn x_label(mut self, label: impl Into<String>) -> Self {
         self.graph.x_label = Some(label.into());
@@ -629,6 +1261,64 @@ impl Plot3DBuilder {
         self.graph.z_label = Some(label.into());
         self
     }
+
+    /// Replace all interaction settings at once
+    pub fn interaction(mut self, interaction: Interaction) -> Self {
+        self.graph.interaction = interaction;
+        self
+    }
+
+    /// Enable or disable panning
+    pub fn pan(mut self, enabled: bool) -> Self {
+        self.graph.interaction.pan = enabled;
+        self
+    }
+
+    /// Enable or disable zooming
+    pub fn zoom(mut self, enabled: bool) -> Self {
+        self.graph.interaction.zoom = enabled;
+        self
+    }
+
+    /// Limit zooming to between `min` and `max` times the fitted view
+    ///
+    /// The limits are swapped if `min > max`; one that is not positive and
+    /// finite keeps its default.
+    pub fn zoom_limits(mut self, min: f32, max: f32) -> Self {
+        self.graph.interaction.set_zoom_limits(min, max);
+        self
+    }
+
+    /// Scale how far each scroll-wheel step zooms (1.0 is the default)
+    pub fn wheel_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.graph.interaction.wheel_sensitivity = sensitivity;
+        self
+    }
+
+    /// Enable or disable keyboard shortcuts
+    pub fn keyboard(mut self, enabled: bool) -> Self {
+        self.graph.interaction.keyboard = enabled;
+        self
+    }
+
+    /// Enable or disable selecting points
+    pub fn select(mut self, enabled: bool) -> Self {
+        self.graph.interaction.select = enabled;
+        self
+    }
+
+    /// Share selections with other plots in `group` whose points line up by
+    /// index, so selecting here highlights the same rows there
+    pub fn link_selection(mut self, group: impl Into<String>) -> Self {
+        self.graph.link_selection = Some(group.into());
+        self
+    }
+
+    /// Enable or disable orbiting the camera
+    pub fn rotate(mut self, enabled: bool) -> Self {
+        self.graph.interaction.rotate = enabled;
+        self
+    }
 }
 
 /* -------------------- DISTRIBUTION BUILDER -------------------- */
@@ -641,6 +1331,7 @@ impl DistBuilder {
     fn new() -> Self {
         Self {
             dist: Distribution::Histogram {
+                id: PlotId::new(),
                 meta: PlotMeta::default(),
                 values: vec![],
                 bins: 30,
@@ -653,6 +1344,7 @@ impl DistBuilder {
 
     pub fn histogram(mut self, values: Vec<f32>) -> Self {
         self.dist = Distribution::Histogram {
+            id: PlotId::new(),
             meta: PlotMeta::default(),
             values,
             bins: 30,
@@ -665,6 +1357,7 @@ impl DistBuilder {
 
     pub fn pdf(mut self, values: Vec<f32>) -> Self {
         self.dist = Distribution::Pdf {
+            id: PlotId::new(),
             meta: PlotMeta::default(),
             values,
             style: Style::default(),
@@ -674,6 +1367,54 @@ impl DistBuilder {
         self
     }
 
+    /// Histogram of a table column (missing values skipped), labeled with its
+    /// name
+    ///
+    /// # Panics
+    ///
+    /// If the column does not exist or is not numeric;
+    /// [`Self::try_histogram_from`] returns the error instead.
+    pub fn histogram_from(self, table: &Table, column: &str) -> Self {
+        expect_column(self.try_histogram_from(table, column))
+    }
+
+    /// Density estimate of a table column; see [`Self::histogram_from`]
+    pub fn pdf_from(self, table: &Table, column: &str) -> Self {
+        expect_column(self.try_pdf_from(table, column))
+    }
+
+    /// Box per distinct value of the `by` column, summarizing `column`; panics
+    /// like [`Self::histogram_from`]
+    pub fn boxplot_group_by(self, table: &Table, column: &str, by: &str) -> Self {
+        expect_column(self.try_boxplot_group_by(table, column, by))
+    }
+
+    /// [`Self::histogram_from`], failing on a missing or non-numeric column
+    pub fn try_histogram_from(self, table: &Table, column: &str) -> Result<Self, String> {
+        Ok(self.histogram(table.values(column)?).x_label(column))
+    }
+
+    /// [`Self::pdf_from`], failing on a missing or non-numeric column
+    pub fn try_pdf_from(self, table: &Table, column: &str) -> Result<Self, String> {
+        Ok(self.pdf(table.values(column)?).x_label(column))
+    }
+
+    /// [`Self::boxplot_group_by`], failing on a missing or non-numeric column
+    pub fn try_boxplot_group_by(
+        self,
+        table: &Table,
+        column: &str,
+        by: &str,
+    ) -> Result<Self, String> {
+        let values = table.f32_column(column)?;
+        let groups: Vec<(String, Vec<f32>)> = table
+            .group_indices(by)?
+            .into_iter()
+            .map(|(key, rows)| (key, rows.into_iter().filter_map(|r| values[r]).collect()))
+            .collect();
+        Ok(self.boxplot(groups).x_label(by).y_label(column))
+    }
+
     pub fn bins(mut self, bins: usize) -> Self {
         if let Distribution::Histogram { bins: b, .. } = &mut self.dist {
             *b = bins.max(1);
@@ -683,6 +1424,7 @@ impl DistBuilder {
 
     pub fn boxplot(mut self, groups: Vec<(impl Into<String>, Vec<f32>)>) -> Self {
         self.dist = Distribution::BoxPlot {
+            id: PlotId::new(),
             meta: PlotMeta::default(),
             groups: groups.into_iter().map(|(l, v)| (l.into(), v)).collect(),
             style: Style::default(),
@@ -694,6 +1436,7 @@ impl DistBuilder {
 
     pub fn ecdf(mut self, values: Vec<f32>) -> Self {
         self.dist = Distribution::ECDF {
+            id: PlotId::new(),
             meta: PlotMeta::default(),
             values,
             style: Style::default(),
@@ -752,6 +1495,16 @@ impl DistBuilder {
         }
         self
     }
+
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        match &mut self.dist {
+            Distribution::Histogram { meta, .. } => meta.key = Some(key.into()),
+            Distribution::Pdf { meta, .. } => meta.key = Some(key.into()),
+            Distribution::BoxPlot { meta, .. } => meta.key = Some(key.into()),
+            Distribution::ECDF { meta, .. } => meta.key = Some(key.into()),
+        }
+        self
+    }
 }
 
 /* -------------------- RADIAL BUILDER -------------------- */
@@ -764,6 +1517,7 @@ impl RadialBuilder {
     fn new() -> Self {
         Self {
             rad: Radial::Pie {
+                id: PlotId::new(),
                 meta: PlotMeta::default(),
                 slices: vec![],
             },
@@ -772,6 +1526,7 @@ impl RadialBuilder {
 
     pub fn pie(mut self, slices: Vec<(impl Into<String>, f32)>) -> Self {
         self.rad = Radial::Pie {
+            id: PlotId::new(),
             meta: PlotMeta::default(),
             slices: slices.into_iter().map(|(l, v)| (l.into(), v)).collect(),
         };
@@ -780,6 +1535,7 @@ impl RadialBuilder {
 
     pub fn radar(mut self, axes: Vec<impl Into<String>>, values: Vec<f32>) -> Self {
         self.rad = Radial::Radar {
+            id: PlotId::new(),
             meta: PlotMeta::default(),
             axes: axes.into_iter().map(|a| a.into()).collect(),
             values,
@@ -810,6 +1566,14 @@ impl RadialBuilder {
         }
         self
     }
+
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        match &mut self.rad {
+            Radial::Pie { meta, .. } => meta.key = Some(key.into()),
+            Radial::Radar { meta, .. } => meta.key = Some(key.into()),
+        }
+        self
+    }
 }
 
 /* -------------------- CANDLESTICK BUILDER -------------------- */
@@ -822,12 +1586,14 @@ impl CandlestickBuilder {
     fn new() -> Self {
         Self {
             candle: Candlestick {
+                id: PlotId::new(),
                 meta: PlotMeta::default(),
                 candles: vec![],
                 up_color: Color::rgb(0.2, 0.8, 0.3),   // green
                 down_color: Color::rgb(0.9, 0.2, 0.2), // red
                 x_label: None,
                 y_label: None,
+                annotations: Vec::new(),
             },
         }
     }
@@ -876,6 +1642,17 @@ impl CandlestickBuilder {
         self.candle.meta.description = Some(desc.into());
         self
     }
+
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.candle.meta.key = Some(key.into());
+        self
+    }
+
+    /// Mark an event, level or range on the chart
+    pub fn annotate(mut self, annotation: Annotation) -> Self {
+        self.candle.annotations.push(annotation);
+        self
+    }
 }
 
 /* -------------------- HEATMAP BUILDER -------------------- */
@@ -888,6 +1665,7 @@ impl HeatmapBuilder {
     fn new() -> Self {
         Self {
             heatmap: Heatmap {
+                id: PlotId::new(),
                 meta: PlotMeta::default(),
                 dims: UVec2::ZERO,
                 values: vec![],
@@ -956,4 +1734,32 @@ impl HeatmapBuilder {
         self.heatmap.meta.description = Some(desc.into());
         self
     }
+
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.heatmap.meta.key = Some(key.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_adapters_report_bad_columns() {
+        let table = Table::from_csv("x,y,label\n1,2,a\n", ',').unwrap();
+        let plot = Plot2DBuilder::new;
+        assert!(plot().try_line_from(&table, "x", "nope").is_err());
+        assert!(
+            plot()
+                .try_scatter_group_by(&table, "x", "label", "label")
+                .is_err()
+        );
+        assert!(plot().try_line_group_by(&table, "x", "y", "label").is_ok());
+
+        let dist = DistBuilder::new;
+        assert!(dist().try_histogram_from(&table, "label").is_err());
+        assert!(dist().try_boxplot_group_by(&table, "y", "nope").is_err());
+        assert!(dist().try_pdf_from(&table, "y").is_ok());
+    }
 }
@@ -0,0 +1,590 @@
+//! Columnar tables read from CSV and JSON-lines.
+//!
+//! A [`Table`] holds named, typed columns. Column types are inferred from the
+//! text: integers, floats, timestamps (RFC 3339 or `YYYY-MM-DD[ HH:MM:SS]`)
+//! and strings, with empty cells and JSON `null`s as missing values. Builders
+//! take tables directly through adapters such as
+//! [`Plot2DBuilder::line_from`](crate::dash::Plot2DBuilder::line_from) and
+//! [`DistBuilder::histogram_from`](crate::dash::DistBuilder::histogram_from).
+//!
+//! ```rust,ignore
+//! let table = Table::read("train.csv")?;
+//! dash()
+//!     .add_2d(|p| p.line_group_by(&table, "step", "loss", "run"))
+//!     .add_distribution(|d| d.histogram_from(&table, "grad_norm"))
+//!     .run_local();
+//! ```
+
+use bevy_math::Vec2;
+use serde_json::Value;
+
+/// Input format of a text table
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Delimiter-separated values with a header row
+    Csv { delimiter: char },
+    /// One JSON object per line
+    JsonLines,
+}
+
+impl Format {
+    /// Format implied by a file extension: `.jsonl`/`.ndjson` are JSON-lines,
+    /// `.tsv` is tab-separated, anything else comma-separated
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("jsonl" | "ndjson") => Format::JsonLines,
+            Some("tsv") => Format::Csv { delimiter: '\t' },
+            _ => Format::Csv { delimiter: ',' },
+        }
+    }
+}
+
+/// Incremental line parser producing text rows
+///
+/// The first CSV line is the header. JSON-lines headers grow as new keys
+/// appear, so rows may be shorter than [`RowReader::headers`].
+#[derive(Clone, Debug)]
+pub struct RowReader {
+    format: Format,
+    headers: Vec<String>,
+}
+
+impl RowReader {
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            headers: Vec::new(),
+        }
+    }
+
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    /// Index of a column by name
+    pub fn column_index(&self, name: &str) -> Result<usize, String> {
+        column_index(&self.headers, name)
+    }
+
+    /// Parse one line, returning its cells once the header is known.
+    /// Blank lines yield nothing.
+    pub fn push_line(&mut self, line: &str) -> Result<Option<Vec<String>>, String> {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            return Ok(None);
+        }
+        match self.format {
+            Format::Csv { delimiter } => {
+                let cells = split_csv(line, delimiter);
+                if self.headers.is_empty() {
+                    self.headers = cells.into_iter().map(|c| c.trim().to_string()).collect();
+                    return Ok(None);
+                }
+                Ok(Some(cells))
+            }
+            Format::JsonLines => {
+                let Value::Object(map) =
+                    serde_json::from_str(line).map_err(|e| format!("invalid JSON: {}", e))?
+                else {
+                    return Err("expected a JSON object".to_string());
+                };
+                let mut row = vec![String::new(); self.headers.len()];
+                for (key, value) in map {
+                    let index = match self.headers.iter().position(|h| *h == key) {
+                        Some(i) => i,
+                        None => {
+                            self.headers.push(key);
+                            row.push(String::new());
+                            self.headers.len() - 1
+                        }
+                    };
+                    row[index] = match value {
+                        Value::String(s) => s,
+                        Value::Null => String::new(),
+                        other => other.to_string(),
+                    };
+                }
+                Ok(Some(row))
+            }
+        }
+    }
+}
+
+/// Type of a [`Column`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnType {
+    F32,
+    I64,
+    /// Milliseconds since the Unix epoch (UTC)
+    Timestamp,
+    Str,
+}
+
+/// A typed column; `None` marks a missing value
+#[derive(Clone, Debug, PartialEq)]
+pub enum Column {
+    F32(Vec<Option<f32>>),
+    I64(Vec<Option<i64>>),
+    /// Milliseconds since the Unix epoch (UTC)
+    Timestamp(Vec<Option<i64>>),
+    Str(Vec<Option<String>>),
+}
+
+impl Column {
+    /// Infer the narrowest type that fits every non-empty cell
+    /// (integer, then float, then timestamp, falling back to string)
+    pub fn infer<'a>(cells: impl Iterator<Item = Option<&'a str>> + Clone) -> Self {
+        let present = || {
+            cells
+                .clone()
+                .flatten()
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+        };
+        let cell = |c: Option<&'a str>| c.map(str::trim).filter(|c| !c.is_empty());
+
+        if present().all(|c| c.parse::<i64>().is_ok()) {
+            Column::I64(
+                cells
+                    .map(|c| cell(c).and_then(|c| c.parse().ok()))
+                    .collect(),
+            )
+        } else if present().all(|c| c.parse::<f32>().is_ok()) {
+            Column::F32(
+                cells
+                    .map(|c| cell(c).and_then(|c| c.parse().ok()))
+                    .collect(),
+            )
+        } else if present().all(|c| parse_timestamp(c).is_some()) {
+            Column::Timestamp(cells.map(|c| cell(c).and_then(parse_timestamp)).collect())
+        } else {
+            Column::Str(cells.map(|c| cell(c).map(str::to_string)).collect())
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Column::F32(v) => v.len(),
+            Column::I64(v) | Column::Timestamp(v) => v.len(),
+            Column::Str(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn column_type(&self) -> ColumnType {
+        match self {
+            Column::F32(_) => ColumnType::F32,
+            Column::I64(_) => ColumnType::I64,
+            Column::Timestamp(_) => ColumnType::Timestamp,
+            Column::Str(_) => ColumnType::Str,
+        }
+    }
+
+    /// Values as plot coordinates. Timestamps become seconds since the
+    /// column's earliest value, so `f32` keeps sub-second precision. Strings
+    /// and non-finite numbers are missing.
+    pub fn to_f32(&self) -> Vec<Option<f32>> {
+        match self {
+            Column::F32(v) => v.iter().map(|x| x.filter(|x| x.is_finite())).collect(),
+            Column::I64(v) => v.iter().map(|x| x.map(|x| x as f32)).collect(),
+            Column::Timestamp(v) => {
+                let origin = v.iter().flatten().min().copied().unwrap_or(0);
+                v.iter()
+                    .map(|x| x.map(|x| ((x - origin) as f64 / 1000.0) as f32))
+                    .collect()
+            }
+            Column::Str(v) => vec![None; v.len()],
+        }
+    }
+
+    /// Value at `row` as text (used as the group key by [`Table::group_by`])
+    pub fn text(&self, row: usize) -> Option<String> {
+        match self {
+            Column::F32(v) => v.get(row).copied().flatten().map(|x| x.to_string()),
+            Column::I64(v) | Column::Timestamp(v) => {
+                v.get(row).copied().flatten().map(|x| x.to_string())
+            }
+            Column::Str(v) => v.get(row).cloned().flatten(),
+        }
+    }
+
+    fn take(&self, rows: &[usize]) -> Column {
+        match self {
+            Column::F32(v) => Column::F32(rows.iter().map(|&r| v[r]).collect()),
+            Column::I64(v) => Column::I64(rows.iter().map(|&r| v[r]).collect()),
+            Column::Timestamp(v) => Column::Timestamp(rows.iter().map(|&r| v[r]).collect()),
+            Column::Str(v) => Column::Str(rows.iter().map(|&r| v[r].clone()).collect()),
+        }
+    }
+}
+
+/// Named columns of equal length
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Table {
+    names: Vec<String>,
+    columns: Vec<Column>,
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add (or replace) a column
+    pub fn with_column(mut self, name: impl Into<String>, column: Column) -> Result<Self, String> {
+        let name = name.into();
+        if !self.columns.is_empty() && column.len() != self.len() {
+            return Err(format!(
+                "Column '{}' has {} rows, the table has {}",
+                name,
+                column.len(),
+                self.len()
+            ));
+        }
+        match self.names.iter().position(|n| *n == name) {
+            Some(i) => self.columns[i] = column,
+            None => {
+                self.names.push(name);
+                self.columns.push(column);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Build a table from text rows, inferring each column's type.
+    /// Short rows are padded with missing values.
+    pub fn from_rows(headers: Vec<String>, rows: &[Vec<String>]) -> Self {
+        let columns = (0..headers.len())
+            .map(|i| Column::infer(rows.iter().map(move |row| row.get(i).map(String::as_str))))
+            .collect();
+        Self {
+            names: headers,
+            columns,
+        }
+    }
+
+    /// Parse text in the given format
+    pub fn parse(text: &str, format: Format) -> Result<Self, String> {
+        let mut reader = RowReader::new(format);
+        let mut rows = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if let Some(row) = reader
+                .push_line(line)
+                .map_err(|e| format!("line {}: {}", i + 1, e))?
+            {
+                rows.push(row);
+            }
+        }
+        Ok(Self::from_rows(reader.headers, &rows))
+    }
+
+    /// Parse CSV with a header row
+    pub fn from_csv(text: &str, delimiter: char) -> Result<Self, String> {
+        Self::parse(text, Format::Csv { delimiter })
+    }
+
+    /// Parse JSON-lines (one object per line)
+    pub fn from_jsonl(text: &str) -> Result<Self, String> {
+        Self::parse(text, Format::JsonLines)
+    }
+
+    /// Read a CSV, TSV or JSON-lines file, picking the format from its extension
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read(path: impl AsRef<std::path::Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        Self::read_as(path, Format::from_path(path))
+    }
+
+    /// Read a file in an explicit format
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_as(path: impl AsRef<std::path::Path>, format: Format) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&text, format).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.columns.first().map(Column::len).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn column_names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn column(&self, name: &str) -> Result<&Column, String> {
+        column_index(&self.names, name).map(|i| &self.columns[i])
+    }
+
+    /// A column as plot coordinates; see [`Column::to_f32`]. Text columns
+    /// are an error rather than all missing.
+    pub fn f32_column(&self, name: &str) -> Result<Vec<Option<f32>>, String> {
+        let column = self.column(name)?;
+        if column.column_type() == ColumnType::Str {
+            return Err(format!("Column '{}' holds text, not numbers", name));
+        }
+        Ok(column.to_f32())
+    }
+
+    /// Present values of a column, skipping missing ones
+    pub fn values(&self, name: &str) -> Result<Vec<f32>, String> {
+        Ok(self.f32_column(name)?.into_iter().flatten().collect())
+    }
+
+    /// `(x, y)` points from two columns, skipping rows missing either
+    pub fn points(&self, x: &str, y: &str) -> Result<Vec<Vec2>, String> {
+        let xs = self.f32_column(x)?;
+        let ys = self.f32_column(y)?;
+        Ok(xs
+            .into_iter()
+            .zip(ys)
+            .filter_map(|(x, y)| Some(Vec2::new(x?, y?)))
+            .collect())
+    }
+
+    /// Row indices for each value of a column, in order of first appearance.
+    /// Rows missing the key are left out.
+    pub fn group_indices(&self, name: &str) -> Result<Vec<(String, Vec<usize>)>, String> {
+        let key = self.column(name)?;
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        for row in 0..self.len() {
+            let Some(value) = key.text(row) else {
+                continue;
+            };
+            match groups.iter_mut().find(|(k, _)| *k == value) {
+                Some((_, rows)) => rows.push(row),
+                None => groups.push((value, vec![row])),
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Split rows into one table per value of a column; see [`Table::group_indices`]
+    pub fn group_by(&self, name: &str) -> Result<Vec<(String, Table)>, String> {
+        Ok(self
+            .group_indices(name)?
+            .into_iter()
+            .map(|(key, rows)| {
+                let table = Table {
+                    names: self.names.clone(),
+                    columns: self.columns.iter().map(|c| c.take(&rows)).collect(),
+                };
+                (key, table)
+            })
+            .collect())
+    }
+}
+
+fn column_index(names: &[String], name: &str) -> Result<usize, String> {
+    names
+        .iter()
+        .position(|n| n == name)
+        .ok_or_else(|| format!("No column '{}' (available: {})", name, names.join(", ")))
+}
+
+/// Split one CSV line, honoring double-quoted fields with `""` escapes
+fn split_csv(line: &str, delimiter: char) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => cells.push(std::mem::take(&mut cell)),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    cells
+}
+
+/// Parse `YYYY-MM-DD`, optionally followed by `T` or a space,
+/// `HH:MM[:SS[.fff]]` and a `Z` or `±HH:MM` offset (UTC if absent),
+/// into milliseconds since the Unix epoch
+pub fn parse_timestamp(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let date = s.get(..10)?;
+    let mut parts = date.split('-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if date.as_bytes()[4] != b'-' || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+
+    let rest = &s[10..];
+    if rest.is_empty() {
+        return Some(days * 86_400_000);
+    }
+    let rest = rest.strip_prefix(['T', ' '])?;
+
+    // Split off the zone designator
+    let (time, offset_min) = if let Some(time) = rest.strip_suffix('Z') {
+        (time, 0)
+    } else if let Some(i) = rest.rfind(['+', '-']) {
+        let (time, zone) = rest.split_at(i);
+        let sign = if zone.starts_with('-') { -1 } else { 1 };
+        let (h, m) = zone[1..].split_once(':').unwrap_or((&zone[1..], "0"));
+        (
+            time,
+            sign * (h.parse::<i64>().ok()? * 60 + m.parse::<i64>().ok()?),
+        )
+    } else {
+        (rest, 0)
+    };
+
+    let mut fields = time.split(':');
+    let hour: i64 = fields.next()?.parse().ok()?;
+    let minute: i64 = fields.next()?.parse().ok()?;
+    let seconds: f64 = match fields.next() {
+        Some(s) => s.parse().ok()?,
+        None => 0.0,
+    };
+    if fields.next().is_some() || hour > 23 || minute > 59 || !(0.0..61.0).contains(&seconds) {
+        return None;
+    }
+
+    let ms = ((hour * 60 + minute - offset_min) * 60) * 1000 + (seconds * 1000.0).round() as i64;
+    Some(days * 86_400_000 + ms)
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(
+            split_csv(r#"a,"b,c","say ""hi""",,"#, ','),
+            ["a", "b,c", r#"say "hi""#, "", ""]
+        );
+        let table = Table::from_csv("name;note\n\"x;y\";\"\"\"q\"\"\"\n", ';').unwrap();
+        assert_eq!(
+            table.column("name").unwrap().text(0).as_deref(),
+            Some("x;y")
+        );
+        assert_eq!(
+            table.column("note").unwrap().text(0).as_deref(),
+            Some("\"q\"")
+        );
+    }
+
+    #[test]
+    fn delimiter_from_extension() {
+        let format = |name: &str| Format::from_path(std::path::Path::new(name));
+        assert_eq!(format("a.csv"), Format::Csv { delimiter: ',' });
+        assert_eq!(format("a.tsv"), Format::Csv { delimiter: '\t' });
+        assert_eq!(format("a.jsonl"), Format::JsonLines);
+        assert_eq!(format("a.ndjson"), Format::JsonLines);
+        assert_eq!(format("a"), Format::Csv { delimiter: ',' });
+
+        let table = Table::parse("x\ty\n1\t2\r\n\n3\t4\n", format("a.tsv")).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.values("y").unwrap(), [2.0, 4.0]);
+    }
+
+    #[test]
+    fn type_inference() {
+        let table = Table::from_csv(
+            "int,float,time,text,empty\n\
+             1,1.5,2024-01-01,a,\n\
+             ,2,2024-01-02T00:00:01Z,b,\n",
+            ',',
+        )
+        .unwrap();
+        let types: Vec<_> = table
+            .column_names()
+            .iter()
+            .map(|n| table.column(n).unwrap().column_type())
+            .collect();
+        assert_eq!(
+            types,
+            [
+                ColumnType::I64,
+                ColumnType::F32,
+                ColumnType::Timestamp,
+                ColumnType::Str,
+                ColumnType::I64
+            ]
+        );
+        assert_eq!(table.f32_column("int").unwrap(), [Some(1.0), None]);
+        assert_eq!(
+            table.f32_column("time").unwrap(),
+            [Some(0.0), Some(86_401.0)]
+        );
+        assert!(table.f32_column("text").is_err());
+        assert!(table.f32_column("missing").is_err());
+
+        let table =
+            Table::from_jsonl("{\"a\": 1, \"b\": \"x\"}\n{\"a\": null, \"c\": 2.5}\n").unwrap();
+        assert_eq!(table.column_names(), ["a", "b", "c"]);
+        assert_eq!(table.f32_column("a").unwrap(), [Some(1.0), None]);
+        assert_eq!(table.f32_column("c").unwrap(), [None, Some(2.5)]);
+        assert!(Table::from_jsonl("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(parse_timestamp("1970-01-01"), Some(0));
+        assert_eq!(parse_timestamp("2000-03-01"), Some(951_868_800_000));
+        assert_eq!(parse_timestamp("1970-01-01T00:00:01.5Z"), Some(1_500));
+        assert_eq!(parse_timestamp("1970-01-01 01:00"), Some(3_600_000));
+        assert_eq!(parse_timestamp("1970-01-01T01:00:00+01:00"), Some(0));
+        assert_eq!(parse_timestamp("1969-12-31T23:00:00-01:00"), Some(0));
+        assert_eq!(parse_timestamp("1969-12-31"), Some(-86_400_000));
+        for bad in [
+            "",
+            "2024",
+            "2024-13-01",
+            "2024-01-32",
+            "2024/01/01",
+            "2024-01-01T24:00",
+            "2024-01-01x",
+        ] {
+            assert_eq!(parse_timestamp(bad), None, "{bad}");
+        }
+    }
+
+    #[test]
+    fn grouping() {
+        let table = Table::from_csv("run,loss\nb,1\na,2\nb,3\n,4\n", ',').unwrap();
+        let groups = table.group_indices("run").unwrap();
+        assert_eq!(
+            groups,
+            [("b".to_string(), vec![0, 2]), ("a".to_string(), vec![1])]
+        );
+
+        let tables = table.group_by("run").unwrap();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].0, "b");
+        assert_eq!(tables[0].1.values("loss").unwrap(), [1.0, 3.0]);
+        assert_eq!(tables[1].1.values("loss").unwrap(), [2.0]);
+        assert!(table.group_by("missing").is_err());
+    }
+}
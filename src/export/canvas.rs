@@ -0,0 +1,168 @@
+//! Backend-neutral display list that the SVG and PDF writers serialize.
+
+use crate::core::Color;
+use bevy_math::{Rect, Vec2};
+
+/// A drawing primitive in page coordinates (pixels, origin top-left, y down)
+#[derive(Clone, Debug)]
+pub(crate) enum Shape {
+    /// Filled axis-aligned rectangle
+    Rect { rect: Rect, fill: Color },
+    /// Stroked polyline, optionally closed
+    Line {
+        points: Vec<Vec2>,
+        width: f32,
+        color: Color,
+        closed: bool,
+    },
+    /// Filled polygon
+    Polygon { points: Vec<Vec2>, fill: Color },
+    /// Text centered on `pos`, rotated clockwise by `rotation` radians.
+    /// Multi-line text is centered as a block.
+    Text {
+        text: String,
+        pos: Vec2,
+        size: f32,
+        color: Color,
+        rotation: f32,
+    },
+    /// Rectangle filled with a vertical gradient, `stops` running bottom to top
+    Gradient { rect: Rect, stops: Vec<Color> },
+}
+
+/// Shapes sharing a clip rectangle (one per tile, plus the unclipped tab bar)
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Group {
+    pub clip: Option<Rect>,
+    pub shapes: Vec<Shape>,
+}
+
+/// Page with a background color and clipped groups of shapes
+///
+/// Drawing methods take world coordinates (origin at the page center, y up), the
+/// same space the Bevy renderer lays tiles out in, and convert them to page
+/// coordinates.
+#[derive(Clone, Debug)]
+pub(crate) struct Canvas {
+    pub size: Vec2,
+    pub background: Color,
+    pub groups: Vec<Group>,
+}
+
+impl Canvas {
+    pub fn new(size: Vec2, background: Color) -> Self {
+        Self {
+            size,
+            background,
+            groups: vec![Group::default()],
+        }
+    }
+
+    /// Start a new group clipped to a world-space rectangle
+    pub fn begin_clip(&mut self, center: Vec2, size: Vec2) {
+        let clip = Rect::from_center_size(self.page(center), size);
+        self.groups.push(Group {
+            clip: Some(clip),
+            shapes: Vec::new(),
+        });
+    }
+
+    /// Axis-aligned rectangle given by its world-space center and size
+    pub fn quad(&mut self, center: Vec2, size: Vec2, fill: Color) {
+        if fill.a <= 0.0 || size.x <= 0.0 || size.y <= 0.0 {
+            return;
+        }
+        let rect = Rect::from_center_size(self.page(center), size);
+        self.push(Shape::Rect { rect, fill });
+    }
+
+    pub fn line(&mut self, points: &[Vec2], width: f32, color: Color) {
+        self.stroke(points, width, color, false);
+    }
+
+    pub fn closed_line(&mut self, points: &[Vec2], width: f32, color: Color) {
+        self.stroke(points, width, color, true);
+    }
+
+    pub fn polygon(&mut self, points: &[Vec2], fill: Color) {
+        if points.len() < 3 || fill.a <= 0.0 {
+            return;
+        }
+        let points = points.iter().map(|&p| self.page(p)).collect();
+        self.push(Shape::Polygon { points, fill });
+    }
+
+    /// Text centered on `pos`, rotated counter-clockwise by `rotation` radians
+    /// (matching `Quat::from_rotation_z` on a `Text2d`)
+    pub fn text(
+        &mut self,
+        text: impl Into<String>,
+        pos: Vec2,
+        size: f32,
+        color: Color,
+        rotation: f32,
+    ) {
+        let text = text.into();
+        if text.is_empty() {
+            return;
+        }
+        let pos = self.page(pos);
+        self.push(Shape::Text {
+            text,
+            pos,
+            size,
+            color,
+            rotation: -rotation,
+        });
+    }
+
+    pub fn gradient(&mut self, center: Vec2, size: Vec2, stops: Vec<Color>) {
+        let rect = Rect::from_center_size(self.page(center), size);
+        self.push(Shape::Gradient { rect, stops });
+    }
+
+    fn stroke(&mut self, points: &[Vec2], width: f32, color: Color, closed: bool) {
+        if points.len() < 2 || color.a <= 0.0 || width <= 0.0 {
+            return;
+        }
+        let points = points.iter().map(|&p| self.page(p)).collect();
+        self.push(Shape::Line {
+            points,
+            width,
+            color,
+            closed,
+        });
+    }
+
+    fn push(&mut self, shape: Shape) {
+        self.groups
+            .last_mut()
+            .expect("canvas always has a group")
+            .shapes
+            .push(shape);
+    }
+
+    fn page(&self, world: Vec2) -> Vec2 {
+        Vec2::new(world.x + self.size.x * 0.5, self.size.y * 0.5 - world.y)
+    }
+}
+
+/// Lines of `text` with their vertical offsets from the block center
+pub(crate) fn text_lines(text: &str, size: f32) -> impl Iterator<Item = (f32, &str)> {
+    let line_height = size * 1.2;
+    let count = text.lines().count().max(1);
+    let first = -(count as f32 - 1.0) * 0.5 * line_height;
+    text.lines()
+        .enumerate()
+        .map(move |(i, line)| (first + i as f32 * line_height, line))
+}
+
+/// Shortest decimal form of `v` with at most two fractional digits
+pub(crate) fn num(v: f32) -> String {
+    let s = format!("{:.2}", v);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    match s {
+        "-0" | "" => "0".to_string(),
+        _ => s.to_string(),
+    }
+}
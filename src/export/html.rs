@@ -0,0 +1,159 @@
+//! Self-contained HTML export.
+//!
+//! The page embeds the prebuilt WASM viewer (`vidi.js` and `vidi_bg.wasm`, as
+//! produced by `scripts/build-wasm.sh`) and the dashboard JSON, all inline, so it
+//! opens from disk without a server. The glue is imported from a Blob URL and
+//! the module is instantiated from the decoded bytes, since browsers refuse to
+//! fetch neighbouring files from `file://` pages.
+
+use crate::core::Dashboard;
+
+/// Canvas the viewer renders into
+const CANVAS_ID: &str = "vidi-canvas";
+
+/// Environment variable pointing at a directory holding the viewer bundle
+pub const VIEWER_DIR_ENV: &str = "VIDI_VIEWER_DIR";
+
+/// The prebuilt WASM viewer: wasm-bindgen `--target web` glue and module
+#[derive(Clone, Debug)]
+pub struct Viewer {
+    pub js: String,
+    pub wasm: Vec<u8>,
+}
+
+impl Viewer {
+    pub fn new(js: impl Into<String>, wasm: impl Into<Vec<u8>>) -> Self {
+        Self {
+            js: js.into(),
+            wasm: wasm.into(),
+        }
+    }
+
+    /// Read `vidi.js` and `vidi_bg.wasm` from a directory
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(dir: impl AsRef<std::path::Path>) -> Result<Self, String> {
+        let dir = dir.as_ref();
+        let read = |name: &str| {
+            let path = dir.join(name);
+            std::fs::read(&path).map_err(|e| {
+                format!(
+                    "Failed to read WASM viewer {}: {} (build it with scripts/build-wasm.sh)",
+                    path.display(),
+                    e
+                )
+            })
+        };
+        let js = String::from_utf8(read("vidi.js")?)
+            .map_err(|e| format!("WASM viewer glue is not UTF-8: {}", e))?;
+        Ok(Self::new(js, read("vidi_bg.wasm")?))
+    }
+
+    /// Load the viewer from `$VIDI_VIEWER_DIR`, falling back to the
+    /// `vidi-server/wasm` directory of this crate's source tree
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn locate() -> Result<Self, String> {
+        match std::env::var_os(VIEWER_DIR_ENV) {
+            Some(dir) => Self::load(dir),
+            None => Self::load(concat!(env!("CARGO_MANIFEST_DIR"), "/vidi-server/wasm")),
+        }
+    }
+}
+
+/// Render a dashboard as a standalone HTML page running the WASM viewer
+///
+/// Pan, zoom, hover and tabs work as in the live viewer; the page never
+/// connects to a server.
+pub fn render_dashboard_html(dashboard: &Dashboard, viewer: &Viewer) -> Result<String, String> {
+    let json = serde_json::to_string(dashboard)
+        .map_err(|e| format!("Failed to serialize dashboard: {}", e))?;
+    let bg = dashboard.background;
+    let background = format!(
+        "rgb({}, {}, {})",
+        channel(bg.r),
+        channel(bg.g),
+        channel(bg.b)
+    );
+
+    Ok(format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8">
+  <meta name="viewport" content="width=device-width, initial-scale=1.0">
+  <title>Vidi Dashboard</title>
+  <style>
+    html, body {{ height: 100%; margin: 0; overflow: hidden; background: {background}; }}
+    #vidi-container {{ position: relative; width: 100%; height: 100%; }}
+    #{CANVAS_ID} {{ display: block; width: 100%; height: 100%; outline: none; }}
+    #vidi-status {{
+      position: absolute; inset: 0; display: flex; align-items: center; justify-content: center;
+      color: #b0b8c8; font: 14px 'Fira Mono', monospace; background: {background};
+    }}
+  </style>
+</head>
+<body>
+  <div id="vidi-container">
+    <canvas id="{CANVAS_ID}"></canvas>
+    <div id="vidi-status">Loading...</div>
+  </div>
+  <script type="application/json" id="vidi-dashboard">{json}</script>
+  <script type="application/octet-stream" id="vidi-glue">{glue}</script>
+  <script type="application/octet-stream" id="vidi-wasm">{wasm}</script>
+  <script type="module">
+    function bytes(id) {{
+      const text = atob(document.getElementById(id).textContent);
+      const out = new Uint8Array(text.length);
+      for (let i = 0; i < text.length; i++) out[i] = text.charCodeAt(i);
+      return out;
+    }}
+
+    const status = document.getElementById('vidi-status');
+    try {{
+      const glue = new Blob([bytes('vidi-glue')], {{ type: 'text/javascript' }});
+      const viewer = await import(URL.createObjectURL(glue));
+      await viewer.default({{ module_or_path: bytes('vidi-wasm') }});
+      const json = document.getElementById('vidi-dashboard').textContent;
+      const dashboard = new viewer.JsDashboard(json, '{CANVAS_ID}');
+      status.remove();
+      dashboard.start();
+    }} catch (error) {{
+      console.error('Failed to start dashboard:', error);
+      status.textContent = 'Failed to load dashboard: ' + error;
+    }}
+  </script>
+</body>
+</html>
+"#,
+        // `<` only occurs inside JSON strings, where the escape is equivalent
+        json = json.replace('<', "\\u003c"),
+        glue = base64(viewer.js.as_bytes()),
+        wasm = base64(&viewer.wasm),
+    ))
+}
+
+fn channel(c: f32) -> u8 {
+    (c.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Standard base64 with padding
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
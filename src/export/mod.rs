@@ -0,0 +1,67 @@
+//! Static export of dashboards and plots (SVG, PDF, standalone HTML).
+//!
+//! Plots are walked from the `core` model onto a backend-neutral canvas using
+//! the renderer's tile layout, auto-fit, tick computation and colormaps, so the
+//! output matches what the Bevy app shows. 3D plots and fields have no vector
+//! form and are exported as placeholders. Unlike PNG export this needs no GPU
+//! and works on every target.
+//!
+//! [`render_dashboard_html`] instead packages the interactive WASM viewer and
+//! the dashboard into one offline HTML file.
+
+mod canvas;
+mod html;
+mod pdf;
+mod scene;
+mod svg;
+
+use crate::core::{Dashboard, Plot};
+use bevy_math::UVec2;
+
+pub use html::{VIEWER_DIR_ENV, Viewer, render_dashboard_html};
+
+/// Render the active view of a dashboard as an SVG document
+///
+/// `size` is in pixels and becomes the SVG's width, height and viewBox.
+pub fn render_dashboard_svg(dashboard: &Dashboard, size: UVec2) -> Result<String, String> {
+    check_size(size)?;
+    Ok(svg::write_svg(&scene::dashboard_canvas(
+        dashboard,
+        size.as_vec2(),
+    )))
+}
+
+/// Render a single plot as an SVG document
+pub fn render_plot_svg(plot: &Plot, size: UVec2) -> Result<String, String> {
+    render_dashboard_svg(&single_plot(plot), size)
+}
+
+/// Render the active view of a dashboard as a single-page PDF
+///
+/// The page measures `size` points, one point per pixel of the equivalent SVG.
+pub fn render_dashboard_pdf(dashboard: &Dashboard, size: UVec2) -> Result<Vec<u8>, String> {
+    check_size(size)?;
+    Ok(pdf::write_pdf(&scene::dashboard_canvas(
+        dashboard,
+        size.as_vec2(),
+    )))
+}
+
+/// Render a single plot as a single-page PDF
+pub fn render_plot_pdf(plot: &Plot, size: UVec2) -> Result<Vec<u8>, String> {
+    render_dashboard_pdf(&single_plot(plot), size)
+}
+
+fn single_plot(plot: &Plot) -> Dashboard {
+    Dashboard {
+        plots: vec![plot.clone()],
+        ..Dashboard::default()
+    }
+}
+
+fn check_size(size: UVec2) -> Result<(), String> {
+    if size.x == 0 || size.y == 0 {
+        return Err(format!("Invalid page size {}x{}", size.x, size.y));
+    }
+    Ok(())
+}
@@ -0,0 +1,275 @@
+//! Minimal single-page PDF serialization of a [`Canvas`].
+//!
+//! One page sized in points to match the canvas pixels, uncompressed content,
+//! the standard Courier font (no embedding needed) and one graphics state per
+//! distinct alpha value. Gradients are drawn as thin stacked bands.
+
+use super::canvas::{Canvas, Shape, num, text_lines};
+use crate::core::Color;
+use bevy_math::{Rect, Vec2};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// Courier advance width, in ems
+const CHAR_WIDTH: f32 = 0.6;
+
+/// Distance from baseline to the middle of capitals, in ems
+const BASELINE_SHIFT: f32 = 0.3;
+
+/// Bands used to approximate a gradient
+const GRADIENT_BANDS: usize = 64;
+
+pub(crate) fn write_pdf(canvas: &Canvas) -> Vec<u8> {
+    let mut content = Content {
+        height: canvas.size.y,
+        bytes: Vec::new(),
+        alphas: BTreeSet::new(),
+        alpha: None,
+    };
+
+    let page = Rect::from_corners(Vec2::ZERO, canvas.size);
+    content.fill_rect(page, canvas.background);
+
+    for group in &canvas.groups {
+        if group.shapes.is_empty() {
+            continue;
+        }
+        content.op("q");
+        if let Some(clip) = group.clip {
+            let (x, y, w, h) = content.rect(clip);
+            content.op(&format!("{x} {y} {w} {h} re W n"));
+        }
+        for shape in &group.shapes {
+            content.shape(shape);
+        }
+        content.op("Q");
+        // Q restores the graphics state, including the alpha
+        content.alpha = None;
+    }
+
+    let ext_g_states: String = content
+        .alphas
+        .iter()
+        .map(|&a| {
+            let v = num(a as f32 / 255.0);
+            format!("/A{a} << /ca {v} /CA {v} >> ")
+        })
+        .collect();
+
+    let objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 5 0 R >> /ExtGState << {}>> >> /Contents 4 0 R >>",
+            num(canvas.size.x),
+            num(canvas.size.y),
+            ext_g_states
+        )
+        .into_bytes(),
+        {
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.bytes.len()).into_bytes();
+            stream.extend_from_slice(&content.bytes);
+            stream.extend_from_slice(b"\nendstream");
+            stream
+        },
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+    ];
+
+    let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        out.extend_from_slice(object);
+        out.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref = out.len();
+    let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(trailer, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        trailer,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    );
+    out.extend_from_slice(trailer.as_bytes());
+    out
+}
+
+/// Page content stream being built
+struct Content {
+    height: f32,
+    bytes: Vec<u8>,
+    /// Alpha values used, quantized to bytes, each becoming an `/A<n>` state
+    alphas: BTreeSet<u8>,
+    alpha: Option<u8>,
+}
+
+impl Content {
+    fn op(&mut self, op: &str) {
+        self.bytes.extend_from_slice(op.as_bytes());
+        self.bytes.push(b'\n');
+    }
+
+    /// Page point (y down) to PDF user space (y up)
+    fn point(&self, p: Vec2) -> String {
+        format!("{} {}", num(p.x), num(self.height - p.y))
+    }
+
+    fn rect(&self, rect: Rect) -> (String, String, String, String) {
+        (
+            num(rect.min.x),
+            num(self.height - rect.max.y),
+            num(rect.width()),
+            num(rect.height()),
+        )
+    }
+
+    fn set_alpha(&mut self, alpha: f32) {
+        let a = (alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+        if self.alpha != Some(a) {
+            self.alphas.insert(a);
+            self.alpha = Some(a);
+            self.op(&format!("/A{a} gs"));
+        }
+    }
+
+    fn fill_color(&mut self, color: Color) {
+        self.set_alpha(color.a);
+        self.op(&format!(
+            "{} {} {} rg",
+            rgb(color.r),
+            rgb(color.g),
+            rgb(color.b)
+        ));
+    }
+
+    fn stroke_color(&mut self, color: Color) {
+        self.set_alpha(color.a);
+        self.op(&format!(
+            "{} {} {} RG",
+            rgb(color.r),
+            rgb(color.g),
+            rgb(color.b)
+        ));
+    }
+
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        self.fill_color(color);
+        let (x, y, w, h) = self.rect(rect);
+        self.op(&format!("{x} {y} {w} {h} re f"));
+    }
+
+    fn path(&mut self, points: &[Vec2]) {
+        for (i, &p) in points.iter().enumerate() {
+            let op = if i == 0 { "m" } else { "l" };
+            let point = self.point(p);
+            self.op(&format!("{point} {op}"));
+        }
+    }
+
+    fn shape(&mut self, shape: &Shape) {
+        match shape {
+            Shape::Rect { rect, fill } => self.fill_rect(*rect, *fill),
+            Shape::Line {
+                points,
+                width,
+                color,
+                closed,
+            } => {
+                self.stroke_color(*color);
+                self.op(&format!("{} w 0 J 1 j", num(*width)));
+                self.path(points);
+                self.op(if *closed { "s" } else { "S" });
+            }
+            Shape::Polygon { points, fill } => {
+                self.fill_color(*fill);
+                self.path(points);
+                self.op("h f");
+            }
+            Shape::Text {
+                text,
+                pos,
+                size,
+                color,
+                rotation,
+            } => {
+                self.fill_color(*color);
+                // Page rotation is clockwise with y down, PDF's is counter-clockwise with y up
+                let (sin, cos) = (-rotation).sin_cos();
+                let center = Vec2::new(pos.x, self.height - pos.y);
+                for (dy, line) in text_lines(text, *size) {
+                    let width = line.chars().count() as f32 * CHAR_WIDTH * size;
+                    let local = Vec2::new(-width * 0.5, -dy - BASELINE_SHIFT * size);
+                    let origin = center
+                        + Vec2::new(local.x * cos - local.y * sin, local.x * sin + local.y * cos);
+                    self.op(&format!(
+                        "BT /F1 {} Tf {} {} {} {} {} {} Tm",
+                        num(*size),
+                        num(cos),
+                        num(sin),
+                        num(-sin),
+                        num(cos),
+                        num(origin.x),
+                        num(origin.y)
+                    ));
+                    self.bytes.push(b'(');
+                    self.bytes.extend(win_ansi(line));
+                    self.op(") Tj ET");
+                }
+            }
+            Shape::Gradient { rect, stops } => {
+                if stops.is_empty() {
+                    return;
+                }
+                let band = rect.height() / GRADIENT_BANDS as f32;
+                for i in 0..GRADIENT_BANDS {
+                    let t = (i as f32 + 0.5) / GRADIENT_BANDS as f32;
+                    // Bands overlap slightly so no seams show between them
+                    let top = rect.max.y - i as f32 * band;
+                    let band_rect = Rect::new(rect.min.x, top - band - 0.5, rect.max.x, top);
+                    self.fill_rect(band_rect.intersect(*rect), sample(stops, t));
+                }
+            }
+        }
+    }
+}
+
+fn rgb(c: f32) -> String {
+    num(c.clamp(0.0, 1.0))
+}
+
+/// Linear interpolation between evenly spaced gradient stops
+fn sample(stops: &[Color], t: f32) -> Color {
+    let pos = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+    let i = (pos.floor() as usize).min(stops.len() - 1);
+    let j = (i + 1).min(stops.len() - 1);
+    let f = pos - i as f32;
+    let (a, b) = (stops[i], stops[j]);
+    Color::rgba(
+        a.r + (b.r - a.r) * f,
+        a.g + (b.g - a.g) * f,
+        a.b + (b.b - a.b) * f,
+        a.a + (b.a - a.a) * f,
+    )
+}
+
+/// Escape a string for a PDF literal, mapping characters outside Latin-1 to `?`
+fn win_ansi(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push(b'\\');
+                out.push(c as u8);
+            }
+            ' '..='~' | '\u{a0}'..='\u{ff}' => out.push(c as u32 as u8),
+            _ => out.push(b'?'),
+        }
+    }
+    out
+}
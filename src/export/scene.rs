@@ -0,0 +1,1037 @@
+//! Walks the dashboard model onto a [`Canvas`].
+//!
+//! Each function mirrors its counterpart in `render::draw` (same paddings,
+//! colors, font sizes and tick placement) so vector exports look like the
+//! rendered dashboard.
+
+use super::canvas::Canvas;
+use crate::core::{
+    Annotation, AxesStyle, Color, Dashboard, Distribution, Geometry2D, Plot, PlotMeta, Radial,
+    Style,
+};
+use crate::render::components::{TileRect, TileView};
+use crate::render::draw::{
+    ANNOTATION_FONT_SIZE, ANNOTATION_LINE_WIDTH, BoxStats, HistogramBins, KdeCurve,
+    LEGEND_FONT_SIZE, Mark, annotation_marks, axis_layout, boxplot_area, boxplot_range,
+    candle_width, compute_box_stats, data_to_world, distribution_area, ecdf_sorted, ecdf_steps,
+    format_tick, grid_lines, heatmap_area, heatmap_range, histogram_bins, kde_curve, legend_layout,
+    nice_step, on_primary_axis, pie_color, radial_radius, spine_lines, visible_data_range,
+};
+use crate::render::systems::{fit_view, tile_rects};
+use bevy_math::Vec2;
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+const BORDER: Color = Color::rgb(0.3, 0.3, 0.4);
+const TICK_LABEL: Color = Color::rgba(0.7, 0.7, 0.7, 0.9);
+const AXIS_LABEL: Color = Color::rgba(0.8, 0.8, 0.8, 1.0);
+
+/// Lay out the active plots of `dash` on a page of `size` pixels
+pub(crate) fn dashboard_canvas(dash: &Dashboard, size: Vec2) -> Canvas {
+    let mut canvas = Canvas::new(size, dash.background);
+
+    if dash.has_tabs() {
+        tab_bar(&mut canvas, dash);
+    }
+
+    let rects = tile_rects(dash, size.x, size.y, 1.0);
+    let plots = dash.active_plots();
+    for (index, (plot, rect)) in plots.iter().zip(&rects).enumerate() {
+        canvas.begin_clip(rect.world_center, rect.world_size);
+        let view = fit_view(plots, index, rect.world_size).unwrap_or_default();
+        draw_plot(&mut canvas, plot, rect, &view);
+    }
+
+    canvas
+}
+
+fn draw_plot(canvas: &mut Canvas, plot: &Plot, rect: &TileRect, view: &TileView) {
+    match plot {
+        Plot::Graph2D(graph) => {
+            plot_title(canvas, &graph.meta, rect);
+            graph_2d(canvas, graph, rect, view);
+            axis_ticks(canvas, rect, view, &graph.axes);
+            annotations(canvas, &graph.annotations, rect, view);
+        }
+        Plot::Distribution(dist) => {
+            plot_title(canvas, plot.meta(), rect);
+            match dist {
+                Distribution::Histogram {
+                    values,
+                    bins,
+                    style,
+                    x_label,
+                    y_label,
+                    ..
+                } => histogram(
+                    canvas,
+                    values,
+                    *bins,
+                    style,
+                    x_label.as_deref(),
+                    y_label.as_deref(),
+                    rect,
+                ),
+                Distribution::Pdf {
+                    values,
+                    style,
+                    x_label,
+                    y_label,
+                    ..
+                } => pdf(
+                    canvas,
+                    values,
+                    style,
+                    x_label.as_deref(),
+                    y_label.as_deref(),
+                    rect,
+                ),
+                Distribution::BoxPlot {
+                    groups,
+                    style,
+                    x_label,
+                    y_label,
+                    ..
+                } => boxplot(
+                    canvas,
+                    groups,
+                    style,
+                    x_label.as_deref(),
+                    y_label.as_deref(),
+                    rect,
+                ),
+                Distribution::ECDF {
+                    values,
+                    style,
+                    x_label,
+                    y_label,
+                    ..
+                } => ecdf(
+                    canvas,
+                    values,
+                    style,
+                    x_label.as_deref(),
+                    y_label.as_deref(),
+                    rect,
+                ),
+            }
+        }
+        Plot::Candlestick(candle) => {
+            plot_title(canvas, &candle.meta, rect);
+            candlestick(canvas, candle, rect, view);
+            annotations(canvas, &candle.annotations, rect, view);
+        }
+        Plot::Heatmap(heatmap) => {
+            plot_title(canvas, &heatmap.meta, rect);
+            heatmap_cells(canvas, heatmap, rect);
+        }
+        Plot::Radial(radial) => {
+            plot_title(canvas, plot.meta(), rect);
+            match radial {
+                Radial::Pie { slices, .. } => pie(canvas, slices, rect),
+                Radial::Radar {
+                    axes,
+                    values,
+                    style,
+                    ..
+                } => radar(canvas, axes, values, style, rect),
+            }
+        }
+        Plot::Graph3D(_) | Plot::Field(_) => {
+            plot_title(canvas, plot.meta(), rect);
+            tile_border(canvas, rect);
+            canvas.quad(
+                rect.world_center,
+                rect.world_size * 0.8,
+                Color::rgb(0.2, 0.2, 0.2),
+            );
+            canvas.text(
+                "Not available in vector export",
+                rect.world_center,
+                11.0,
+                TICK_LABEL,
+                0.0,
+            );
+        }
+    }
+}
+
+fn style_color(style: &Style, alpha: f32) -> Color {
+    style.color.with_a(alpha)
+}
+
+fn tile_border(canvas: &mut Canvas, rect: &TileRect) {
+    let thickness = 2.0;
+    for (dx, dy) in [(0.0, 0.5), (0.0, -0.5), (-0.5, 0.0), (0.5, 0.0)] {
+        let center = rect.world_center + Vec2::new(dx, dy) * rect.world_size;
+        let size = if dx == 0.0 {
+            Vec2::new(rect.world_size.x, thickness)
+        } else {
+            Vec2::new(thickness, rect.world_size.y)
+        };
+        canvas.quad(center, size, BORDER);
+    }
+}
+
+fn plot_title(canvas: &mut Canvas, meta: &PlotMeta, rect: &TileRect) {
+    let title_y = rect.world_center.y + rect.world_size.y * 0.5 - 18.0;
+
+    if let Some(title) = &meta.title {
+        canvas.text(
+            title.clone(),
+            Vec2::new(rect.world_center.x, title_y),
+            14.0,
+            Color::rgba(1.0, 1.0, 1.0, 0.95),
+            0.0,
+        );
+    }
+
+    if let Some(desc) = &meta.description {
+        let desc_y = title_y - if meta.title.is_some() { 16.0 } else { 0.0 };
+        canvas.text(
+            desc.clone(),
+            Vec2::new(rect.world_center.x, desc_y),
+            10.0,
+            Color::rgba(0.7, 0.7, 0.7, 0.85),
+            0.0,
+        );
+    }
+}
+
+/// Axis titles below and left of a fixed-axis chart
+fn axis_labels(canvas: &mut Canvas, rect: &TileRect, x: (&str, f32), y: (&str, f32, f32)) {
+    canvas.text(
+        x.0,
+        Vec2::new(x.1, rect.world_center.y - rect.world_size.y * 0.5 + 14.0),
+        11.0,
+        AXIS_LABEL,
+        0.0,
+    );
+    canvas.text(
+        y.0,
+        Vec2::new(rect.world_center.x - rect.world_size.x * 0.5 + y.1, y.2),
+        11.0,
+        AXIS_LABEL,
+        FRAC_PI_2,
+    );
+}
+
+fn graph_2d(canvas: &mut Canvas, graph: &crate::core::Graph2D, rect: &TileRect, view: &TileView) {
+    tile_border(canvas, rect);
+
+    // Gridlines and axis lines
+    let layout = axis_layout(rect, view, &graph.axes);
+    for (center, size, minor) in grid_lines(&layout, &graph.axes) {
+        let alpha = if minor { 0.08 } else { 0.18 };
+        canvas.quad(center, size, Color::rgba(0.5, 0.5, 0.55, alpha));
+    }
+    let axis_color = Color::rgb(0.5, 0.5, 0.5);
+    for (center, size) in spine_lines(&layout, rect, view, graph.axes.spines) {
+        canvas.quad(center, size, axis_color);
+    }
+
+    let label_color = Color::rgb(0.8, 0.8, 0.8);
+    if let Some(x_label) = &graph.x_label {
+        canvas.text(
+            x_label.clone(),
+            Vec2::new(
+                rect.world_center.x,
+                rect.world_center.y - rect.world_size.y * 0.5 + 12.0,
+            ),
+            14.0,
+            label_color,
+            0.0,
+        );
+    }
+    if let Some(y_label) = &graph.y_label {
+        canvas.text(
+            y_label.clone(),
+            Vec2::new(
+                rect.world_center.x - rect.world_size.x * 0.5 + 12.0,
+                rect.world_center.y,
+            ),
+            14.0,
+            label_color,
+            FRAC_PI_2,
+        );
+    }
+
+    if let Some(y2_label) = &graph.y2_label {
+        canvas.text(
+            y2_label.clone(),
+            Vec2::new(
+                rect.world_center.x + rect.world_size.x * 0.5 - 12.0,
+                rect.world_center.y,
+            ),
+            14.0,
+            label_color,
+            -FRAC_PI_2,
+        );
+    }
+
+    for layer in &graph.layers {
+        let layer = on_primary_axis(layer, view);
+        let color = style_color(&layer.style, layer.style.opacity);
+        let to_world = |pts: &[Vec2]| -> Vec<Vec2> {
+            pts.iter().map(|&p| data_to_world(p, rect, view)).collect()
+        };
+
+        match layer.geometry {
+            Geometry2D::Line => {
+                canvas.line(&to_world(&layer.xy), layer.style.size, color);
+            }
+            Geometry2D::Points => {
+                for (i, &pt) in layer.xy.iter().enumerate() {
+                    let size = layer
+                        .sizes
+                        .as_ref()
+                        .and_then(|sizes| sizes.get(i).copied())
+                        .unwrap_or(layer.style.size);
+                    canvas.quad(data_to_world(pt, rect, view), Vec2::splat(size), color);
+                }
+            }
+            Geometry2D::FillBetween => {
+                let Some(lower) = &layer.lower_line else {
+                    continue;
+                };
+                let n = layer.xy.len().min(lower.len());
+                if n < 2 {
+                    continue;
+                }
+                let mut outline = to_world(&layer.xy[..n]);
+                outline.extend(to_world(&lower[..n]).into_iter().rev());
+                canvas.polygon(&outline, color);
+            }
+            _ => {}
+        }
+    }
+
+    legend(
+        canvas,
+        graph
+            .layers
+            .iter()
+            .filter_map(|l| Some((l.label.as_deref()?, l.style.color.with_a(l.style.opacity)))),
+        rect,
+    );
+}
+
+/// Swatch and name of each labelled layer
+fn legend<'a>(
+    canvas: &mut Canvas,
+    entries: impl IntoIterator<Item = (&'a str, Color)>,
+    rect: &TileRect,
+) {
+    for entry in legend_layout(entries, rect) {
+        canvas.quad(entry.swatch_center, Vec2::new(14.0, 4.0), entry.color);
+        canvas.text(
+            entry.label,
+            entry.text_center,
+            LEGEND_FONT_SIZE,
+            Color::rgba(0.85, 0.85, 0.85, 0.95),
+            0.0,
+        );
+    }
+}
+
+fn axis_ticks(canvas: &mut Canvas, rect: &TileRect, view: &TileView, axes: &AxesStyle) {
+    let tick_color = Color::rgba(0.5, 0.5, 0.55, 0.6);
+    let tick_length = 6.0;
+
+    let layout = axis_layout(rect, view, axes);
+    let mut tick = |center: Vec2, size: Vec2, label: &str, label_at: Vec2| {
+        canvas.quad(center, size, tick_color);
+        canvas.text(label.to_string(), label_at, 10.0, TICK_LABEL, 0.0);
+    };
+    for (x, label) in &layout.x_ticks {
+        tick(
+            Vec2::new(*x, layout.anchor.y),
+            Vec2::new(1.0, tick_length),
+            label,
+            Vec2::new(*x, layout.anchor.y - 14.0),
+        );
+    }
+    for (y, label) in &layout.y_ticks {
+        tick(
+            Vec2::new(layout.anchor.x, *y),
+            Vec2::new(tick_length, 1.0),
+            label,
+            Vec2::new(layout.anchor.x - 20.0, *y),
+        );
+    }
+    let (tick_x, label_x) = layout.y2_x;
+    for (y, label) in &layout.y2_ticks {
+        tick(
+            Vec2::new(tick_x, *y),
+            Vec2::new(tick_length, 1.0),
+            label,
+            Vec2::new(label_x, *y),
+        );
+    }
+}
+
+fn annotations(canvas: &mut Canvas, annotations: &[Annotation], rect: &TileRect, view: &TileView) {
+    for mark in annotation_marks(annotations, rect, view) {
+        match mark {
+            Mark::Fill {
+                center,
+                size,
+                color,
+            } => canvas.quad(center, size, color),
+            Mark::Segment { from, to, color } => {
+                canvas.line(&[from, to], ANNOTATION_LINE_WIDTH, color)
+            }
+            Mark::Label { text, at, color } => {
+                canvas.text(text, at, ANNOTATION_FONT_SIZE, color, 0.0)
+            }
+        }
+    }
+}
+
+fn histogram(
+    canvas: &mut Canvas,
+    values: &[f32],
+    bins: usize,
+    style: &Style,
+    x_label: Option<&str>,
+    y_label: Option<&str>,
+    rect: &TileRect,
+) {
+    let Some(HistogramBins {
+        min,
+        bin_width,
+        counts,
+    }) = histogram_bins(values, bins)
+    else {
+        return;
+    };
+    let max_count = counts.iter().cloned().max().unwrap_or(1) as f32;
+
+    tile_border(canvas, rect);
+
+    let area = distribution_area(rect);
+    let bar_width = area.width / bins as f32;
+    let gap = bar_width * 0.1;
+    let color = style_color(style, style.opacity);
+
+    for (i, &count) in counts.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        let height = (count as f32 / max_count) * area.height;
+        canvas.quad(
+            Vec2::new(
+                area.left + (i as f32 + 0.5) * bar_width,
+                area.bottom + height * 0.5,
+            ),
+            Vec2::new(bar_width - gap, height),
+            color,
+        );
+    }
+
+    let step = bins / bins.min(8);
+    for i in (0..=bins).step_by(step.max(1)) {
+        let val = min + i as f32 * bin_width;
+        let x = area.left + (i as f32 / bins as f32) * area.width;
+        canvas.text(
+            format!("{:.1}", val),
+            Vec2::new(x, area.bottom - 14.0),
+            9.0,
+            TICK_LABEL,
+            0.0,
+        );
+    }
+
+    axis_labels(
+        canvas,
+        rect,
+        (x_label.unwrap_or("Value"), area.left + area.width * 0.5),
+        (
+            y_label.unwrap_or("Frequency"),
+            14.0,
+            area.bottom + area.height * 0.5,
+        ),
+    );
+}
+
+fn pdf(
+    canvas: &mut Canvas,
+    values: &[f32],
+    style: &Style,
+    x_label: Option<&str>,
+    y_label: Option<&str>,
+    rect: &TileRect,
+) {
+    let Some(KdeCurve {
+        x_min,
+        x_max,
+        points,
+        max_density,
+    }) = kde_curve(values)
+    else {
+        return;
+    };
+
+    tile_border(canvas, rect);
+
+    let area = distribution_area(rect);
+    let to_x = |x: f32| area.left + ((x - x_min) / (x_max - x_min)) * area.width;
+    let curve: Vec<Vec2> = points
+        .iter()
+        .map(|&(x, y)| Vec2::new(to_x(x), area.bottom + (y / max_density) * area.height))
+        .collect();
+
+    let mut fill = curve.clone();
+    fill.push(Vec2::new(to_x(x_max), area.bottom));
+    fill.push(Vec2::new(to_x(x_min), area.bottom));
+    canvas.polygon(&fill, style_color(style, style.opacity * 0.4));
+    canvas.line(&curve, style.size, style_color(style, style.opacity));
+
+    let n_ticks = 5;
+    for i in 0..=n_ticks {
+        let t = i as f32 / n_ticks as f32;
+        canvas.text(
+            format!("{:.1}", x_min + t * (x_max - x_min)),
+            Vec2::new(area.left + t * area.width, area.bottom - 12.0),
+            10.0,
+            TICK_LABEL,
+            0.0,
+        );
+    }
+
+    axis_labels(
+        canvas,
+        rect,
+        (x_label.unwrap_or("Value"), area.left + area.width * 0.5),
+        (
+            y_label.unwrap_or("Density"),
+            14.0,
+            area.bottom + area.height * 0.5,
+        ),
+    );
+}
+
+fn boxplot(
+    canvas: &mut Canvas,
+    groups: &[(String, Vec<f32>)],
+    style: &Style,
+    x_label: Option<&str>,
+    y_label: Option<&str>,
+    rect: &TileRect,
+) {
+    if groups.is_empty() {
+        return;
+    }
+
+    let stats: Vec<Option<BoxStats>> = groups.iter().map(|(_, v)| compute_box_stats(v)).collect();
+    let Some((y_min, y_max)) = boxplot_range(&stats) else {
+        return;
+    };
+
+    tile_border(canvas, rect);
+
+    let area = boxplot_area(rect);
+    let to_y = |y: f32| area.bottom + ((y - y_min) / (y_max - y_min)) * area.height;
+
+    let group_width = area.width / groups.len() as f32;
+    let box_width = group_width * 0.6;
+
+    let box_color = style_color(style, style.opacity);
+    let median_color = Color::rgba(1.0, 0.8, 0.2, 1.0);
+    let whisker_color = Color::rgba(0.7, 0.7, 0.7, 0.9);
+    let outlier_color = Color::rgba(0.9, 0.3, 0.3, 0.8);
+
+    for (i, (label, _)) in groups.iter().enumerate() {
+        let cx = area.left + (i as f32 + 0.5) * group_width;
+
+        if let Some(s) = &stats[i] {
+            let (q1, q3, med) = (to_y(s.q1), to_y(s.q3), to_y(s.median));
+            let (lo, hi) = (to_y(s.min), to_y(s.max));
+
+            // Whiskers first, the box covers their inner ends
+            if q1 - lo > 1.0 {
+                canvas.quad(
+                    Vec2::new(cx, (q1 + lo) * 0.5),
+                    Vec2::new(2.0, q1 - lo),
+                    whisker_color,
+                );
+                canvas.quad(
+                    Vec2::new(cx, lo),
+                    Vec2::new(box_width * 0.5, 2.0),
+                    whisker_color,
+                );
+            }
+            if hi - q3 > 1.0 {
+                canvas.quad(
+                    Vec2::new(cx, (q3 + hi) * 0.5),
+                    Vec2::new(2.0, hi - q3),
+                    whisker_color,
+                );
+                canvas.quad(
+                    Vec2::new(cx, hi),
+                    Vec2::new(box_width * 0.5, 2.0),
+                    whisker_color,
+                );
+            }
+
+            canvas.quad(
+                Vec2::new(cx, (q1 + q3) * 0.5),
+                Vec2::new(box_width, (q3 - q1).max(2.0)),
+                box_color,
+            );
+            canvas.quad(Vec2::new(cx, med), Vec2::new(box_width, 3.0), median_color);
+
+            for &o in &s.outliers {
+                canvas.quad(Vec2::new(cx, to_y(o)), Vec2::splat(5.0), outlier_color);
+            }
+        }
+
+        canvas.text(
+            label.clone(),
+            Vec2::new(cx, area.bottom - 12.0),
+            9.0,
+            Color::rgba(0.8, 0.8, 0.8, 0.9),
+            0.0,
+        );
+    }
+
+    let n_ticks = 5;
+    for i in 0..=n_ticks {
+        let val = y_min + (i as f32 / n_ticks as f32) * (y_max - y_min);
+        canvas.text(
+            format!("{:.1}", val),
+            Vec2::new(area.left - 20.0, to_y(val)),
+            9.0,
+            TICK_LABEL,
+            0.0,
+        );
+    }
+
+    axis_labels(
+        canvas,
+        rect,
+        (x_label.unwrap_or("Group"), area.left + area.width * 0.5),
+        (
+            y_label.unwrap_or("Value"),
+            10.0,
+            area.bottom + area.height * 0.5,
+        ),
+    );
+}
+
+fn ecdf(
+    canvas: &mut Canvas,
+    values: &[f32],
+    style: &Style,
+    x_label: Option<&str>,
+    y_label: Option<&str>,
+    rect: &TileRect,
+) {
+    let Some(sorted) = ecdf_sorted(values) else {
+        return;
+    };
+    let min_val = sorted[0];
+    let max_val = sorted[sorted.len() - 1];
+
+    tile_border(canvas, rect);
+
+    let area = distribution_area(rect);
+    let steps = ecdf_steps(&sorted, &area);
+
+    let mut fill = steps.clone();
+    if let Some(last) = steps.last() {
+        fill.push(Vec2::new(last.x, area.bottom));
+    }
+    canvas.polygon(&fill, style_color(style, style.opacity * 0.3));
+    canvas.line(&steps, style.size, style_color(style, style.opacity));
+
+    let n_ticks = 5;
+    for i in 0..=n_ticks {
+        let t = i as f32 / n_ticks as f32;
+        canvas.text(
+            format!("{:.1}", min_val + t * (max_val - min_val)),
+            Vec2::new(area.left + t * area.width, area.bottom - 12.0),
+            10.0,
+            TICK_LABEL,
+            0.0,
+        );
+    }
+    for i in 0..=4 {
+        let t = i as f32 / 4.0;
+        canvas.text(
+            format!("{:.1}", t),
+            Vec2::new(area.left - 18.0, area.bottom + t * area.height),
+            10.0,
+            TICK_LABEL,
+            0.0,
+        );
+    }
+
+    axis_labels(
+        canvas,
+        rect,
+        (x_label.unwrap_or("Value"), area.left + area.width * 0.5),
+        (
+            y_label.unwrap_or("Cumulative Probability"),
+            10.0,
+            area.bottom + area.height * 0.5,
+        ),
+    );
+}
+
+fn candlestick(
+    canvas: &mut Canvas,
+    candle: &crate::core::Candlestick,
+    rect: &TileRect,
+    view: &TileView,
+) {
+    if candle.candles.is_empty() {
+        return;
+    }
+
+    tile_border(canvas, rect);
+
+    let body_width = candle_width(&candle.candles);
+    let body_world_width = body_width * view.scale.x;
+    let wick_world_width = body_world_width * 0.15;
+
+    let half_size = rect.world_size * 0.5;
+    let (visible_min, visible_max) = visible_data_range(rect, view);
+
+    for c in &candle.candles {
+        if c.x + body_width * 0.5 < visible_min.x || c.x - body_width * 0.5 > visible_max.x {
+            continue;
+        }
+        if c.high < visible_min.y || c.low > visible_max.y {
+            continue;
+        }
+
+        let color = if c.close >= c.open {
+            candle.up_color
+        } else {
+            candle.down_color
+        };
+        let top = data_to_world(Vec2::new(c.x, c.open.max(c.close)), rect, view);
+        let bottom = data_to_world(Vec2::new(c.x, c.open.min(c.close)), rect, view);
+        let high = data_to_world(Vec2::new(c.x, c.high), rect, view);
+        let low = data_to_world(Vec2::new(c.x, c.low), rect, view);
+
+        if high.y - top.y > 0.5 {
+            canvas.quad(
+                Vec2::new(top.x, (high.y + top.y) * 0.5),
+                Vec2::new(wick_world_width, high.y - top.y),
+                color,
+            );
+        }
+        if bottom.y - low.y > 0.5 {
+            canvas.quad(
+                Vec2::new(top.x, (bottom.y + low.y) * 0.5),
+                Vec2::new(wick_world_width, bottom.y - low.y),
+                color,
+            );
+        }
+        canvas.quad(
+            Vec2::new(top.x, (top.y + bottom.y) * 0.5),
+            Vec2::new(body_world_width, (top.y - bottom.y).max(1.0)),
+            color,
+        );
+    }
+
+    let y_step = nice_step(visible_max.y - visible_min.y, 6);
+    for i in (visible_min.y / y_step).floor() as i32..=(visible_max.y / y_step).ceil() as i32 {
+        let y_data = i as f32 * y_step;
+        let y = data_to_world(Vec2::new(0.0, y_data), rect, view).y;
+        if y < rect.world_center.y - half_size.y + 20.0
+            || y > rect.world_center.y + half_size.y - 10.0
+        {
+            continue;
+        }
+        canvas.text(
+            format_tick(y_data),
+            Vec2::new(rect.world_center.x - half_size.x + 25.0, y),
+            9.0,
+            TICK_LABEL,
+            0.0,
+        );
+    }
+
+    let x_step = nice_step(visible_max.x - visible_min.x, 8);
+    for i in (visible_min.x / x_step).floor() as i32..=(visible_max.x / x_step).ceil() as i32 {
+        let x_data = i as f32 * x_step;
+        let x = data_to_world(Vec2::new(x_data, 0.0), rect, view).x;
+        if x < rect.world_center.x - half_size.x + 50.0
+            || x > rect.world_center.x + half_size.x - 20.0
+        {
+            continue;
+        }
+        canvas.text(
+            format!("{:.0}", x_data),
+            Vec2::new(x, rect.world_center.y - half_size.y + 12.0),
+            9.0,
+            TICK_LABEL,
+            0.0,
+        );
+    }
+
+    canvas.text(
+        candle.x_label.as_deref().unwrap_or("Time"),
+        Vec2::new(
+            rect.world_center.x,
+            rect.world_center.y - half_size.y + 25.0,
+        ),
+        11.0,
+        AXIS_LABEL,
+        0.0,
+    );
+    canvas.text(
+        candle.y_label.as_deref().unwrap_or("Price"),
+        Vec2::new(
+            rect.world_center.x - half_size.x + 15.0,
+            rect.world_center.y,
+        ),
+        11.0,
+        AXIS_LABEL,
+        FRAC_PI_2,
+    );
+}
+
+fn heatmap_cells(canvas: &mut Canvas, heatmap: &crate::core::Heatmap, rect: &TileRect) {
+    let rows = heatmap.dims.y as usize;
+    let cols = heatmap.dims.x as usize;
+    if rows == 0 || cols == 0 || heatmap.values.is_empty() {
+        return;
+    }
+
+    let (vmin, vrange) = heatmap_range(heatmap);
+
+    tile_border(canvas, rect);
+
+    let area = heatmap_area(rect, heatmap);
+    let cell_width = area.width / cols as f32;
+    let cell_height = area.height / rows as f32;
+    let top = area.bottom + area.height;
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let Some(&value) = heatmap.values.get(row * cols + col) else {
+                continue;
+            };
+            let t = ((value - vmin) / vrange).clamp(0.0, 1.0);
+            let center = Vec2::new(
+                area.left + (col as f32 + 0.5) * cell_width,
+                top - (row as f32 + 0.5) * cell_height,
+            );
+            canvas.quad(
+                center,
+                Vec2::new(cell_width - 1.0, cell_height - 1.0),
+                heatmap.colormap.sample(t),
+            );
+
+            if heatmap.show_values && cell_width > 20.0 && cell_height > 15.0 {
+                let text_color = if t > 0.5 {
+                    Color::rgba(0.0, 0.0, 0.0, 0.9)
+                } else {
+                    Color::rgba(1.0, 1.0, 1.0, 0.9)
+                };
+                canvas.text(
+                    format!("{:.1}", value),
+                    center,
+                    (cell_height * 0.4).clamp(6.0, 12.0),
+                    text_color,
+                    0.0,
+                );
+            }
+        }
+    }
+
+    let label_color = Color::rgba(0.8, 0.8, 0.8, 0.9);
+    if let Some(row_labels) = &heatmap.row_labels {
+        for (row, label) in row_labels.iter().enumerate().take(rows) {
+            canvas.text(
+                label.clone(),
+                Vec2::new(area.left - 12.0, top - (row as f32 + 0.5) * cell_height),
+                9.0,
+                label_color,
+                0.0,
+            );
+        }
+    }
+    if let Some(col_labels) = &heatmap.col_labels {
+        for (col, label) in col_labels.iter().enumerate().take(cols) {
+            canvas.text(
+                label.clone(),
+                Vec2::new(
+                    area.left + (col as f32 + 0.5) * cell_width,
+                    area.bottom - 15.0,
+                ),
+                9.0,
+                label_color,
+                -0.4,
+            );
+        }
+    }
+
+    // Colorbar in the right padding, which the renderer leaves empty
+    let bar_width = 8.0;
+    let bar_x = area.left + area.width + 6.0 + bar_width * 0.5;
+    let stops = (0..=16)
+        .map(|i| heatmap.colormap.sample(i as f32 / 16.0))
+        .collect();
+    canvas.gradient(
+        Vec2::new(bar_x, area.bottom + area.height * 0.5),
+        Vec2::new(bar_width, area.height),
+        stops,
+    );
+    canvas.text(
+        format_tick(vmin + vrange),
+        Vec2::new(bar_x, top + 7.0),
+        8.0,
+        TICK_LABEL,
+        0.0,
+    );
+    canvas.text(
+        format_tick(vmin),
+        Vec2::new(bar_x, area.bottom - 7.0),
+        8.0,
+        TICK_LABEL,
+        0.0,
+    );
+}
+
+fn pie(canvas: &mut Canvas, slices: &[(String, f32)], rect: &TileRect) {
+    if slices.is_empty() {
+        return;
+    }
+
+    tile_border(canvas, rect);
+
+    let total: f32 = slices.iter().map(|(_, v)| v.max(0.0)).sum();
+    if total <= 0.0 {
+        return;
+    }
+
+    let center = rect.world_center;
+    let radius = radial_radius(rect);
+    let mut start_angle = -FRAC_PI_2;
+
+    for (i, (label, value)) in slices.iter().enumerate() {
+        if *value <= 0.0 {
+            continue;
+        }
+        let sweep = (*value / total) * TAU;
+
+        // Finer arcs than the renderer's 32 segments, vector output gets zoomed
+        let segments = ((sweep / TAU) * 128.0).ceil().max(2.0) as usize;
+        let mut outline = vec![center];
+        outline.extend((0..=segments).map(|j| {
+            let angle = start_angle + (j as f32 / segments as f32) * sweep;
+            center + radius * Vec2::from_angle(angle)
+        }));
+        let color = pie_color(i).to_srgba();
+        canvas.polygon(
+            &outline,
+            Color::rgba(color.red, color.green, color.blue, color.alpha),
+        );
+
+        let mid_angle = start_angle + sweep * 0.5;
+        canvas.text(
+            format!("{}\n{:.1}%", label, (*value / total) * 100.0),
+            center + radius * 1.25 * Vec2::from_angle(mid_angle),
+            10.0,
+            Color::rgba(0.9, 0.9, 0.9, 1.0),
+            0.0,
+        );
+
+        start_angle += sweep;
+    }
+}
+
+fn radar(canvas: &mut Canvas, axes: &[String], values: &[f32], style: &Style, rect: &TileRect) {
+    let n = axes.len().min(values.len());
+    if n < 3 {
+        return;
+    }
+
+    tile_border(canvas, rect);
+
+    let center = rect.world_center;
+    let radius = radial_radius(rect);
+    let angle_step = TAU / n as f32;
+    let axis_angle = |i: usize| -FRAC_PI_2 + i as f32 * angle_step;
+
+    let grid_color = Color::rgba(0.4, 0.4, 0.5, 0.5);
+    for ring in 1..=4 {
+        let r = radius * (ring as f32 / 4.0);
+        let circle: Vec<Vec2> = (0..64)
+            .map(|j| center + r * Vec2::from_angle(j as f32 / 64.0 * TAU))
+            .collect();
+        canvas.closed_line(&circle, 1.0, grid_color);
+    }
+
+    let axis_color = Color::rgba(0.5, 0.5, 0.6, 0.7);
+    for (i, axis) in axes.iter().enumerate().take(n) {
+        let dir = Vec2::from_angle(axis_angle(i));
+        canvas.line(&[center, center + radius * dir], 1.5, axis_color);
+        canvas.text(
+            axis.clone(),
+            center + (radius + 20.0) * dir,
+            10.0,
+            Color::rgba(0.8, 0.8, 0.8, 0.9),
+            0.0,
+        );
+    }
+
+    let points: Vec<Vec2> = (0..n)
+        .map(|i| center + radius * values[i].clamp(0.0, 1.0) * Vec2::from_angle(axis_angle(i)))
+        .collect();
+    canvas.polygon(&points, style_color(style, style.opacity * 0.4));
+
+    let line_color = style_color(style, style.opacity);
+    canvas.closed_line(&points, style.size, line_color);
+    for &p in &points {
+        canvas.quad(p, Vec2::splat(6.0), line_color);
+    }
+}
+
+fn tab_bar(canvas: &mut Canvas, dash: &Dashboard) {
+    let Vec2 {
+        x: width,
+        y: height,
+    } = canvas.size;
+    let tab_bar_height = 35.0;
+    let tab_width = 100.0;
+    let tab_gap = 5.0;
+    let margin = 20.0;
+    let tab_y = height * 0.5 - margin - tab_bar_height * 0.5;
+
+    canvas.quad(
+        Vec2::new(0.0, tab_y),
+        Vec2::new(width - margin * 2.0, tab_bar_height),
+        Color::rgba(0.15, 0.15, 0.2, 0.95),
+    );
+
+    let names = dash.tab_names();
+    let total_width = names.len() as f32 * (tab_width + tab_gap) - tab_gap;
+    let start_x = -total_width * 0.5 + tab_width * 0.5;
+
+    for (i, name) in names.iter().enumerate() {
+        let active = i == dash.active_tab;
+        let tab_x = start_x + i as f32 * (tab_width + tab_gap);
+        let (fill, text) = if active {
+            (
+                Color::rgba(0.3, 0.5, 0.8, 1.0),
+                Color::rgba(1.0, 1.0, 1.0, 1.0),
+            )
+        } else {
+            (
+                Color::rgba(0.25, 0.25, 0.3, 0.9),
+                Color::rgba(0.7, 0.7, 0.7, 0.9),
+            )
+        };
+        canvas.quad(
+            Vec2::new(tab_x, tab_y),
+            Vec2::new(tab_width, tab_bar_height - 6.0),
+            fill,
+        );
+        canvas.text(*name, Vec2::new(tab_x, tab_y), 13.0, text, 0.0);
+    }
+}
@@ -0,0 +1,206 @@
+//! SVG serialization of a [`Canvas`].
+
+use super::canvas::{Canvas, Shape, num, text_lines};
+use crate::core::Color;
+use bevy_math::Vec2;
+use std::fmt::Write;
+
+/// Monospace stack closest to Bevy's built-in Fira Mono
+const FONT_FAMILY: &str = "'Fira Mono', 'DejaVu Sans Mono', Menlo, Consolas, monospace";
+
+pub(crate) fn write_svg(canvas: &Canvas) -> String {
+    let (w, h) = (num(canvas.size.x), num(canvas.size.y));
+    let mut out = String::new();
+    let mut defs = String::new();
+    let mut body = String::new();
+
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="{FONT_FAMILY}">"#
+    );
+    let _ = writeln!(
+        body,
+        r#"<rect width="{w}" height="{h}"{}/>"#,
+        paint("fill", canvas.background)
+    );
+
+    let mut gradients = 0;
+    for (i, group) in canvas.groups.iter().enumerate() {
+        if group.shapes.is_empty() {
+            continue;
+        }
+        match group.clip {
+            Some(clip) => {
+                let _ = writeln!(
+                    defs,
+                    r#"<clipPath id="tile{i}"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath>"#,
+                    num(clip.min.x),
+                    num(clip.min.y),
+                    num(clip.width()),
+                    num(clip.height())
+                );
+                let _ = writeln!(body, r#"<g clip-path="url(#tile{i})">"#);
+            }
+            None => body.push_str("<g>\n"),
+        }
+        for shape in &group.shapes {
+            write_shape(&mut body, &mut defs, &mut gradients, shape);
+        }
+        body.push_str("</g>\n");
+    }
+
+    if !defs.is_empty() {
+        let _ = write!(out, "<defs>\n{defs}</defs>\n");
+    }
+    out.push_str(&body);
+    out.push_str("</svg>\n");
+    out
+}
+
+fn write_shape(out: &mut String, defs: &mut String, gradients: &mut usize, shape: &Shape) {
+    match shape {
+        Shape::Rect { rect, fill } => {
+            let _ = writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="{}" height="{}"{}/>"#,
+                num(rect.min.x),
+                num(rect.min.y),
+                num(rect.width()),
+                num(rect.height()),
+                paint("fill", *fill)
+            );
+        }
+        Shape::Line {
+            points,
+            width,
+            color,
+            closed,
+        } => {
+            let tag = if *closed { "polygon" } else { "polyline" };
+            let _ = writeln!(
+                out,
+                r#"<{tag} points="{}" fill="none" stroke-width="{}" stroke-linejoin="round"{}/>"#,
+                point_list(points),
+                num(*width),
+                paint("stroke", *color)
+            );
+        }
+        Shape::Polygon { points, fill } => {
+            let _ = writeln!(
+                out,
+                r#"<polygon points="{}"{}/>"#,
+                point_list(points),
+                paint("fill", *fill)
+            );
+        }
+        Shape::Text {
+            text,
+            pos,
+            size,
+            color,
+            rotation,
+        } => {
+            let transform = if *rotation != 0.0 {
+                format!(
+                    r#" transform="rotate({} {} {})""#,
+                    num(rotation.to_degrees()),
+                    num(pos.x),
+                    num(pos.y)
+                )
+            } else {
+                String::new()
+            };
+            let _ = write!(
+                out,
+                r#"<text x="{}" y="{}" font-size="{}" text-anchor="middle" dominant-baseline="central"{}{}>"#,
+                num(pos.x),
+                num(pos.y),
+                num(*size),
+                paint("fill", *color),
+                transform
+            );
+            if text.contains('\n') {
+                for (dy, line) in text_lines(text, *size) {
+                    let _ = write!(
+                        out,
+                        r#"<tspan x="{}" y="{}">{}</tspan>"#,
+                        num(pos.x),
+                        num(pos.y + dy),
+                        escape(line)
+                    );
+                }
+            } else {
+                out.push_str(&escape(text));
+            }
+            out.push_str("</text>\n");
+        }
+        Shape::Gradient { rect, stops } => {
+            let id = format!("gradient{}", *gradients);
+            *gradients += 1;
+            let _ = write!(
+                defs,
+                r#"<linearGradient id="{id}" x1="0" y1="1" x2="0" y2="0">"#
+            );
+            let last = stops.len().saturating_sub(1).max(1) as f32;
+            for (i, stop) in stops.iter().enumerate() {
+                let _ = write!(
+                    defs,
+                    r#"<stop offset="{}" stop-color="{}" stop-opacity="{}"/>"#,
+                    num(i as f32 / last),
+                    hex(*stop),
+                    num(stop.a)
+                );
+            }
+            defs.push_str("</linearGradient>\n");
+            let _ = writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="url(#{id})"/>"#,
+                num(rect.min.x),
+                num(rect.min.y),
+                num(rect.width()),
+                num(rect.height())
+            );
+        }
+    }
+}
+
+/// `fill`/`stroke` attribute plus opacity when not fully opaque
+fn paint(attr: &str, color: Color) -> String {
+    if color.a >= 1.0 {
+        format!(r#" {attr}="{}""#, hex(color))
+    } else {
+        format!(
+            r#" {attr}="{}" {attr}-opacity="{}""#,
+            hex(color),
+            num(color.a.max(0.0))
+        )
+    }
+}
+
+fn hex(color: Color) -> String {
+    let byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        byte(color.r),
+        byte(color.g),
+        byte(color.b)
+    )
+}
+
+fn point_list(points: &[Vec2]) -> String {
+    let mut out = String::with_capacity(points.len() * 12);
+    for (i, p) in points.iter().enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        let _ = write!(out, "{},{}", num(p.x), num(p.y));
+    }
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
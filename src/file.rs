@@ -0,0 +1,420 @@
+//! Versioned dashboard files.
+//!
+//! A saved dashboard is wrapped in an envelope recording the schema version:
+//!
+//! ```json
+//! { "format": "vidi-dashboard", "version": 1, "dashboard": { ... } }
+//! ```
+//!
+//! Loading upgrades older versions step by step through [`MIGRATIONS`] before
+//! deserializing, so files written by earlier releases keep opening. Bare
+//! dashboard JSON without an envelope (what `serde_json` produced before the
+//! format existed) is treated as version 0.
+//!
+//! The binary encoding stores the same envelope as a JSON header and moves large
+//! float arrays (trace points, heatmap values, samples) into a little-endian
+//! `f32` payload, which is several times smaller and faster to parse:
+//!
+//! ```text
+//! "VIDIDASH" | header length (u32 LE) | header JSON | f32 payload
+//! ```
+//!
+//! A packed array is replaced in the header by `{"$f32": [offset, len, width]}`,
+//! where `width` is 0 for an array of numbers and the row length for an array
+//! of equal-length rows such as `Vec2` points.
+
+use crate::core::Dashboard;
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+/// Schema version written by this release
+pub const FORMAT_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Value of the envelope's `format` field
+const FORMAT_NAME: &str = "vidi-dashboard";
+
+/// Leading bytes of a binary file
+const BINARY_MAGIC: &[u8; 8] = b"VIDIDASH";
+
+/// Header key marking a packed array
+const PACKED_KEY: &str = "$f32";
+
+/// Arrays shorter than this stay in the JSON header
+const MIN_PACKED_LEN: usize = 64;
+
+/// Longest row packed as a unit (`Vec4`)
+const MAX_PACKED_WIDTH: usize = 4;
+
+/// Upgrade of the dashboard JSON by one version
+type Migration = fn(&mut Value) -> Result<(), String>;
+
+/// Upgrades to the dashboard JSON, indexed by the version they upgrade from
+const MIGRATIONS: &[Migration] = &[migrate_v0];
+
+/// On-disk encoding of a dashboard file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FileEncoding {
+    /// Human-readable JSON envelope
+    #[default]
+    Json,
+    /// JSON header with float arrays in a binary payload
+    Binary,
+}
+
+impl FileEncoding {
+    /// Encoding implied by a file extension: `.vidib` is binary, anything else JSON
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("vidib") => FileEncoding::Binary,
+            _ => FileEncoding::Json,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Envelope<'a> {
+    format: &'static str,
+    version: u32,
+    dashboard: &'a Dashboard,
+}
+
+impl Dashboard {
+    /// Encode the dashboard in the versioned file format
+    pub fn to_file_bytes(&self, encoding: FileEncoding) -> Result<Vec<u8>, String> {
+        let envelope = Envelope {
+            format: FORMAT_NAME,
+            version: FORMAT_VERSION,
+            dashboard: self,
+        };
+        match encoding {
+            FileEncoding::Json => serde_json::to_vec_pretty(&envelope)
+                .map_err(|e| format!("Failed to serialize dashboard: {}", e)),
+            FileEncoding::Binary => {
+                // Straight to a `Value` so floats are still exact `f32`s when packed
+                let mut header = serde_json::to_value(&envelope)
+                    .map_err(|e| format!("Failed to serialize dashboard: {}", e))?;
+                let mut payload = Vec::new();
+                pack(&mut header, &mut payload);
+                let header = serde_json::to_vec(&header)
+                    .map_err(|e| format!("Failed to serialize dashboard: {}", e))?;
+                let header_len = u32::try_from(header.len())
+                    .map_err(|_| "Dashboard header is too large".to_string())?;
+
+                let mut out = Vec::with_capacity(12 + header.len() + payload.len());
+                out.extend_from_slice(BINARY_MAGIC);
+                out.extend_from_slice(&header_len.to_le_bytes());
+                out.extend_from_slice(&header);
+                out.extend_from_slice(&payload);
+                Ok(out)
+            }
+        }
+    }
+
+    /// Decode a dashboard file in either encoding, migrating older versions
+    pub fn from_file_bytes(bytes: &[u8]) -> Result<Dashboard, String> {
+        let value = match bytes.strip_prefix(BINARY_MAGIC) {
+            Some(rest) => decode_binary(rest)?,
+            None => serde_json::from_slice(bytes)
+                .map_err(|e| format!("Invalid dashboard file: {}", e))?,
+        };
+
+        let (version, mut dashboard) = open_envelope(value)?;
+        if version > FORMAT_VERSION {
+            return Err(format!(
+                "Dashboard file version {} is newer than the supported version {}",
+                version, FORMAT_VERSION
+            ));
+        }
+        for migrate in &MIGRATIONS[version as usize..] {
+            migrate(&mut dashboard)?;
+        }
+
+        let mut dashboard: Dashboard = serde_json::from_value(dashboard)
+            .map_err(|e| format!("Invalid dashboard file: {}", e))?;
+        dashboard.ensure_unique_ids();
+        Ok(dashboard)
+    }
+
+    /// Save the dashboard to a file, binary if the extension is `.vidib`
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        self.save_as(path, FileEncoding::from_path(path))
+    }
+
+    /// Save the dashboard to a file with an explicit encoding
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_as(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        encoding: FileEncoding,
+    ) -> Result<(), String> {
+        let bytes = self.to_file_bytes(encoding)?;
+        std::fs::write(path.as_ref(), bytes)
+            .map_err(|e| format!("Failed to write {}: {}", path.as_ref().display(), e))
+    }
+
+    /// Load a dashboard saved by [`Dashboard::save`] (any version, either encoding)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Dashboard, String> {
+        let bytes = std::fs::read(path.as_ref())
+            .map_err(|e| format!("Failed to read {}: {}", path.as_ref().display(), e))?;
+        Self::from_file_bytes(&bytes).map_err(|e| format!("{}: {}", path.as_ref().display(), e))
+    }
+}
+
+/// Split a file into its version and dashboard JSON
+fn open_envelope(value: Value) -> Result<(u32, Value), String> {
+    let Value::Object(mut map) = value else {
+        return Err("Invalid dashboard file: expected a JSON object".to_string());
+    };
+    if map.get("format").and_then(Value::as_str) != Some(FORMAT_NAME) {
+        // Bare dashboard from before the envelope existed
+        return Ok((0, Value::Object(map)));
+    }
+
+    let version = map
+        .get("version")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or("Invalid dashboard file: missing version")?;
+    let dashboard = map
+        .remove("dashboard")
+        .ok_or("Invalid dashboard file: missing dashboard")?;
+    Ok((version, dashboard))
+}
+
+/// Version 0 (bare JSON): fill fields older writers could omit and fold the
+/// legacy `layout: { cols, rows }` object into `columns`
+fn migrate_v0(dashboard: &mut Value) -> Result<(), String> {
+    let map = dashboard
+        .as_object_mut()
+        .ok_or("Invalid dashboard: expected a JSON object")?;
+
+    if let Some(layout) = map.remove("layout") {
+        let cols = layout.get("cols").and_then(Value::as_u64);
+        if let (Some(cols), None) = (cols, map.get("columns")) {
+            map.insert("columns".into(), json!(cols));
+        }
+    }
+
+    let defaults = serde_json::to_value(Dashboard::default())
+        .map_err(|e| format!("Failed to serialize dashboard: {}", e))?;
+    if let Value::Object(defaults) = defaults {
+        for (key, value) in defaults {
+            map.entry(key).or_insert(value);
+        }
+    }
+    Ok(())
+}
+
+/// Move large float arrays out of `value` into `payload`
+fn pack(value: &mut Value, payload: &mut Vec<u8>) {
+    match value {
+        Value::Array(items) => match pack_array(items, payload) {
+            Some(packed) => *value = packed,
+            None => items.iter_mut().for_each(|item| pack(item, payload)),
+        },
+        Value::Object(map) => map.values_mut().for_each(|v| pack(v, payload)),
+        _ => {}
+    }
+}
+
+fn pack_array(items: &[Value], payload: &mut Vec<u8>) -> Option<Value> {
+    if items.len() < MIN_PACKED_LEN {
+        return None;
+    }
+    let width = match &items[0] {
+        Value::Array(row) if (1..=MAX_PACKED_WIDTH).contains(&row.len()) => row.len(),
+        Value::Array(_) => return None,
+        _ => 0,
+    };
+
+    let mut floats = Vec::with_capacity(items.len() * width.max(1));
+    for item in items {
+        match item {
+            Value::Array(row) if width > 0 && row.len() == width => {
+                for v in row {
+                    floats.push(exact_f32(v)?);
+                }
+            }
+            v if width == 0 => floats.push(exact_f32(v)?),
+            _ => return None,
+        }
+    }
+
+    let offset = payload.len() / 4;
+    for f in floats {
+        payload.extend_from_slice(&f.to_le_bytes());
+    }
+    Some(json!({ PACKED_KEY: [offset, items.len(), width] }))
+}
+
+/// A float that survives the trip through `f32`. Integers are left alone so
+/// they deserialize back into integer fields.
+fn exact_f32(v: &Value) -> Option<f32> {
+    if !v.is_f64() {
+        return None;
+    }
+    let f = v.as_f64()?;
+    let single = f as f32;
+    (single as f64 == f).then_some(single)
+}
+
+fn decode_binary(bytes: &[u8]) -> Result<Value, String> {
+    let truncated = || "Invalid dashboard file: truncated binary data".to_string();
+    let (len, rest) = bytes.split_first_chunk::<4>().ok_or_else(truncated)?;
+    let len = u32::from_le_bytes(*len) as usize;
+    if rest.len() < len {
+        return Err(truncated());
+    }
+    let (header, payload) = rest.split_at(len);
+
+    let mut value: Value = serde_json::from_slice(header)
+        .map_err(|e| format!("Invalid dashboard file header: {}", e))?;
+    unpack(&mut value, payload)?;
+    Ok(value)
+}
+
+/// Restore packed arrays from `payload`
+fn unpack(value: &mut Value, payload: &[u8]) -> Result<(), String> {
+    match value {
+        Value::Object(map) => match packed_ref(map) {
+            Some(packed) => *value = unpack_array(packed, payload)?,
+            None => {
+                for v in map.values_mut() {
+                    unpack(v, payload)?;
+                }
+            }
+        },
+        Value::Array(items) => {
+            for item in items {
+                unpack(item, payload)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn packed_ref(map: &Map<String, Value>) -> Option<[usize; 3]> {
+    if map.len() != 1 {
+        return None;
+    }
+    let fields = map.get(PACKED_KEY)?.as_array()?;
+    match fields.as_slice() {
+        [offset, len, width] => Some([
+            offset.as_u64()? as usize,
+            len.as_u64()? as usize,
+            width.as_u64()? as usize,
+        ]),
+        _ => None,
+    }
+}
+
+fn unpack_array([offset, len, width]: [usize; 3], payload: &[u8]) -> Result<Value, String> {
+    let count = len
+        .checked_mul(width.max(1))
+        .filter(|_| width <= MAX_PACKED_WIDTH)
+        .ok_or("Invalid dashboard file: bad packed array")?;
+    let bytes = offset
+        .checked_mul(4)
+        .and_then(|start| Some(start..start.checked_add(count.checked_mul(4)?)?))
+        .and_then(|range| payload.get(range))
+        .ok_or("Invalid dashboard file: packed array out of bounds")?;
+
+    let mut floats = bytes
+        .chunks_exact(4)
+        .map(|b| json!(f32::from_le_bytes([b[0], b[1], b[2], b[3]])));
+    let items = if width == 0 {
+        floats.collect()
+    } else {
+        (0..len)
+            .map(|_| Value::Array(floats.by_ref().take(width).collect()))
+            .collect()
+    };
+    Ok(Value::Array(items))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dash::dash;
+    use bevy_math::Vec2;
+
+    fn sample() -> Dashboard {
+        let wave: Vec<Vec2> = (0..200)
+            .map(|i| Vec2::new(i as f32 * 0.1, (i as f32 * 0.1).sin()))
+            .collect();
+        dash()
+            .add_2d(|p| p.line(wave, None).title("wave"))
+            .add_distribution(|d| d.histogram((0..100).map(|i| i as f32 * 0.5).collect()))
+            .build()
+    }
+
+    fn same(a: &Dashboard, b: &Dashboard) -> bool {
+        serde_json::to_value(a).unwrap() == serde_json::to_value(b).unwrap()
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let dashboard = sample();
+        let bytes = dashboard.to_file_bytes(FileEncoding::Json).unwrap();
+        let value: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(value["format"], FORMAT_NAME);
+        assert_eq!(value["version"], FORMAT_VERSION);
+        assert!(same(
+            &Dashboard::from_file_bytes(&bytes).unwrap(),
+            &dashboard
+        ));
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let dashboard = sample();
+        let bytes = dashboard.to_file_bytes(FileEncoding::Binary).unwrap();
+        assert!(bytes.starts_with(BINARY_MAGIC));
+        let json = dashboard.to_file_bytes(FileEncoding::Json).unwrap();
+        assert!(bytes.len() < json.len() / 2);
+        assert!(same(
+            &Dashboard::from_file_bytes(&bytes).unwrap(),
+            &dashboard
+        ));
+    }
+
+    #[test]
+    fn bare_v0_layout_migrates() {
+        let bare = json!({
+            "plots": [],
+            "layout": { "cols": 3, "rows": 2 },
+        });
+        let dashboard = Dashboard::from_file_bytes(&serde_json::to_vec(&bare).unwrap()).unwrap();
+        assert_eq!(dashboard.columns, Some(3));
+
+        let future =
+            json!({ "format": FORMAT_NAME, "version": FORMAT_VERSION + 1, "dashboard": {} });
+        assert!(Dashboard::from_file_bytes(&serde_json::to_vec(&future).unwrap()).is_err());
+    }
+
+    #[test]
+    fn bad_binary_is_an_error() {
+        let bytes = sample().to_file_bytes(FileEncoding::Binary).unwrap();
+        for len in (0..bytes.len()).step_by(7) {
+            assert!(Dashboard::from_file_bytes(&bytes[..len]).is_err(), "{len}");
+        }
+
+        let mut garbage = BINARY_MAGIC.to_vec();
+        garbage.extend_from_slice(&u32::MAX.to_le_bytes());
+        garbage.extend_from_slice(b"{}");
+        assert!(Dashboard::from_file_bytes(&garbage).is_err());
+
+        let header = json!({ "format": FORMAT_NAME, "version": 1, "dashboard": { "plots": { "$f32": [u64::MAX, 5, 2] } } });
+        let header = serde_json::to_vec(&header).unwrap();
+        let mut out_of_bounds = BINARY_MAGIC.to_vec();
+        out_of_bounds.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        out_of_bounds.extend_from_slice(&header);
+        assert!(Dashboard::from_file_bytes(&out_of_bounds).is_err());
+
+        assert!(Dashboard::from_file_bytes(&[0xff; 64]).is_err());
+    }
+}
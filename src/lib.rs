@@ -68,15 +68,103 @@
 //! handle.append_points_2d(plot_id, 0, &new_points)?;
 //! ```
 //!
+//! ## Live Local Dashboard
+//!
+//! Stream updates into a native window from a background thread:
+//!
+//! ```rust,ignore
+//! dash()
+//!     .add_2d(|p| p.line(vec![], None))
+//!     .spawn_local(|handle| {
+//!         for step in 0..1000 {
+//!             handle.append_points_2d(plot_id, 0, &[(step as f32, loss)]).ok();
+//!         }
+//!     });
+//! ```
+//!
+//! ## Headless Export
+//!
+//! Render to PNG without opening a window (CI artifacts, reports):
+//!
+//! ```rust,ignore
+//! let board = dash().add_2d(|p| p.line(data, None));
+//! board.save_png("report.png", UVec2::new(1280, 720))?;
+//!
+//! // Or a single plot
+//! let png = render_plot_png(&board.build().plots[0], UVec2::new(640, 480))?;
+//! ```
+//!
+//! ## Vector Export
+//!
+//! 2D plots can be exported as SVG or PDF for papers (no GPU needed):
+//!
+//! ```rust,ignore
+//! board.save_svg("figure.svg", UVec2::new(800, 600))?;
+//! board.save_pdf("figure.pdf", UVec2::new(800, 600))?;
+//! ```
+//!
+//! ## HTML Export
+//!
+//! A single HTML file embedding the WASM viewer keeps pan and zoom and opens
+//! offline (build the viewer first with `scripts/build-wasm.sh`):
+//!
+//! ```rust,ignore
+//! board.save_html("report.html")?;
+//! ```
+//!
+//! ## Tabular Data
+//!
+//! [`data::Table`] reads CSV and JSON-lines into typed columns, and builders
+//! plot columns by name, one palette-colored layer per group:
+//!
+//! ```rust,ignore
+//! let table = Table::read("train.csv")?;
+//! dash()
+//!     .add_2d(|p| p.line_group_by(&table, "step", "loss", "run"))
+//!     .add_distribution(|d| d.histogram_from(&table, "grad_norm"))
+//!     .run_local();
+//! ```
+//!
+//! ## Saving Dashboards
+//!
+//! Dashboards save to a versioned file format and load back in later releases.
+//! A `.vidib` extension selects the compact binary encoding:
+//!
+//! ```rust,ignore
+//! board.save("run.vidi")?;
+//! let dashboard = Dashboard::load("run.vidi")?;
+//! ```
+//!
+//! Saved files open in a window with `vidi open run.vidi`.
+//!
+//! ## Command Line
+//!
+//! The `vidi` binary (`cargo install vidi-charts --features cli`) plots CSV and
+//! JSON-lines files without writing a program, and shows, exports (`--out`) or
+//! pushes (`--push`) the result:
+//!
+//! ```text
+//! vidi line data.csv --x step --y loss,val_loss
+//! vidi hist samples.jsonl --col latency --out latency.svg
+//! vidi heatmap matrix.csv --colormap magma
+//! vidi tail train.csv --x step --y loss --push http://localhost:8080
+//! ```
+//!
 //! ## Modules
 //!
 //! - [`core`]: Data model definitions (Plot, Graph2D, Graph3D, etc.)
 //! - [`dash`]: Builder API for constructing dashboards
+//! - [`data`]: Columnar tables read from CSV and JSON-lines
+//! - [`export`]: SVG, PDF and standalone HTML export
+//! - [`file`]: Versioned dashboard file format
 //! - [`render`]: Bevy ECS rendering implementation
 //! - [`runtime`]: Application bootstrap and run loop
 
 pub mod core;
 pub mod dash;
+pub mod data;
+pub mod export;
+pub mod file;
 pub mod render;
 pub mod runtime;
 #[cfg(target_arch = "wasm32")]
@@ -115,14 +203,14 @@ pub fn start() {
 /// - Dashboard builder API (dash, DashBuilder, Plot2DBuilder, etc.)
 /// - Render components (PlotId, etc.)
 /// - Runtime functions (run_dashboard)
-/// - Web dashboard types (WebConfig, WebDashboard) on native
+/// - Web and local dashboard handles (WebConfig, WebDashboard, LocalDashboardHandle) on native
 pub mod prelude {
     pub use crate::core::*;
     pub use crate::dash::*;
     pub use crate::render::*;
     pub use crate::runtime::*;
 
-    // Re-export web dashboard types (native only)
+    // Re-export web and local dashboard handles (native only)
     #[cfg(not(target_arch = "wasm32"))]
-    pub use crate::dash::{WebConfig, WebDashboard};
+    pub use crate::dash::{LocalDashboardHandle, WebConfig, WebDashboard};
 }
@@ -26,32 +26,86 @@ pub struct PlotTile {
     pub kind: PlotKind,
 }
 
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
 pub enum PlotKind {
     TwoD,
     ThreeD,
     Placeholder,
 }
 
+/// Pan/zoom state of a 2D tile; x and y are scaled independently
 #[derive(Component, Clone, Copy)]
 pub struct TileView {
+    /// World position of the data origin relative to the tile center
     pub offset: Vec2,
-    pub scale: f32,
-    pub min_scale: f32,
-    pub max_scale: f32,
+    /// World units per data unit along each axis
+    pub scale: Vec2,
+    pub min_scale: Vec2,
+    pub max_scale: Vec2,
+    /// Axes drawn in reverse (x increasing leftwards, y downwards)
+    pub invert: BVec2,
+    /// Secondary y axis, if the plot has one, as `(a, b)`: secondary value
+    /// `y` sits at primary value `a * y + b`, so panning and zooming move
+    /// both axes together
+    pub y2_map: Option<Vec2>,
 }
 
 impl Default for TileView {
     fn default() -> Self {
         Self {
             offset: Vec2::ZERO,
-            scale: 1.0,
-            min_scale: 0.1,
-            max_scale: 100.0,
+            scale: Vec2::ONE,
+            min_scale: Vec2::splat(0.1),
+            max_scale: Vec2::splat(100.0),
+            invert: BVec2::FALSE,
+            y2_map: None,
+        }
+    }
+}
+
+impl TileView {
+    /// World offset per data unit, negative along inverted axes
+    pub fn signed_scale(&self) -> Vec2 {
+        Vec2::select(self.invert, -self.scale, self.scale)
+    }
+
+    /// `scale` kept within the zoom limits, whichever way round they are
+    pub fn clamp_scale(&self, scale: Vec2) -> Vec2 {
+        let low = self.min_scale.min(self.max_scale);
+        let high = self.min_scale.max(self.max_scale);
+        scale.max(low).min(high)
+    }
+
+    /// Primary-axis value of a secondary-axis `y`
+    pub fn y2_to_primary(&self, y: f32) -> f32 {
+        let map = self.y2_map.unwrap_or(Vec2::X);
+        map.x * y + map.y
+    }
+
+    /// Secondary-axis value of a primary-axis `y`
+    pub fn primary_to_y2(&self, y: f32) -> f32 {
+        let map = self.y2_map.unwrap_or(Vec2::X);
+        (y - map.y) / map.x
+    }
+
+    /// Map the secondary range `y2_min..=y2_max` onto the primary range
+    /// `y_min..=y_max`
+    pub fn align_y2(&mut self, (y_min, y_max): (f32, f32), (y2_min, y2_max): (f32, f32)) {
+        let a = (y_max - y_min) / (y2_max - y2_min);
+        if a.is_finite() && a != 0.0 {
+            let b = (y_min + y_max) * 0.5 - a * (y2_min + y2_max) * 0.5;
+            self.y2_map = Some(Vec2::new(a, b));
         }
     }
 }
 
+/// A single axis of a 2D tile
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis2D {
+    X,
+    Y,
+}
+
 #[derive(Component)]
 pub struct TileRect {
     pub world_center: Vec2,
@@ -84,6 +138,14 @@ pub struct CrosshairHLine;
 #[derive(Component)]
 pub struct CrosshairVLine;
 
+/// Marker for the rectangle shown while dragging out a box zoom
+#[derive(Component)]
+pub struct ZoomBoxOverlay;
+
+/// Marker for selected-point highlights and the selection being dragged out
+#[derive(Component)]
+pub struct SelectionMarker;
+
 /// Marker for coordinate text display
 #[derive(Component)]
 pub struct CrosshairCoordText;
@@ -102,10 +164,6 @@ pub struct TabButton {
     pub index: usize,
 }
 
-/// Resource to track the previous active tab for change detection
-#[derive(Resource, Default)]
-pub struct PreviousActiveTab(pub usize);
-
 /// 3D orbit camera state for a tile
 #[derive(Component, Clone, Copy, Debug)]
 pub struct View3D {
@@ -0,0 +1,230 @@
+//! Annotations: reference lines, shaded spans, text and arrows over 2D and
+//! candlestick plots.
+
+#![allow(clippy::too_many_arguments)]
+
+use super::common::data_to_world;
+use crate::core::{Annotation, AnnotationShape, Position};
+use crate::render::{TileRect, TileView, UnitMeshes};
+use bevy::prelude::*;
+use bevy_camera::visibility::RenderLayers;
+
+/// Font size of annotation labels
+pub(crate) const ANNOTATION_FONT_SIZE: f32 = 10.0;
+
+/// Width of reference lines and arrows
+pub(crate) const ANNOTATION_LINE_WIDTH: f32 = 1.5;
+
+/// Share of the annotation color's alpha used to fill spans
+const SPAN_ALPHA: f32 = 0.2;
+
+/// Space left under the tile top for the plot title
+const TITLE_MARGIN: f32 = 40.0;
+
+/// One primitive of an annotation, in world coordinates, shared by the
+/// renderer and the exporter
+pub(crate) enum Mark {
+    Fill {
+        center: Vec2,
+        size: Vec2,
+        color: crate::core::Color,
+    },
+    Segment {
+        from: Vec2,
+        to: Vec2,
+        color: crate::core::Color,
+    },
+    Label {
+        text: String,
+        at: Vec2,
+        color: crate::core::Color,
+    },
+}
+
+/// Rough width of a label, for placing it beside a line
+fn label_half_width(text: &str) -> f32 {
+    text.chars().count() as f32 * ANNOTATION_FONT_SIZE * 0.3
+}
+
+fn resolve(pos: Position, rect: &TileRect, view: &TileView) -> Vec2 {
+    match pos {
+        Position::Data { x, y } => data_to_world(Vec2::new(x, y), rect, view),
+        Position::Screen { x, y } => rect.world_center + (Vec2::new(x, y) - 0.5) * rect.world_size,
+    }
+}
+
+/// Lay out annotations in a tile; anything entirely outside it is dropped
+/// and lines and spans are cut to its edges.
+pub(crate) fn annotation_marks(
+    annotations: &[Annotation],
+    rect: &TileRect,
+    view: &TileView,
+) -> Vec<Mark> {
+    let tile = Rect::from_center_size(rect.world_center, rect.world_size);
+    let mut marks = Vec::new();
+
+    for annotation in annotations {
+        let color = annotation.color;
+        let label = annotation.label.as_deref().unwrap_or_default();
+        let push_label = |marks: &mut Vec<Mark>, at: Vec2| {
+            if !label.is_empty() {
+                marks.push(Mark::Label {
+                    text: label.to_string(),
+                    at,
+                    color,
+                });
+            }
+        };
+
+        match annotation.shape {
+            AnnotationShape::HLine { y } => {
+                let y = data_to_world(Vec2::new(0.0, y), rect, view).y;
+                if !(tile.min.y..=tile.max.y).contains(&y) {
+                    continue;
+                }
+                marks.push(Mark::Segment {
+                    from: Vec2::new(tile.min.x, y),
+                    to: Vec2::new(tile.max.x, y),
+                    color,
+                });
+                let x = tile.max.x - 8.0 - label_half_width(label);
+                push_label(&mut marks, Vec2::new(x, y + 9.0));
+            }
+            AnnotationShape::VLine { x } => {
+                let x = data_to_world(Vec2::new(x, 0.0), rect, view).x;
+                if !(tile.min.x..=tile.max.x).contains(&x) {
+                    continue;
+                }
+                marks.push(Mark::Segment {
+                    from: Vec2::new(x, tile.min.y),
+                    to: Vec2::new(x, tile.max.y),
+                    color,
+                });
+                let x = x + 6.0 + label_half_width(label);
+                push_label(&mut marks, Vec2::new(x, tile.max.y - TITLE_MARGIN));
+            }
+            AnnotationShape::XSpan { x0, x1 } => {
+                let a = data_to_world(Vec2::new(x0, 0.0), rect, view).x;
+                let b = data_to_world(Vec2::new(x1, 0.0), rect, view).x;
+                let (lo, hi) = (a.min(b).max(tile.min.x), a.max(b).min(tile.max.x));
+                if lo >= hi {
+                    continue;
+                }
+                marks.push(Mark::Fill {
+                    center: Vec2::new((lo + hi) * 0.5, tile.center().y),
+                    size: Vec2::new(hi - lo, tile.height()),
+                    color: color.with_a(color.a * SPAN_ALPHA),
+                });
+                push_label(
+                    &mut marks,
+                    Vec2::new((lo + hi) * 0.5, tile.max.y - TITLE_MARGIN),
+                );
+            }
+            AnnotationShape::YSpan { y0, y1 } => {
+                let a = data_to_world(Vec2::new(0.0, y0), rect, view).y;
+                let b = data_to_world(Vec2::new(0.0, y1), rect, view).y;
+                let (lo, hi) = (a.min(b).max(tile.min.y), a.max(b).min(tile.max.y));
+                if lo >= hi {
+                    continue;
+                }
+                marks.push(Mark::Fill {
+                    center: Vec2::new(tile.center().x, (lo + hi) * 0.5),
+                    size: Vec2::new(tile.width(), hi - lo),
+                    color: color.with_a(color.a * SPAN_ALPHA),
+                });
+                let x = tile.max.x - 8.0 - label_half_width(label);
+                push_label(&mut marks, Vec2::new(x, (lo + hi) * 0.5));
+            }
+            AnnotationShape::Text { at } => {
+                let at = resolve(at, rect, view);
+                if tile.contains(at) {
+                    push_label(&mut marks, at);
+                }
+            }
+            AnnotationShape::Arrow { from, to } => {
+                let (from, to) = (resolve(from, rect, view), resolve(to, rect, view));
+                if !tile.contains(from) && !tile.contains(to) {
+                    continue;
+                }
+                marks.push(Mark::Segment { from, to, color });
+                let back = (from - to).normalize_or_zero();
+                for angle in [0.45f32, -0.45] {
+                    let head = Vec2::from_angle(angle).rotate(back) * 8.0;
+                    marks.push(Mark::Segment {
+                        from: to,
+                        to: to + head,
+                        color,
+                    });
+                }
+                if tile.contains(from) {
+                    push_label(&mut marks, from + back * 10.0);
+                }
+            }
+        }
+    }
+    marks
+}
+
+/// Draw a plot's annotations above its data.
+pub fn draw_annotations(
+    commands: &mut Commands,
+    root: Entity,
+    annotations: &[Annotation],
+    rect: &TileRect,
+    view: &TileView,
+    unit: &UnitMeshes,
+    materials: &mut Assets<ColorMaterial>,
+    layers: RenderLayers,
+) {
+    if annotations.is_empty() {
+        return;
+    }
+    let marks = annotation_marks(annotations, rect, view);
+
+    commands.entity(root).with_children(|parent| {
+        for mark in marks {
+            match mark {
+                Mark::Fill {
+                    center,
+                    size,
+                    color,
+                } => {
+                    parent.spawn((
+                        Mesh2d(unit.quad.clone()),
+                        MeshMaterial2d(materials.add(ColorMaterial::from(Color::from(color)))),
+                        Transform {
+                            translation: center.extend(-0.1),
+                            scale: size.extend(1.0),
+                            ..default()
+                        },
+                        layers.clone(),
+                    ));
+                }
+                Mark::Segment { from, to, color } => {
+                    parent.spawn((
+                        Mesh2d(unit.quad.clone()),
+                        MeshMaterial2d(materials.add(ColorMaterial::from(Color::from(color)))),
+                        Transform {
+                            translation: ((from + to) * 0.5).extend(0.7),
+                            rotation: Quat::from_rotation_z((to - from).to_angle()),
+                            scale: Vec3::new(from.distance(to), ANNOTATION_LINE_WIDTH, 1.0),
+                        },
+                        layers.clone(),
+                    ));
+                }
+                Mark::Label { text, at, color } => {
+                    parent.spawn((
+                        Text2d::new(text),
+                        TextFont {
+                            font_size: ANNOTATION_FONT_SIZE,
+                            ..default()
+                        },
+                        TextColor(color.into()),
+                        Transform::from_translation(at.extend(2.5)),
+                        layers.clone(),
+                    ));
+                }
+            }
+        }
+    });
+}
@@ -2,7 +2,10 @@
 
 #![allow(clippy::too_many_arguments)]
 
-use super::common::{data_to_world, draw_tile_border};
+use super::common::{
+    data_to_world, draw_grid_and_spines, draw_legend, draw_tile_border, legend_layout,
+    on_primary_axis,
+};
 use crate::render::{TileRect, TileView, UnitMeshes};
 use bevy::prelude::*;
 use bevy_asset::RenderAssetUsages;
@@ -32,35 +35,17 @@ pub fn draw_2d_plot(
         1.0,
     );
 
-    // Draw axis at data origin (0,0) - moves with pan/zoom
-    let axis_mat = materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)));
-    let axis_origin = data_to_world(Vec2::ZERO, rect, view);
-
-    commands.entity(root).with_children(|parent| {
-        // X-axis (horizontal line at y=0)
-        parent.spawn((
-            Mesh2d(unit.quad.clone()),
-            MeshMaterial2d(axis_mat.clone()),
-            Transform {
-                translation: Vec3::new(rect.world_center.x, axis_origin.y, 0.5),
-                scale: Vec3::new(rect.world_size.x, 1.0, 1.0),
-                ..default()
-            },
-            layers.clone(),
-        ));
-
-        // Y-axis (vertical line at x=0)
-        parent.spawn((
-            Mesh2d(unit.quad.clone()),
-            MeshMaterial2d(axis_mat),
-            Transform {
-                translation: Vec3::new(axis_origin.x, rect.world_center.y, 0.5),
-                scale: Vec3::new(1.0, rect.world_size.y, 1.0),
-                ..default()
-            },
-            layers.clone(),
-        ));
-    });
+    // Gridlines and axis lines, beneath the data
+    draw_grid_and_spines(
+        commands,
+        root,
+        rect,
+        view,
+        &graph.axes,
+        unit,
+        materials,
+        layers.clone(),
+    );
 
     // Draw axis labels
     if let Some(ref x_label) = graph.x_label {
@@ -105,7 +90,32 @@ pub fn draw_2d_plot(
         });
     }
 
+    if let Some(ref y2_label) = graph.y2_label {
+        commands.entity(root).with_children(|parent| {
+            parent.spawn((
+                Text2d::new(y2_label.clone()),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                Transform {
+                    translation: Vec3::new(
+                        rect.world_center.x + rect.world_size.x * 0.5 - 12.0,
+                        rect.world_center.y,
+                        2.0,
+                    ),
+                    rotation: Quat::from_rotation_z(-std::f32::consts::FRAC_PI_2),
+                    ..default()
+                },
+                layers.clone(),
+            ));
+        });
+    }
+
     for layer in &graph.layers {
+        let layer = on_primary_axis(layer, view);
+        let layer = &*layer;
         let color = Color::srgba(
             layer.style.color.r,
             layer.style.color.g,
@@ -129,6 +139,15 @@ pub fn draw_2d_plot(
             _ => {}
         }
     }
+
+    let legend = legend_layout(
+        graph
+            .layers
+            .iter()
+            .filter_map(|l| Some((l.label.as_deref()?, l.style.color.with_a(l.style.opacity)))),
+        rect,
+    );
+    draw_legend(commands, root, &legend, unit, materials, layers);
 }
 
 fn draw_line_layer(
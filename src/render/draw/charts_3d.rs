@@ -2,7 +2,7 @@
 
 #![allow(clippy::too_many_arguments)]
 
-use super::common::draw_tile_border;
+use super::common::{draw_legend, draw_tile_border, legend_layout};
 use crate::render::{AxisInfo3D, AxisInfo3DStore, ScatterPoints3D, TileRect, UnitMeshes};
 use bevy::prelude::*;
 use bevy_asset::RenderAssetUsages;
@@ -126,6 +126,22 @@ pub fn draw_3d_plot(
         rect,
         unit,
         color_materials,
+        overlay_layers.clone(),
+    );
+
+    let legend = legend_layout(
+        graph
+            .layers
+            .iter()
+            .filter_map(|l| Some((l.label.as_deref()?, l.style.color.with_a(l.style.opacity)))),
+        rect,
+    );
+    draw_legend(
+        commands,
+        root,
+        &legend,
+        unit,
+        color_materials,
         overlay_layers,
     );
 }
@@ -191,7 +207,7 @@ fn draw_3d_title_and_border(
 }
 
 /// Compute bounding box for 3D data
-fn compute_3d_bounds(layers: &[crate::core::Layer3D]) -> ([f32; 3], [f32; 3]) {
+pub(crate) fn compute_3d_bounds(layers: &[crate::core::Layer3D]) -> ([f32; 3], [f32; 3]) {
     let mut min = [f32::INFINITY; 3];
     let mut max = [f32::NEG_INFINITY; 3];
 
@@ -224,7 +240,7 @@ fn compute_3d_bounds(layers: &[crate::core::Layer3D]) -> ([f32; 3], [f32; 3]) {
 }
 
 /// Normalize a 3D point to fit within a standard viewing volume
-fn normalize_point(pt: Vec3, bounds: &([f32; 3], [f32; 3])) -> Vec3 {
+pub(crate) fn normalize_point(pt: Vec3, bounds: &([f32; 3], [f32; 3])) -> Vec3 {
     let (min, max) = bounds;
     let scale = 5.0; // Size of normalized volume
     Vec3::new(
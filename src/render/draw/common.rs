@@ -1,5 +1,9 @@
 //! Common drawing utilities shared across chart types.
 
+#![allow(clippy::too_many_arguments)]
+
+use crate::core::{AxesStyle, Spines, TickFormat};
+use crate::render::components::Axis2D;
 use crate::render::{TileRect, TileView, UnitMeshes};
 use bevy::prelude::*;
 use bevy_camera::visibility::RenderLayers;
@@ -118,14 +122,151 @@ pub fn draw_placeholder(
     });
 }
 
+/// Font size of legend entries.
+pub(crate) const LEGEND_FONT_SIZE: f32 = 10.0;
+
+/// One legend row, positioned in world space.
+pub(crate) struct LegendEntry<'a> {
+    pub label: &'a str,
+    pub color: crate::core::Color,
+    pub swatch_center: Vec2,
+    pub text_center: Vec2,
+}
+
+/// Lay out a legend in the top-right corner of a tile, below the title.
+/// Text is drawn centered, so entries are left-aligned using a width
+/// estimated from the character count.
+pub(crate) fn legend_layout<'a>(
+    entries: impl IntoIterator<Item = (&'a str, crate::core::Color)>,
+    rect: &TileRect,
+) -> Vec<LegendEntry<'a>> {
+    let entries: Vec<_> = entries.into_iter().collect();
+    let char_width = LEGEND_FONT_SIZE * 0.6;
+    let widest = entries
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0) as f32
+        * char_width;
+
+    let right = rect.world_center.x + rect.world_size.x * 0.5 - 10.0;
+    let text_left = right - widest;
+    let top = rect.world_center.y + rect.world_size.y * 0.5 - 44.0;
+
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(i, (label, color))| {
+            let y = top - i as f32 * 14.0;
+            LegendEntry {
+                label,
+                color,
+                swatch_center: Vec2::new(text_left - 12.0, y),
+                text_center: Vec2::new(
+                    text_left + label.chars().count() as f32 * char_width * 0.5,
+                    y,
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Draw a color swatch and name for each legend entry.
+pub(crate) fn draw_legend(
+    commands: &mut Commands,
+    root: Entity,
+    entries: &[LegendEntry],
+    unit: &UnitMeshes,
+    materials: &mut Assets<ColorMaterial>,
+    layers: RenderLayers,
+) {
+    for entry in entries {
+        let c = entry.color;
+        let mat = materials.add(ColorMaterial::from(Color::srgba(c.r, c.g, c.b, c.a)));
+
+        commands.entity(root).with_children(|parent| {
+            parent.spawn((
+                Mesh2d(unit.quad.clone()),
+                MeshMaterial2d(mat),
+                Transform {
+                    translation: entry.swatch_center.extend(3.0),
+                    scale: Vec3::new(14.0, 4.0, 1.0),
+                    ..default()
+                },
+                layers.clone(),
+            ));
+            parent.spawn((
+                Text2d::new(entry.label),
+                TextFont {
+                    font_size: LEGEND_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(Color::srgba(0.85, 0.85, 0.85, 0.95)),
+                Transform::from_translation(entry.text_center.extend(3.0)),
+                layers.clone(),
+            ));
+        });
+    }
+}
+
+/// Inner area of a tile left after fractional padding on each side, used by
+/// charts with fixed axes (distributions, heatmaps).
+#[derive(Clone, Copy, Debug)]
+pub struct PlotArea {
+    pub left: f32,
+    pub bottom: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl PlotArea {
+    pub fn new(rect: &TileRect, left: f32, right: f32, bottom: f32, top: f32) -> Self {
+        Self {
+            left: rect.world_center.x - rect.world_size.x * 0.5 + rect.world_size.x * left,
+            bottom: rect.world_center.y - rect.world_size.y * 0.5 + rect.world_size.y * bottom,
+            width: rect.world_size.x * (1.0 - left - right),
+            height: rect.world_size.y * (1.0 - bottom - top),
+        }
+    }
+}
+
 /// Convert data coordinates to world coordinates.
 pub fn data_to_world(data: Vec2, rect: &TileRect, view: &TileView) -> Vec2 {
-    rect.world_center + view.offset + data * view.scale
+    rect.world_center + view.offset + data * view.signed_scale()
 }
 
 /// Convert world coordinates to data coordinates.
 pub fn world_to_data(world: Vec2, rect: &TileRect, view: &TileView) -> Vec2 {
-    (world - rect.world_center - view.offset) / view.scale
+    (world - rect.world_center - view.offset) / view.signed_scale()
+}
+
+/// A 2D layer with its points in primary-axis coordinates; layers on the
+/// secondary y axis are copied with their y values mapped.
+pub fn on_primary_axis<'a>(
+    layer: &'a crate::core::Layer2D,
+    view: &TileView,
+) -> std::borrow::Cow<'a, crate::core::Layer2D> {
+    if layer.y_axis == crate::core::YAxis::Primary {
+        return std::borrow::Cow::Borrowed(layer);
+    }
+    let map = |pts: &[Vec2]| -> Vec<Vec2> {
+        pts.iter()
+            .map(|p| Vec2::new(p.x, view.y2_to_primary(p.y)))
+            .collect()
+    };
+    let mut mapped = layer.clone();
+    mapped.xy = map(&layer.xy);
+    mapped.lower_line = layer.lower_line.as_deref().map(map);
+    std::borrow::Cow::Owned(mapped)
+}
+
+/// Smallest and largest data coordinates visible in a tile, whichever way
+/// its axes run.
+pub fn visible_data_range(rect: &TileRect, view: &TileView) -> (Vec2, Vec2) {
+    let half_size = rect.world_size * 0.5;
+    let a = world_to_data(rect.world_center - half_size, rect, view);
+    let b = world_to_data(rect.world_center + half_size, rect, view);
+    (a.min(b), a.max(b))
 }
 
 /// Calculate nice tick step for given range.
@@ -148,30 +289,368 @@ pub fn nice_step(range: f32, target_ticks: usize) -> f32 {
         10.0
     };
 
-    (nice * base).max(0.001)
+    nice * base
 }
 
-/// Format tick value for display.
+/// Format tick value for display: integers as-is, decimals as needed and
+/// scientific notation only for very large or very small values.
 pub fn format_tick(val: f32) -> String {
-    if val.abs() < 0.001 && val != 0.0 {
-        format!("{:.1e}", val)
-    } else if val.abs() >= 1000.0 {
+    let abs = val.abs();
+    if val == 0.0 {
+        "0".to_string()
+    } else if !(1e-3..1e6).contains(&abs) {
         format!("{:.1e}", val)
     } else if val.fract().abs() < 0.001 {
         format!("{:.0}", val)
-    } else if val.abs() < 1.0 {
+    } else if abs < 1.0 {
         format!("{:.2}", val)
     } else {
         format!("{:.1}", val)
     }
 }
 
-/// Draw axis tick marks and value labels.
+/// Most decimals a tick label shows; beyond this `f32` has no digits left and
+/// huge precisions make `format!` panic
+const MAX_DECIMALS: usize = 17;
+
+/// Format a tick value the way an axis asks for.
+pub fn format_tick_with(format: &TickFormat, val: f32) -> String {
+    let cap = |decimals: &usize| (*decimals).min(MAX_DECIMALS);
+    match format {
+        TickFormat::Auto => format_tick(val),
+        TickFormat::Si => format_si(val),
+        TickFormat::Percent { decimals } => format!("{:.*}%", cap(decimals), val * 100.0),
+        TickFormat::Currency { symbol, decimals } => format_currency(val, symbol, cap(decimals)),
+        TickFormat::Fixed { decimals } => format!("{:.*}", cap(decimals), val),
+        TickFormat::Scientific { decimals } => format!("{:.*e}", cap(decimals), val),
+        TickFormat::Pattern(pattern) => format_pattern(pattern, val),
+        TickFormat::Custom { formatter } => match formatter {
+            Some(f) => (f.0)(val),
+            None => format_tick(val),
+        },
+    }
+}
+
+/// `1200` as `1.2k`, `0.005` as `5m`
+fn format_si(val: f32) -> String {
+    const PREFIXES: [&str; 9] = ["p", "n", "µ", "m", "", "k", "M", "G", "T"];
+    if val == 0.0 || !val.is_finite() {
+        return format_tick(val);
+    }
+    let mut group = ((val.abs().log10() / 3.0).floor() as i32).clamp(-4, 4);
+    let digits = loop {
+        let scaled = val / 1000f32.powi(group);
+        let (digits, rounded) = if (scaled - scaled.round()).abs() < 0.05 {
+            (format!("{:.0}", scaled), scaled.round())
+        } else {
+            (format!("{:.1}", scaled), (scaled * 10.0).round() / 10.0)
+        };
+        // 999.96 rounds to "1000k": show it as "1M" instead
+        if rounded.abs() < 1000.0 || group == 4 {
+            break digits;
+        }
+        group += 1;
+    };
+    format!("{digits}{}", PREFIXES[(group + 4) as usize])
+}
+
+/// `-1234.5` as `-$1,234.50`
+fn format_currency(val: f32, symbol: &str, decimals: usize) -> String {
+    let fixed = format!("{:.*}", decimals, val.abs());
+    let (int, frac) = fixed.split_at(fixed.find('.').unwrap_or(fixed.len()));
+    let mut grouped = String::new();
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    let sign = if val < 0.0 && fixed.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+        "-"
+    } else {
+        ""
+    };
+    format!("{sign}{symbol}{grouped}{frac}")
+}
+
+/// Replace the first `{}`, `{:.N}` or `{:.Ne}` in `pattern` with `val`
+fn format_pattern(pattern: &str, val: f32) -> String {
+    let Some(open) = pattern.find('{') else {
+        return pattern.to_string();
+    };
+    let Some(len) = pattern[open..].find('}') else {
+        return pattern.to_string();
+    };
+    let spec = &pattern[open + 1..open + len];
+    let decimals = spec
+        .strip_prefix(":.")
+        .map(|d| d.trim_end_matches('e'))
+        .and_then(|d| d.parse::<usize>().ok())
+        .map(|d| d.min(MAX_DECIMALS));
+    let value = match (spec.ends_with('e'), decimals) {
+        (true, Some(d)) => format!("{:.*e}", d, val),
+        (true, None) => format!("{:e}", val),
+        (false, Some(d)) => format!("{:.*}", d, val),
+        (false, None) => format_tick(val),
+    };
+    format!(
+        "{}{}{}",
+        &pattern[..open],
+        value,
+        &pattern[open + len + 1..]
+    )
+}
+
+/// Where a 2D plot's ticks and gridlines go, shared by the renderer and the
+/// exporter
+pub(crate) struct AxisLayout {
+    /// Where the axes cross: x ticks sit at its y and y ticks at its x
+    pub anchor: Vec2,
+    /// Plot area the gridlines and edge spines span
+    pub frame: Rect,
+    /// World x and label of each x tick
+    pub x_ticks: Vec<(f32, String)>,
+    /// World y and label of each y tick
+    pub y_ticks: Vec<(f32, String)>,
+    /// World y and label of each secondary y tick
+    pub y2_ticks: Vec<(f32, String)>,
+    /// World x of the secondary y ticks and of their labels
+    pub y2_x: (f32, f32),
+    pub minor_x: Vec<f32>,
+    pub minor_y: Vec<f32>,
+}
+
+/// Plot area of a 2D tile and the point its axes cross.
+///
+/// With `Spines::Origin` the axes run through the data origin, clamped into
+/// the tile; every other style frames an inset plot area and anchors the
+/// axes at its bottom-left corner.
+pub(crate) fn axis_frame(rect: &TileRect, view: &TileView, spines: Spines) -> (Vec2, Rect) {
+    let min = rect.world_center - rect.world_size * 0.5;
+    let max = rect.world_center + rect.world_size * 0.5;
+    if spines == Spines::Origin {
+        let origin = data_to_world(Vec2::ZERO, rect, view);
+        let anchor = Vec2::new(
+            origin.x.clamp(min.x + 30.0, max.x - 30.0),
+            origin.y.clamp(min.y + 20.0, max.y - 20.0),
+        );
+        return (anchor, Rect::from_corners(min, max));
+    }
+    let right = if view.y2_map.is_some() { 50.0 } else { 12.0 };
+    let frame = Rect::from_corners(
+        min + Vec2::new(50.0, 40.0),
+        (max - Vec2::new(right, 30.0)).max(min + Vec2::new(51.0, 41.0)),
+    );
+    (frame.min, frame)
+}
+
+/// Tick positions, labels and minor gridlines of a 2D tile
+pub(crate) fn axis_layout(rect: &TileRect, view: &TileView, axes: &AxesStyle) -> AxisLayout {
+    let (anchor, frame) = axis_frame(rect, view, axes.spines);
+    let (data_min, data_max) = visible_data_range(rect, view);
+    // Origin-style axes label the whole tile, less a margin; framed axes
+    // only their plot area
+    let bounds = if axes.spines == Spines::Origin {
+        Rect::from_corners(frame.min + 10.0, frame.max - 10.0)
+    } else {
+        frame
+    };
+
+    let x_step = nice_step(data_max.x - data_min.x, tick_count(axes.x_ticks.count, 8));
+    let y_step = nice_step(data_max.y - data_min.y, tick_count(axes.y_ticks.count, 6));
+    let x_world = |x: f32| data_to_world(Vec2::new(x, 0.0), rect, view).x;
+    let y_world = |y: f32| data_to_world(Vec2::new(0.0, y), rect, view).y;
+    let in_x = |x: &f32| (bounds.min.x..=bounds.max.x).contains(x);
+    let in_y = |y: &f32| (bounds.min.y..=bounds.max.y).contains(y);
+
+    let x_ticks = steps(data_min.x, data_max.x, x_step)
+        .map(|x| (x_world(x), format_tick_with(&axes.x_ticks.format, x)))
+        .filter(|(x, _)| in_x(x))
+        .collect();
+    let y_ticks = steps(data_min.y, data_max.y, y_step)
+        .map(|y| (y_world(y), format_tick_with(&axes.y_ticks.format, y)))
+        .filter(|(y, _)| in_y(y))
+        .collect();
+
+    let y2_ticks = if view.y2_map.is_some() {
+        let (a, b) = (
+            view.primary_to_y2(data_min.y),
+            view.primary_to_y2(data_max.y),
+        );
+        let (y2_min, y2_max) = (a.min(b), a.max(b));
+        let step = nice_step(y2_max - y2_min, tick_count(axes.y2_ticks.count, 6));
+        steps(y2_min, y2_max, step)
+            .map(|y2| {
+                (
+                    y_world(view.y2_to_primary(y2)),
+                    format_tick_with(&axes.y2_ticks.format, y2),
+                )
+            })
+            .filter(|(y, _)| in_y(y))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let y2_x = if axes.spines == Spines::Origin {
+        let right = rect.world_center.x + rect.world_size.x * 0.5;
+        (right - 3.0, right - 36.0)
+    } else {
+        (frame.max.x, frame.max.x + 24.0)
+    };
+
+    let (minor_x, minor_y) = if axes.minor_grid {
+        let minor = |min: f32, max: f32, step: f32| {
+            let sub = minor_step(step);
+            steps(min, max, sub).filter(move |v| ((v / step).round() * step - v).abs() > sub * 0.25)
+        };
+        (
+            minor(data_min.x, data_max.x, x_step)
+                .map(x_world)
+                .filter(in_x)
+                .collect(),
+            minor(data_min.y, data_max.y, y_step)
+                .map(y_world)
+                .filter(in_y)
+                .collect(),
+        )
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    AxisLayout {
+        anchor,
+        frame,
+        x_ticks,
+        y_ticks,
+        y2_ticks,
+        y2_x,
+        minor_x,
+        minor_y,
+    }
+}
+
+/// Most ticks an axis asks for; the hint comes from user JSON
+const MAX_TICKS: usize = 50;
+
+/// Tick count to aim for: the axis's hint, kept within what fits on screen
+fn tick_count(hint: Option<usize>, default: usize) -> usize {
+    hint.unwrap_or(default).clamp(2, MAX_TICKS)
+}
+
+/// Multiples of `step` covering `min..=max`, at most a few hundred of them
+fn steps(min: f32, max: f32, step: f32) -> impl Iterator<Item = f32> {
+    let start = (min / step).floor() as i32;
+    let end = ((max / step).ceil() as i32).min(start.saturating_add(MAX_TICKS as i32 * 10));
+    (start..=end).map(move |i| i as f32 * step)
+}
+
+/// Spacing of minor gridlines: quarters of a 2-step, fifths otherwise
+fn minor_step(step: f32) -> f32 {
+    let mantissa = step / 10f32.powf(step.log10().floor());
+    if (mantissa - 2.0).abs() < 0.01 {
+        step / 4.0
+    } else {
+        step / 5.0
+    }
+}
+
+/// Gridlines as `(center, size, minor)` quads
+pub(crate) fn grid_lines(layout: &AxisLayout, axes: &AxesStyle) -> Vec<(Vec2, Vec2, bool)> {
+    let frame = layout.frame;
+    let vertical = |x: f32, minor| {
+        (
+            Vec2::new(x, frame.center().y),
+            Vec2::new(1.0, frame.height()),
+            minor,
+        )
+    };
+    let horizontal = |y: f32, minor| {
+        (
+            Vec2::new(frame.center().x, y),
+            Vec2::new(frame.width(), 1.0),
+            minor,
+        )
+    };
+    let mut lines = Vec::new();
+    if axes.major_grid {
+        lines.extend(layout.x_ticks.iter().map(|(x, _)| vertical(*x, false)));
+        lines.extend(layout.y_ticks.iter().map(|(y, _)| horizontal(*y, false)));
+    }
+    lines.extend(layout.minor_x.iter().map(|x| vertical(*x, true)));
+    lines.extend(layout.minor_y.iter().map(|y| horizontal(*y, true)));
+    lines
+}
+
+/// Axis lines as `(center, size)` quads
+pub(crate) fn spine_lines(
+    layout: &AxisLayout,
+    rect: &TileRect,
+    view: &TileView,
+    spines: Spines,
+) -> Vec<(Vec2, Vec2)> {
+    let frame = layout.frame;
+    let left = (
+        Vec2::new(frame.min.x, frame.center().y),
+        Vec2::new(1.0, frame.height()),
+    );
+    let bottom = (
+        Vec2::new(frame.center().x, frame.min.y),
+        Vec2::new(frame.width(), 1.0),
+    );
+    match spines {
+        Spines::Origin => {
+            let origin = data_to_world(Vec2::ZERO, rect, view);
+            vec![
+                (
+                    Vec2::new(rect.world_center.x, origin.y),
+                    Vec2::new(rect.world_size.x, 1.0),
+                ),
+                (
+                    Vec2::new(origin.x, rect.world_center.y),
+                    Vec2::new(1.0, rect.world_size.y),
+                ),
+            ]
+        }
+        Spines::Box => vec![
+            left,
+            bottom,
+            (Vec2::new(frame.max.x, frame.center().y), left.1),
+            (Vec2::new(frame.center().x, frame.max.y), bottom.1),
+        ],
+        Spines::LeftBottom => vec![left, bottom],
+        Spines::Hidden => Vec::new(),
+    }
+}
+
+/// The axis whose ticks, labels or title strip lie under `world`, if any.
+///
+/// Near the origin, where both axes meet, no single axis is picked.
+pub(crate) fn axis_at(
+    world: Vec2,
+    rect: &TileRect,
+    view: &TileView,
+    spines: Spines,
+) -> Option<Axis2D> {
+    let (anchor, _) = axis_frame(rect, view, spines);
+    let min = rect.world_center - rect.world_size * 0.5;
+
+    let on_x = (world.y - anchor.y).abs() <= 20.0 || world.y - min.y <= 24.0;
+    let on_y = (world.x - anchor.x).abs() <= 36.0 || world.x - min.x <= 24.0;
+    match (on_x, on_y) {
+        (true, false) => Some(Axis2D::X),
+        (false, true) => Some(Axis2D::Y),
+        _ => None,
+    }
+}
+
+/// Draw axis tick marks and value labels, plus a column on the right edge
+/// for a secondary y axis.
 pub fn draw_axis_ticks(
     commands: &mut Commands,
     root: Entity,
     rect: &TileRect,
     view: &TileView,
+    axes: &AxesStyle,
     unit: &UnitMeshes,
     materials: &mut Assets<ColorMaterial>,
     layers: RenderLayers,
@@ -181,112 +660,134 @@ pub fn draw_axis_ticks(
     let tick_length = 6.0;
     let tick_width = 1.0;
 
-    // Calculate visible data range
-    let half_size = rect.world_size * 0.5;
-    let data_min = world_to_data(rect.world_center - half_size, rect, view);
-    let data_max = world_to_data(rect.world_center + half_size, rect, view);
-
-    // Compute nice tick spacing
-    let x_step = nice_step(data_max.x - data_min.x, 8);
-    let y_step = nice_step(data_max.y - data_min.y, 6);
-
-    // Get axis origin in world coords (clamped to visible area)
-    let origin_data = Vec2::ZERO;
-    let origin_world = data_to_world(origin_data, rect, view);
-    let clamped_origin_y = origin_world.y.clamp(
-        rect.world_center.y - half_size.y + 20.0,
-        rect.world_center.y + half_size.y - 20.0,
-    );
-    let clamped_origin_x = origin_world.x.clamp(
-        rect.world_center.x - half_size.x + 30.0,
-        rect.world_center.x + half_size.x - 30.0,
-    );
-
-    // Draw X-axis ticks and labels
-    let start_x = (data_min.x / x_step).floor() as i32;
-    let end_x = (data_max.x / x_step).ceil() as i32;
-
-    for i in start_x..=end_x {
-        let x_data = i as f32 * x_step;
-        let x_world = data_to_world(Vec2::new(x_data, 0.0), rect, view).x;
-
-        // Skip if outside visible area
-        if x_world < rect.world_center.x - half_size.x + 10.0
-            || x_world > rect.world_center.x + half_size.x - 10.0
-        {
-            continue;
-        }
-
-        let layers_tick = layers.clone();
+    let layout = axis_layout(rect, view, axes);
+    let label_color = TextColor(Color::srgba(0.7, 0.7, 0.7, 0.9));
+    let font = TextFont {
+        font_size: 10.0,
+        ..default()
+    };
 
-        commands.entity(root).with_children(|parent| {
-            // Tick mark
+    commands.entity(root).with_children(|parent| {
+        let mut tick = |center: Vec2, size: Vec2, label: &str, label_at: Vec2| {
             parent.spawn((
                 Mesh2d(unit.quad.clone()),
                 MeshMaterial2d(tick_mat.clone()),
                 Transform {
-                    translation: Vec3::new(x_world, clamped_origin_y, 0.6),
-                    scale: Vec3::new(tick_width, tick_length, 1.0),
+                    translation: center.extend(0.6),
+                    scale: size.extend(1.0),
                     ..default()
                 },
-                layers_tick.clone(),
+                layers.clone(),
             ));
-
-            // Tick label
             parent.spawn((
-                Text2d::new(format_tick(x_data)),
-                TextFont {
-                    font_size: 10.0,
-                    ..default()
-                },
-                TextColor(Color::srgba(0.7, 0.7, 0.7, 0.9)),
-                Transform::from_translation(Vec3::new(x_world, clamped_origin_y - 14.0, 2.0)),
-                layers_tick,
+                Text2d::new(label),
+                font.clone(),
+                label_color,
+                Transform::from_translation(label_at.extend(2.0)),
+                layers.clone(),
             ));
-        });
-    }
-
-    // Draw Y-axis ticks and labels
-    let start_y = (data_min.y / y_step).floor() as i32;
-    let end_y = (data_max.y / y_step).ceil() as i32;
-
-    for i in start_y..=end_y {
-        let y_data = i as f32 * y_step;
-        let y_world = data_to_world(Vec2::new(0.0, y_data), rect, view).y;
-
-        // Skip if outside visible area
-        if y_world < rect.world_center.y - half_size.y + 10.0
-            || y_world > rect.world_center.y + half_size.y - 10.0
-        {
-            continue;
+        };
+
+        for (x, label) in &layout.x_ticks {
+            tick(
+                Vec2::new(*x, layout.anchor.y),
+                Vec2::new(tick_width, tick_length),
+                label,
+                Vec2::new(*x, layout.anchor.y - 14.0),
+            );
         }
+        for (y, label) in &layout.y_ticks {
+            tick(
+                Vec2::new(layout.anchor.x, *y),
+                Vec2::new(tick_length, tick_width),
+                label,
+                Vec2::new(layout.anchor.x - 20.0, *y),
+            );
+        }
+        // Secondary y axis: ticks along the right, in its own units
+        let (tick_x, label_x) = layout.y2_x;
+        for (y, label) in &layout.y2_ticks {
+            tick(
+                Vec2::new(tick_x, *y),
+                Vec2::new(tick_length, tick_width),
+                label,
+                Vec2::new(label_x, *y),
+            );
+        }
+    });
+}
 
-        let layers_tick = layers.clone();
+/// Draw gridlines and axis lines of a 2D tile beneath its data.
+pub fn draw_grid_and_spines(
+    commands: &mut Commands,
+    root: Entity,
+    rect: &TileRect,
+    view: &TileView,
+    axes: &AxesStyle,
+    unit: &UnitMeshes,
+    materials: &mut Assets<ColorMaterial>,
+    layers: RenderLayers,
+) {
+    let layout = axis_layout(rect, view, axes);
+    let major_mat = materials.add(ColorMaterial::from(Color::srgba(0.5, 0.5, 0.55, 0.18)));
+    let minor_mat = materials.add(ColorMaterial::from(Color::srgba(0.5, 0.5, 0.55, 0.08)));
+    let axis_mat = materials.add(ColorMaterial::from(Color::srgb(0.5, 0.5, 0.5)));
 
-        commands.entity(root).with_children(|parent| {
-            // Tick mark
+    let grid = grid_lines(&layout, axes);
+    let spines = spine_lines(&layout, rect, view, axes.spines);
+    commands.entity(root).with_children(|parent| {
+        for (center, size, minor) in grid {
+            let mat = if minor { &minor_mat } else { &major_mat };
             parent.spawn((
                 Mesh2d(unit.quad.clone()),
-                MeshMaterial2d(tick_mat.clone()),
+                MeshMaterial2d(mat.clone()),
                 Transform {
-                    translation: Vec3::new(clamped_origin_x, y_world, 0.6),
-                    scale: Vec3::new(tick_length, tick_width, 1.0),
+                    translation: center.extend(-0.2),
+                    scale: size.extend(1.0),
                     ..default()
                 },
-                layers_tick.clone(),
+                layers.clone(),
             ));
-
-            // Tick label
+        }
+        for (center, size) in spines {
             parent.spawn((
-                Text2d::new(format_tick(y_data)),
-                TextFont {
-                    font_size: 10.0,
+                Mesh2d(unit.quad.clone()),
+                MeshMaterial2d(axis_mat.clone()),
+                Transform {
+                    translation: center.extend(0.5),
+                    scale: size.extend(1.0),
                     ..default()
                 },
-                TextColor(Color::srgba(0.7, 0.7, 0.7, 0.9)),
-                Transform::from_translation(Vec3::new(clamped_origin_x - 20.0, y_world, 2.0)),
-                layers_tick,
+                layers.clone(),
             ));
-        });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_hints_are_bounded() {
+        assert_eq!(tick_count(Some(usize::MAX), 8), MAX_TICKS);
+        assert_eq!(tick_count(Some(0), 8), 2);
+        assert_eq!(tick_count(None, 8), 8);
+        let step = nice_step(1.0, tick_count(Some(usize::MAX), 8));
+        assert!(steps(0.0, 1.0, step).count() <= MAX_TICKS * 2);
+        assert!(steps(-1e30, 1e30, 1e-30).count() <= MAX_TICKS * 10 + 1);
+    }
+
+    #[test]
+    fn tick_labels() {
+        assert_eq!(format_si(999_960.0), "1M");
+        assert_eq!(format_si(1_500.0), "1.5k");
+        assert_eq!(format_si(0.005), "5m");
+        let fixed = TickFormat::Fixed { decimals: 70_000 };
+        assert_eq!(format_tick_with(&fixed, 0.5).len(), 2 + MAX_DECIMALS);
+        assert_eq!(
+            format_pattern("{:.99999}s", 1.0),
+            format!("1.{}s", "0".repeat(17))
+        );
     }
 }
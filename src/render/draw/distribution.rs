@@ -2,7 +2,7 @@
 
 #![allow(clippy::too_many_arguments)]
 
-use super::common::draw_tile_border;
+use super::common::{PlotArea, draw_tile_border};
 use crate::render::{TileRect, TileView, UnitMeshes};
 use bevy::prelude::*;
 use bevy_asset::RenderAssetUsages;
@@ -24,25 +24,14 @@ pub fn draw_histogram(
     materials: &mut Assets<ColorMaterial>,
     layers: RenderLayers,
 ) {
-    if values.is_empty() || bins == 0 {
-        return;
-    }
-
-    let min_val = values.iter().cloned().fold(f32::INFINITY, f32::min);
-    let max_val = values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
-
-    if !min_val.is_finite() || !max_val.is_finite() || min_val >= max_val {
+    let Some(HistogramBins {
+        min: min_val,
+        bin_width,
+        counts,
+    }) = histogram_bins(values, bins)
+    else {
         return;
-    }
-
-    let bin_width = (max_val - min_val) / bins as f32;
-    let mut counts = vec![0usize; bins];
-
-    for &v in values {
-        let idx = ((v - min_val) / bin_width).floor() as usize;
-        let idx = idx.min(bins - 1);
-        counts[idx] += 1;
-    }
+    };
 
     let max_count = counts.iter().cloned().max().unwrap_or(1) as f32;
 
@@ -57,17 +46,9 @@ pub fn draw_histogram(
         1.0,
     );
 
-    let padding_left = 0.15;
-    let padding_right = 0.08;
-    let padding_bottom = 0.18;
-    let padding_top = 0.08;
-
-    let usable_width = rect.world_size.x * (1.0 - padding_left - padding_right);
-    let usable_height = rect.world_size.y * (1.0 - padding_bottom - padding_top);
-
-    let left_x = rect.world_center.x - rect.world_size.x * 0.5 + rect.world_size.x * padding_left;
-    let bottom_y =
-        rect.world_center.y - rect.world_size.y * 0.5 + rect.world_size.y * padding_bottom;
+    let area = distribution_area(rect);
+    let (left_x, bottom_y) = (area.left, area.bottom);
+    let (usable_width, usable_height) = (area.width, area.height);
 
     let bar_width_world = usable_width / bins as f32;
     let gap = bar_width_world * 0.1;
@@ -175,53 +156,15 @@ pub fn draw_pdf(
     materials: &mut Assets<ColorMaterial>,
     layers: RenderLayers,
 ) {
-    if values.is_empty() {
+    let Some(KdeCurve {
+        x_min,
+        x_max,
+        points: kde_points,
+        max_density,
+    }) = kde_curve(values)
+    else {
         return;
-    }
-
-    let min_val = values.iter().cloned().fold(f32::INFINITY, f32::min);
-    let max_val = values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
-
-    if !min_val.is_finite() || !max_val.is_finite() || min_val >= max_val {
-        return;
-    }
-
-    // Silverman's rule of thumb for bandwidth
-    let n = values.len() as f32;
-    let std_dev = {
-        let mean = values.iter().sum::<f32>() / n;
-        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / n;
-        variance.sqrt()
     };
-    let bandwidth = (1.06 * std_dev * n.powf(-0.2)).max(0.01);
-
-    // Sample the KDE
-    let n_samples = 200;
-    let range = max_val - min_val;
-    let x_min = min_val - range * 0.1;
-    let x_max = max_val + range * 0.1;
-
-    let mut kde_points: Vec<(f32, f32)> = Vec::with_capacity(n_samples);
-    let mut max_density = 0.0f32;
-
-    for i in 0..n_samples {
-        let x = x_min + (i as f32 / (n_samples - 1) as f32) * (x_max - x_min);
-        let density: f32 = values
-            .iter()
-            .map(|&xi| {
-                let u = (x - xi) / bandwidth;
-                (-0.5 * u * u).exp() / (2.506628 * bandwidth)
-            })
-            .sum::<f32>()
-            / n;
-
-        kde_points.push((x, density));
-        max_density = max_density.max(density);
-    }
-
-    if max_density <= 0.0 {
-        return;
-    }
 
     draw_tile_border(
         commands,
@@ -234,16 +177,9 @@ pub fn draw_pdf(
         1.0,
     );
 
-    let padding_left = 0.15;
-    let padding_right = 0.08;
-    let padding_bottom = 0.18;
-    let padding_top = 0.08;
-
-    let usable_width = rect.world_size.x * (1.0 - padding_left - padding_right);
-    let usable_height = rect.world_size.y * (1.0 - padding_bottom - padding_top);
-    let left_x = rect.world_center.x - rect.world_size.x * 0.5 + rect.world_size.x * padding_left;
-    let bottom_y =
-        rect.world_center.y - rect.world_size.y * 0.5 + rect.world_size.y * padding_bottom;
+    let area = distribution_area(rect);
+    let (left_x, bottom_y) = (area.left, area.bottom);
+    let (usable_width, usable_height) = (area.width, area.height);
 
     let upper: Vec<Vec2> = kde_points
         .iter()
@@ -403,17 +339,168 @@ pub fn draw_pdf(
     });
 }
 
+/// Padded plot area of histogram, PDF and ECDF charts.
+pub(crate) fn distribution_area(rect: &TileRect) -> PlotArea {
+    PlotArea::new(rect, 0.15, 0.08, 0.18, 0.08)
+}
+
+/// Padded plot area of boxplots.
+pub(crate) fn boxplot_area(rect: &TileRect) -> PlotArea {
+    PlotArea::new(rect, 0.12, 0.05, 0.18, 0.08)
+}
+
+/// Histogram bin counts over the value range.
+pub(crate) struct HistogramBins {
+    pub min: f32,
+    pub bin_width: f32,
+    pub counts: Vec<usize>,
+}
+
+pub(crate) fn histogram_bins(values: &[f32], bins: usize) -> Option<HistogramBins> {
+    if values.is_empty() || bins == 0 {
+        return None;
+    }
+
+    let min_val = values.iter().cloned().fold(f32::INFINITY, f32::min);
+    let max_val = values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+
+    if !min_val.is_finite() || !max_val.is_finite() || min_val >= max_val {
+        return None;
+    }
+
+    let bin_width = (max_val - min_val) / bins as f32;
+    let mut counts = vec![0usize; bins];
+
+    for &v in values {
+        let idx = ((v - min_val) / bin_width).floor() as usize;
+        let idx = idx.min(bins - 1);
+        counts[idx] += 1;
+    }
+
+    Some(HistogramBins {
+        min: min_val,
+        bin_width,
+        counts,
+    })
+}
+
+/// Sampled kernel density estimate.
+pub(crate) struct KdeCurve {
+    pub x_min: f32,
+    pub x_max: f32,
+    /// (x, density) samples
+    pub points: Vec<(f32, f32)>,
+    pub max_density: f32,
+}
+
+pub(crate) fn kde_curve(values: &[f32]) -> Option<KdeCurve> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let min_val = values.iter().cloned().fold(f32::INFINITY, f32::min);
+    let max_val = values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+
+    if !min_val.is_finite() || !max_val.is_finite() || min_val >= max_val {
+        return None;
+    }
+
+    // Silverman's rule of thumb for bandwidth
+    let n = values.len() as f32;
+    let std_dev = {
+        let mean = values.iter().sum::<f32>() / n;
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / n;
+        variance.sqrt()
+    };
+    let bandwidth = (1.06 * std_dev * n.powf(-0.2)).max(0.01);
+
+    // Sample the KDE
+    let n_samples = 200;
+    let range = max_val - min_val;
+    let x_min = min_val - range * 0.1;
+    let x_max = max_val + range * 0.1;
+
+    let mut kde_points: Vec<(f32, f32)> = Vec::with_capacity(n_samples);
+    let mut max_density = 0.0f32;
+
+    for i in 0..n_samples {
+        let x = x_min + (i as f32 / (n_samples - 1) as f32) * (x_max - x_min);
+        let density: f32 = values
+            .iter()
+            .map(|&xi| {
+                let u = (x - xi) / bandwidth;
+                (-0.5 * u * u).exp() / (2.506628 * bandwidth)
+            })
+            .sum::<f32>()
+            / n;
+
+        kde_points.push((x, density));
+        max_density = max_density.max(density);
+    }
+
+    if max_density <= 0.0 {
+        return None;
+    }
+
+    Some(KdeCurve {
+        x_min,
+        x_max,
+        points: kde_points,
+        max_density,
+    })
+}
+
+/// Finite values sorted ascending, or `None` if they span no range.
+pub(crate) fn ecdf_sorted(values: &[f32]) -> Option<Vec<f32>> {
+    let mut sorted: Vec<f32> = values.iter().cloned().filter(|x| x.is_finite()).collect();
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    if sorted[0] >= sorted[sorted.len() - 1] {
+        return None;
+    }
+    Some(sorted)
+}
+
+/// ECDF step function through `sorted` values, in world coordinates.
+pub(crate) fn ecdf_steps(sorted: &[f32], area: &PlotArea) -> Vec<Vec2> {
+    let n = sorted.len();
+    let min_val = sorted[0];
+    let max_val = sorted[n - 1];
+
+    // Build step function points
+    let mut step_points: Vec<Vec2> = Vec::with_capacity(n * 2 + 2);
+
+    // Start at (min_val, 0)
+    step_points.push(Vec2::new(area.left, area.bottom));
+
+    for (i, &val) in sorted.iter().enumerate() {
+        let x = area.left + ((val - min_val) / (max_val - min_val)) * area.width;
+        let prev_y = (i as f32 / n as f32) * area.height + area.bottom;
+        let next_y = ((i + 1) as f32 / n as f32) * area.height + area.bottom;
+
+        // Horizontal to current x at previous y
+        step_points.push(Vec2::new(x, prev_y));
+        // Vertical step up
+        step_points.push(Vec2::new(x, next_y));
+    }
+
+    step_points
+}
+
 /// Box plot statistics.
-struct BoxStats {
-    min: f32,
-    q1: f32,
-    median: f32,
-    q3: f32,
-    max: f32,
-    outliers: Vec<f32>,
+pub(crate) struct BoxStats {
+    pub min: f32,
+    pub q1: f32,
+    pub median: f32,
+    pub q3: f32,
+    pub max: f32,
+    pub outliers: Vec<f32>,
 }
 
-fn compute_box_stats(values: &[f32]) -> Option<BoxStats> {
+pub(crate) fn compute_box_stats(values: &[f32]) -> Option<BoxStats> {
     if values.is_empty() {
         return None;
     }
@@ -469,6 +556,27 @@ fn compute_box_stats(values: &[f32]) -> Option<BoxStats> {
     })
 }
 
+/// Value range covering every group's whiskers and outliers, padded by 10%.
+pub(crate) fn boxplot_range(stats: &[Option<BoxStats>]) -> Option<(f32, f32)> {
+    let mut y_min = f32::INFINITY;
+    let mut y_max = f32::NEG_INFINITY;
+    for s in stats.iter().flatten() {
+        y_min = y_min.min(s.min);
+        y_max = y_max.max(s.max);
+        for &o in &s.outliers {
+            y_min = y_min.min(o);
+            y_max = y_max.max(o);
+        }
+    }
+
+    if !y_min.is_finite() || !y_max.is_finite() {
+        return None;
+    }
+
+    let y_range = y_max - y_min;
+    Some((y_min - y_range * 0.1, y_max + y_range * 0.1))
+}
+
 /// Draw a boxplot for grouped data.
 pub fn draw_boxplot(
     commands: &mut Commands,
@@ -488,26 +596,9 @@ pub fn draw_boxplot(
     }
 
     let stats: Vec<Option<BoxStats>> = groups.iter().map(|(_, v)| compute_box_stats(v)).collect();
-
-    // Find global y range
-    let mut y_min = f32::INFINITY;
-    let mut y_max = f32::NEG_INFINITY;
-    for s in stats.iter().flatten() {
-        y_min = y_min.min(s.min);
-        y_max = y_max.max(s.max);
-        for &o in &s.outliers {
-            y_min = y_min.min(o);
-            y_max = y_max.max(o);
-        }
-    }
-
-    if !y_min.is_finite() || !y_max.is_finite() {
+    let Some((y_min, y_max)) = boxplot_range(&stats) else {
         return;
-    }
-
-    let y_range = y_max - y_min;
-    let y_min = y_min - y_range * 0.1;
-    let y_max = y_max + y_range * 0.1;
+    };
 
     draw_tile_border(
         commands,
@@ -520,16 +611,9 @@ pub fn draw_boxplot(
         1.0,
     );
 
-    let padding_left = 0.12;
-    let padding_right = 0.05;
-    let padding_bottom = 0.18;
-    let padding_top = 0.08;
-
-    let usable_width = rect.world_size.x * (1.0 - padding_left - padding_right);
-    let usable_height = rect.world_size.y * (1.0 - padding_bottom - padding_top);
-    let left_x = rect.world_center.x - rect.world_size.x * 0.5 + rect.world_size.x * padding_left;
-    let bottom_y =
-        rect.world_center.y - rect.world_size.y * 0.5 + rect.world_size.y * padding_bottom;
+    let area = boxplot_area(rect);
+    let (left_x, bottom_y) = (area.left, area.bottom);
+    let (usable_width, usable_height) = (area.width, area.height);
 
     let to_world_y = |y: f32| -> f32 { bottom_y + ((y - y_min) / (y_max - y_min)) * usable_height };
 
@@ -738,23 +822,12 @@ pub fn draw_ecdf(
     materials: &mut Assets<ColorMaterial>,
     layers: RenderLayers,
 ) {
-    if values.is_empty() {
-        return;
-    }
-
-    let mut sorted: Vec<f32> = values.iter().cloned().filter(|x| x.is_finite()).collect();
-    if sorted.is_empty() {
+    let Some(sorted) = ecdf_sorted(values) else {
         return;
-    }
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
-
+    };
     let min_val = sorted[0];
     let max_val = sorted[sorted.len() - 1];
 
-    if min_val >= max_val {
-        return;
-    }
-
     draw_tile_border(
         commands,
         root,
@@ -766,36 +839,11 @@ pub fn draw_ecdf(
         1.0,
     );
 
-    let padding_left = 0.15;
-    let padding_right = 0.08;
-    let padding_bottom = 0.18;
-    let padding_top = 0.08;
+    let area = distribution_area(rect);
+    let (left_x, bottom_y) = (area.left, area.bottom);
+    let (usable_width, usable_height) = (area.width, area.height);
 
-    let usable_width = rect.world_size.x * (1.0 - padding_left - padding_right);
-    let usable_height = rect.world_size.y * (1.0 - padding_bottom - padding_top);
-    let left_x = rect.world_center.x - rect.world_size.x * 0.5 + rect.world_size.x * padding_left;
-    let bottom_y =
-        rect.world_center.y - rect.world_size.y * 0.5 + rect.world_size.y * padding_bottom;
-
-    let n = sorted.len();
-
-    // Build step function points
-    let mut step_points: Vec<Vec2> = Vec::with_capacity(n * 2 + 2);
-
-    // Start at (min_val, 0)
-    let start_x = left_x;
-    step_points.push(Vec2::new(start_x, bottom_y));
-
-    for (i, &val) in sorted.iter().enumerate() {
-        let x = left_x + ((val - min_val) / (max_val - min_val)) * usable_width;
-        let prev_y = (i as f32 / n as f32) * usable_height + bottom_y;
-        let next_y = ((i + 1) as f32 / n as f32) * usable_height + bottom_y;
-
-        // Horizontal to current x at previous y
-        step_points.push(Vec2::new(x, prev_y));
-        // Vertical step up
-        step_points.push(Vec2::new(x, next_y));
-    }
+    let step_points = ecdf_steps(&sorted, &area);
 
     // Draw filled area under step function
     if step_points.len() >= 2 {
@@ -2,7 +2,7 @@
 
 #![allow(clippy::too_many_arguments)]
 
-use super::common::{draw_tile_border, format_tick, nice_step, world_to_data};
+use super::common::{data_to_world, draw_tile_border, format_tick, nice_step, visible_data_range};
 use crate::render::{TileRect, TileView, UnitMeshes};
 use bevy::prelude::*;
 use bevy_camera::visibility::RenderLayers;
@@ -34,25 +34,10 @@ pub fn draw_candlestick(
     );
 
     // Transform data to world coordinates using view
-    let data_to_world_candle =
-        |data: Vec2| -> Vec2 { rect.world_center + view.offset + data * view.scale };
+    let data_to_world_candle = |data: Vec2| -> Vec2 { data_to_world(data, rect, view) };
 
-    // Calculate candle width
-    let n_candles = candle.candles.len();
-    let x_min = candle
-        .candles
-        .iter()
-        .map(|c| c.x)
-        .fold(f32::INFINITY, f32::min);
-    let x_max = candle
-        .candles
-        .iter()
-        .map(|c| c.x)
-        .fold(f32::NEG_INFINITY, f32::max);
-    let x_range = (x_max - x_min).max(1.0);
-
-    let candle_data_width = x_range / (n_candles as f32 * 1.5);
-    let candle_world_width = candle_data_width * view.scale;
+    let candle_data_width = candle_width(&candle.candles);
+    let candle_world_width = candle_data_width * view.scale.x;
     let wick_world_width = candle_world_width * 0.15;
 
     // Prepare materials
@@ -73,8 +58,7 @@ pub fn draw_candlestick(
 
     // Compute visible data range for culling
     let half_size = rect.world_size * 0.5;
-    let visible_min = world_to_data(rect.world_center - half_size, rect, view);
-    let visible_max = world_to_data(rect.world_center + half_size, rect, view);
+    let (visible_min, visible_max) = visible_data_range(rect, view);
     let candle_half_width = candle_data_width * 0.5;
 
     // Draw candles
@@ -259,3 +243,14 @@ pub fn draw_candlestick(
         ));
     });
 }
+
+/// Width of a candle body in data units, leaving gaps between neighbours.
+pub(crate) fn candle_width(candles: &[crate::core::OHLC]) -> f32 {
+    let x_min = candles.iter().map(|c| c.x).fold(f32::INFINITY, f32::min);
+    let x_max = candles
+        .iter()
+        .map(|c| c.x)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let x_range = (x_max - x_min).max(1.0);
+    x_range / (candles.len() as f32 * 1.5)
+}
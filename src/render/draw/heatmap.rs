@@ -2,7 +2,7 @@
 
 #![allow(clippy::too_many_arguments)]
 
-use super::common::draw_tile_border;
+use super::common::{PlotArea, draw_tile_border};
 use crate::render::{TileRect, TileView, UnitMeshes};
 use bevy::prelude::*;
 use bevy_camera::visibility::RenderLayers;
@@ -25,18 +25,7 @@ pub fn draw_heatmap(
         return;
     }
 
-    // Compute value range
-    let vmin = heatmap
-        .vmin
-        .unwrap_or_else(|| heatmap.values.iter().cloned().fold(f32::INFINITY, f32::min));
-    let vmax = heatmap.vmax.unwrap_or_else(|| {
-        heatmap
-            .values
-            .iter()
-            .cloned()
-            .fold(f32::NEG_INFINITY, f32::max)
-    });
-    let vrange = (vmax - vmin).max(0.001);
+    let (vmin, vrange) = heatmap_range(heatmap);
 
     draw_tile_border(
         commands,
@@ -49,20 +38,9 @@ pub fn draw_heatmap(
         1.0,
     );
 
-    // Padding for labels
-    let has_row_labels = heatmap.row_labels.is_some();
-    let has_col_labels = heatmap.col_labels.is_some();
-
-    let padding_left = if has_row_labels { 0.12 } else { 0.06 };
-    let padding_right = 0.06;
-    let padding_bottom = if has_col_labels { 0.12 } else { 0.06 };
-    let padding_top = 0.06;
-
-    let usable_width = rect.world_size.x * (1.0 - padding_left - padding_right);
-    let usable_height = rect.world_size.y * (1.0 - padding_bottom - padding_top);
-    let left_x = rect.world_center.x - rect.world_size.x * 0.5 + rect.world_size.x * padding_left;
-    let bottom_y =
-        rect.world_center.y - rect.world_size.y * 0.5 + rect.world_size.y * padding_bottom;
+    let area = heatmap_area(rect, heatmap);
+    let (left_x, bottom_y) = (area.left, area.bottom);
+    let (usable_width, usable_height) = (area.width, area.height);
 
     let cell_width = usable_width / cols as f32;
     let cell_height = usable_height / rows as f32;
@@ -165,3 +143,33 @@ pub fn draw_heatmap(
         }
     }
 }
+
+/// Lower bound and extent of the colormap's value range.
+pub(crate) fn heatmap_range(heatmap: &crate::core::Heatmap) -> (f32, f32) {
+    let vmin = heatmap
+        .vmin
+        .unwrap_or_else(|| heatmap.values.iter().cloned().fold(f32::INFINITY, f32::min));
+    let vmax = heatmap.vmax.unwrap_or_else(|| {
+        heatmap
+            .values
+            .iter()
+            .cloned()
+            .fold(f32::NEG_INFINITY, f32::max)
+    });
+    (vmin, (vmax - vmin).max(0.001))
+}
+
+/// Cell area of a heatmap, leaving room for row and column labels.
+pub(crate) fn heatmap_area(rect: &TileRect, heatmap: &crate::core::Heatmap) -> PlotArea {
+    let padding_left = if heatmap.row_labels.is_some() {
+        0.12
+    } else {
+        0.06
+    };
+    let padding_bottom = if heatmap.col_labels.is_some() {
+        0.12
+    } else {
+        0.06
+    };
+    PlotArea::new(rect, padding_left, 0.06, padding_bottom, 0.06)
+}
@@ -2,6 +2,7 @@
 //!
 //! This module is organized into focused submodules:
 //! - `common`: Shared utilities (borders, transforms, axis ticks)
+//! - `annotations`: Reference lines, spans, text and arrows
 //! - `charts_2d`: 2D scatter/line/fill plots
 //! - `charts_3d`: 3D scatter and surface plots
 //! - `distribution`: Histogram, PDF, boxplot, ECDF
@@ -9,6 +10,7 @@
 //! - `heatmap`: Grid-based color visualizations
 //! - `radial`: Pie charts and radar/spider charts
 
+mod annotations;
 mod charts_2d;
 mod charts_3d;
 mod common;
@@ -18,13 +20,31 @@ mod heatmap;
 mod radial;
 
 // Re-export public drawing functions
+pub use annotations::draw_annotations;
 pub use charts_2d::draw_2d_plot;
 pub use charts_3d::draw_3d_plot;
 pub use common::{
-    data_to_world, draw_axis_ticks, draw_placeholder, draw_plot_title, draw_tile_border,
-    format_tick, nice_step, world_to_data,
+    data_to_world, draw_axis_ticks, draw_grid_and_spines, draw_placeholder, draw_plot_title,
+    draw_tile_border, format_tick, format_tick_with, nice_step, on_primary_axis,
+    visible_data_range, world_to_data,
 };
 pub use distribution::{draw_boxplot, draw_ecdf, draw_histogram, draw_pdf};
 pub use financial::draw_candlestick;
 pub use heatmap::draw_heatmap;
 pub use radial::draw_radial;
+
+// Scene layout of 3D tiles, for picking points
+pub(crate) use charts_3d::{compute_3d_bounds, normalize_point};
+
+// Layout and statistics shared with the vector exporter
+pub(crate) use annotations::{ANNOTATION_FONT_SIZE, ANNOTATION_LINE_WIDTH, Mark, annotation_marks};
+pub(crate) use common::{
+    LEGEND_FONT_SIZE, axis_at, axis_layout, grid_lines, legend_layout, spine_lines,
+};
+pub(crate) use distribution::{
+    BoxStats, HistogramBins, KdeCurve, boxplot_area, boxplot_range, compute_box_stats,
+    distribution_area, ecdf_sorted, ecdf_steps, histogram_bins, kde_curve,
+};
+pub(crate) use financial::candle_width;
+pub(crate) use heatmap::{heatmap_area, heatmap_range};
+pub(crate) use radial::{pie_color, radial_radius};
@@ -70,18 +70,7 @@ fn draw_pie(
     }
 
     let center = rect.world_center;
-    let radius = (rect.world_size.x.min(rect.world_size.y) * 0.35).max(10.0);
-
-    let colors = [
-        Color::srgba(0.3, 0.6, 0.9, 0.9),
-        Color::srgba(0.9, 0.4, 0.3, 0.9),
-        Color::srgba(0.4, 0.8, 0.4, 0.9),
-        Color::srgba(0.9, 0.7, 0.2, 0.9),
-        Color::srgba(0.7, 0.4, 0.9, 0.9),
-        Color::srgba(0.3, 0.8, 0.8, 0.9),
-        Color::srgba(0.9, 0.5, 0.7, 0.9),
-        Color::srgba(0.6, 0.6, 0.6, 0.9),
-    ];
+    let radius = radial_radius(rect);
 
     let mut start_angle = -std::f32::consts::FRAC_PI_2;
     let segments_per_slice = 32;
@@ -92,7 +81,7 @@ fn draw_pie(
         }
 
         let sweep = (*value / total) * std::f32::consts::TAU;
-        let color = colors[i % colors.len()];
+        let color = pie_color(i);
 
         let mut positions: Vec<[f32; 3]> = Vec::new();
         let mut indices: Vec<u32> = Vec::new();
@@ -196,7 +185,7 @@ fn draw_radar(
     );
 
     let center = rect.world_center;
-    let radius = (rect.world_size.x.min(rect.world_size.y) * 0.35).max(10.0);
+    let radius = radial_radius(rect);
     let angle_step = std::f32::consts::TAU / n as f32;
 
     // Draw grid circles
@@ -371,3 +360,14 @@ fn draw_radar(
         });
     }
 }
+
+/// Outer radius of pie and radar charts.
+pub(crate) fn radial_radius(rect: &TileRect) -> f32 {
+    (rect.world_size.x.min(rect.world_size.y) * 0.35).max(10.0)
+}
+
+/// Fill color of the `i`th pie slice.
+pub(crate) fn pie_color(i: usize) -> Color {
+    let c = crate::core::Color::palette(i);
+    Color::srgba(c.r, c.g, c.b, 0.9)
+}
@@ -19,23 +19,33 @@ impl Plugin for DashRenderPlugin {
         app.init_resource::<TileRegistry>()
             .init_resource::<HoveredTile>()
             .init_resource::<CursorWorldPos>()
-            .init_resource::<PreviousActiveTab>()
+            .init_resource::<ZoomBox>()
+            .init_resource::<LastClick>()
             .init_resource::<ScatterPoints3D>()
+            .init_resource::<HoverIndex>()
+            .init_resource::<Selections>()
+            .init_resource::<SelectGesture>()
+            .add_message::<SelectionChanged>()
             .init_resource::<AxisInfo3DStore>()
             .add_systems(Startup, (setup_global_scene, setup_unit_meshes))
             .add_systems(
                 Update,
                 (
+                    apply_live_updates,
                     handle_tab_clicks,
-                    detect_tab_change,
                     sync_plots_to_tiles,
                     update_tile_layout,
                     auto_fit_tiles,
                     sync_tile_cameras,
                     update_hovered_tile,
+                    handle_selection,
                     handle_input,
+                    sync_linked_views,
+                    update_zoom_box_overlay,
                     draw_dirty_tiles,
                     update_crosshair,
+                    update_selection_overlay,
+                    notify_selection_listeners,
                     update_3d_axis_labels,
                     update_tab_bar,
                 )
@@ -1,17 +1,128 @@
-use super::components::PlotId;
+use super::components::{PlotId, TileView, View3D};
 use bevy::prelude::*;
+use bevy_camera::RenderTarget;
 use bevy_camera::visibility::RenderLayers;
-use std::collections::{HashMap, VecDeque};
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::sync::mpsc::Receiver;
 
 #[derive(Resource, Clone)]
 pub struct DashboardRes(pub crate::core::Dashboard);
 
 impl DashboardRes {
-    pub fn new(dashboard: crate::core::Dashboard) -> Self {
+    pub fn new(mut dashboard: crate::core::Dashboard) -> Self {
+        dashboard.ensure_unique_ids();
         Self(dashboard)
     }
 }
 
+/// Channel of live updates feeding a running dashboard (see `LocalDashboardHandle` and `JsDashboard`)
+#[derive(Resource)]
+pub struct LiveUpdates {
+    pub receiver: Mutex<Receiver<crate::core::UpdateCommand>>,
+    /// Callbacks told about selections, registered through the handle
+    pub on_select: SelectionListeners,
+}
+
+impl LiveUpdates {
+    pub fn new(receiver: Receiver<crate::core::UpdateCommand>) -> Self {
+        Self {
+            receiver: Mutex::new(receiver),
+            on_select: SelectionListeners::default(),
+        }
+    }
+}
+
+/// Callback run on the render thread for each selection
+pub type SelectionListener = Arc<Mutex<dyn FnMut(&crate::core::Selection) + Send>>;
+
+/// Selection callbacks shared between a running app and its handle
+#[derive(Clone, Default)]
+pub struct SelectionListeners(Arc<Mutex<ListenerList>>);
+
+#[derive(Default)]
+struct ListenerList {
+    next_id: u64,
+    listeners: Vec<(u64, SelectionListener)>,
+}
+
+impl SelectionListeners {
+    /// Register a callback, returning the ID that [`Self::remove`] takes
+    pub fn add(&self, callback: impl FnMut(&crate::core::Selection) + Send + 'static) -> u64 {
+        let mut list = self.0.lock();
+        let id = list.next_id;
+        list.next_id += 1;
+        list.listeners.push((id, Arc::new(Mutex::new(callback))));
+        id
+    }
+
+    /// Unregister a callback; returns whether it was registered
+    pub fn remove(&self, id: u64) -> bool {
+        let mut list = self.0.lock();
+        let before = list.listeners.len();
+        list.listeners.retain(|(listener, _)| *listener != id);
+        list.listeners.len() != before
+    }
+
+    /// Call every listener with `selection`
+    ///
+    /// The list is copied and unlocked first, so callbacks may add or remove
+    /// listeners; one removed during the pass is not called afterwards.
+    pub fn notify(&self, selection: &crate::core::Selection) {
+        let listeners = self.0.lock().listeners.clone();
+        for (id, listener) in listeners {
+            let registered = self.0.lock().listeners.iter().any(|(l, _)| *l == id);
+            if registered {
+                (listener.lock())(selection);
+            }
+        }
+    }
+}
+
+/// Sent when points are selected on a plot, its selection is cleared, or a
+/// data update drops selected points that no longer exist. Plots that mirror the selection through a link group are not reported.
+#[derive(Message, Clone, Debug)]
+pub struct SelectionChanged(pub crate::core::Selection);
+
+/// Points selected on each plot, including selections mirrored from linked
+/// plots
+#[derive(Resource, Default)]
+pub struct Selections {
+    pub by_plot: HashMap<PlotId, Vec<crate::core::SelectedPoints>>,
+}
+
+/// Selection gesture in progress: a left press that may turn out to be a
+/// click, or a box or lasso being dragged out (world coordinates)
+#[derive(Resource, Default)]
+pub struct SelectGesture {
+    pub press: Option<(usize, Vec2)>,
+    pub drag: Option<SelectDrag>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SelectDrag {
+    pub tile_index: usize,
+    pub lasso: bool,
+    /// Cursor positions so far; a box spans the first and last
+    pub path: Vec<Vec2>,
+}
+
+/// Image that all cameras render into instead of the primary window (headless export)
+#[derive(Resource, Clone)]
+pub struct OffscreenTarget {
+    pub image: Handle<Image>,
+    /// Size of the image in physical pixels
+    pub size: UVec2,
+}
+
+/// Render target for cameras: the offscreen image when exporting, else the primary window
+pub fn camera_target(offscreen: Option<&OffscreenTarget>) -> RenderTarget {
+    offscreen
+        .map(|target| RenderTarget::from(target.image.clone()))
+        .unwrap_or_default()
+}
+
 #[derive(Resource, Default)]
 pub struct TileRegistry {
     pub by_plot: HashMap<PlotId, Entity>,
@@ -19,6 +130,8 @@ pub struct TileRegistry {
     /// Overlay 2D cameras for 3D tiles (used for titles/borders)
     pub overlay_of: HashMap<PlotId, Entity>,
     pub dirty: VecDeque<PlotId>,
+    /// View state of plots that left the active view, restored when they return
+    pub saved_views: HashMap<PlotId, (TileView, Option<View3D>)>,
 }
 
 #[derive(Resource, Default)]
@@ -34,6 +147,24 @@ pub struct CursorWorldPos {
     pub tile_index: Option<usize>,
 }
 
+/// Rectangle being dragged out on a 2D tile to zoom into (world coordinates)
+#[derive(Resource, Default)]
+pub struct ZoomBox(pub Option<ZoomBoxDrag>);
+
+#[derive(Clone, Copy, Debug)]
+pub struct ZoomBoxDrag {
+    pub tile_index: usize,
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+/// Last left click, for detecting double-clicks
+#[derive(Resource, Default)]
+pub struct LastClick {
+    /// Time (seconds since startup) and world position of the click
+    pub at: Option<(f64, Vec2)>,
+}
+
 #[derive(Resource)]
 pub struct UnitMeshes {
     pub quad: Handle<Mesh>,
@@ -41,7 +172,7 @@ pub struct UnitMeshes {
 }
 
 // TODO: why below not in mod
-pub fn setup_global_scene(mut commands: Commands) {
+pub fn setup_global_scene(mut commands: Commands, offscreen: Option<Res<OffscreenTarget>>) {
     commands.insert_resource(AmbientLight {
         brightness: 300.0,
         ..default()
@@ -52,6 +183,7 @@ pub fn setup_global_scene(mut commands: Commands) {
         Camera2d,
         Camera {
             order: 100, // Render after tile cameras (which use order 10+)
+            target: camera_target(offscreen.as_deref()),
             ..default()
         },
         RenderLayers::layer(0),
@@ -86,3 +218,135 @@ pub struct AxisInfo3D {
 pub struct AxisInfo3DStore {
     pub info: HashMap<usize, AxisInfo3D>,
 }
+
+/// Layers of 2D plots sorted by x, so hovering stays fast on large layers.
+/// Built on first hover; live updates drop only the plots and layers they
+/// touch.
+#[derive(Resource, Default)]
+pub struct HoverIndex {
+    layers: HashMap<PlotId, Vec<SortedLayer>>,
+    /// Cached layers whose points changed since they were sorted
+    stale: HashSet<(PlotId, usize)>,
+}
+
+impl HoverIndex {
+    /// Sorted layers of `graph`, aligned with `graph.layers`; fills between
+    /// lines are left empty so hovering skips them
+    pub fn layers_of(&mut self, graph: &crate::core::Graph2D) -> &[SortedLayer] {
+        let sorted = |layer: &crate::core::Layer2D| match layer.geometry {
+            crate::core::Geometry2D::FillBetween => SortedLayer::default(),
+            _ => SortedLayer::new(&layer.xy),
+        };
+        let Self { layers, stale } = self;
+        let cached = layers.entry(graph.id).or_default();
+        if cached.len() != graph.layers.len() {
+            *cached = graph.layers.iter().map(sorted).collect();
+            stale.retain(|(id, _)| *id != graph.id);
+        } else {
+            stale.retain(|&(id, index)| {
+                if id != graph.id {
+                    return true;
+                }
+                cached[index] = sorted(&graph.layers[index]);
+                false
+            });
+        }
+        cached
+    }
+
+    /// Re-sort one layer of a plot on its next hover
+    pub fn invalidate_layer(&mut self, id: PlotId, layer: usize) {
+        if self
+            .layers
+            .get(&id)
+            .is_some_and(|cached| layer < cached.len())
+        {
+            self.stale.insert((id, layer));
+        }
+    }
+
+    /// Rebuild every layer of a plot on its next hover
+    pub fn invalidate_plot(&mut self, id: PlotId) {
+        self.layers.remove(&id);
+        self.stale.retain(|(plot, _)| *plot != id);
+    }
+
+    pub fn clear(&mut self) {
+        self.layers.clear();
+        self.stale.clear();
+    }
+}
+
+/// Points of one layer in increasing x, without NaNs
+#[derive(Clone, Debug, Default)]
+pub struct SortedLayer {
+    pub points: Vec<Vec2>,
+}
+
+impl SortedLayer {
+    pub fn new(points: &[Vec2]) -> Self {
+        let mut points: Vec<Vec2> = points.iter().copied().filter(|p| !p.is_nan()).collect();
+        points.sort_by(|a, b| a.x.total_cmp(&b.x));
+        Self { points }
+    }
+
+    /// Point nearest `target` once both are scaled by `weight` (1 for data
+    /// units, the view scale for screen distance), with each point's y
+    /// passed through `map_y` first. Returns the unmapped point and its
+    /// squared distance.
+    ///
+    /// Walks outwards from `target.x` in both directions and stops once the
+    /// x gap alone exceeds the best distance found.
+    pub fn nearest(
+        &self,
+        target: Vec2,
+        weight: Vec2,
+        map_y: impl Fn(f32) -> f32,
+    ) -> Option<(Vec2, f32)> {
+        let dist_sq = |p: Vec2| ((Vec2::new(p.x, map_y(p.y)) - target) * weight).length_squared();
+        let split = self.points.partition_point(|p| p.x < target.x);
+        let mut best: Option<(Vec2, f32)> = None;
+        let (mut left, mut right) = (split, split);
+        loop {
+            let gap = |p: &Vec2| ((p.x - target.x) * weight.x).powi(2);
+            let bound = best.map_or(f32::INFINITY, |(_, d)| d);
+            let next_left = left
+                .checked_sub(1)
+                .filter(|&i| gap(&self.points[i]) < bound);
+            let next_right =
+                Some(right).filter(|&i| i < self.points.len() && gap(&self.points[i]) < bound);
+            if next_left.is_none() && next_right.is_none() {
+                return best;
+            }
+            for i in next_left.into_iter().chain(next_right) {
+                let p = self.points[i];
+                let d = dist_sq(p);
+                if best.is_none_or(|(_, best_d)| d < best_d) {
+                    best = Some((p, d));
+                }
+            }
+            if let Some(i) = next_left {
+                left = i;
+            }
+            if let Some(i) = next_right {
+                right = i + 1;
+            }
+        }
+    }
+
+    /// The layer's value at `x`: interpolated between the points either side
+    /// when `interpolate` is set and `x` lies within the layer, otherwise the
+    /// point with the nearest x
+    pub fn at_x(&self, x: f32, interpolate: bool) -> Option<Vec2> {
+        let split = self.points.partition_point(|p| p.x < x);
+        let after = self.points.get(split).copied();
+        let before = split.checked_sub(1).map(|i| self.points[i]);
+        match (before, after) {
+            (Some(a), Some(b)) if interpolate && b.x > a.x => {
+                Some(Vec2::new(x, a.y.lerp(b.y, (x - a.x) / (b.x - a.x))))
+            }
+            (Some(a), Some(b)) => Some(if x - a.x <= b.x - x { a } else { b }),
+            (a, b) => a.or(b),
+        }
+    }
+}
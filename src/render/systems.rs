@@ -1,14 +1,16 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::collapsible_if)]
+#![allow(clippy::type_complexity)]
 
 use super::*;
 use crate::render::PlotId;
+use bevy::ecs::system::SystemParam;
 use bevy::input::mouse::{MouseMotion, MouseWheel};
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use bevy_camera::visibility::RenderLayers;
 use bevy_camera::{
-    OrthographicProjection, PerspectiveProjection, Projection, ScalingMode, Viewport,
+    OrthographicProjection, PerspectiveProjection, Projection, RenderTarget, ScalingMode, Viewport,
 };
 use bevy_math::UVec2;
 use std::collections::HashSet;
@@ -16,48 +18,193 @@ use std::collections::HashSet;
 // #[derive(Resource, Clone, Debug)]
 // pub struct DashboardRes(pub crate::core::Dashboard);
 
+/// What the dashboard is drawn into: the primary window, or an offscreen image
+/// when rendering headlessly
+#[derive(SystemParam)]
+pub struct RenderSurface<'w, 's> {
+    windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    offscreen: Option<Res<'w, OffscreenTarget>>,
+}
+
+impl RenderSurface<'_, '_> {
+    /// Logical width, height and scale factor of the surface
+    fn size(&self) -> Option<(f32, f32, f32)> {
+        if let Some(target) = &self.offscreen {
+            return Some((target.size.x as f32, target.size.y as f32, 1.0));
+        }
+        let window = self.windows.single().ok()?;
+        Some((
+            window.width(),
+            window.height(),
+            window.resolution.scale_factor(),
+        ))
+    }
+
+    fn target(&self) -> RenderTarget {
+        camera_target(self.offscreen.as_deref())
+    }
+}
+
 /// Core system: Sync dashboard plots to tile entities
+///
+/// Tiles are keyed by the plot's stable ID, so reordering, tab switches and
+/// live updates keep each plot's view state.
 pub fn sync_plots_to_tiles(
     mut commands: Commands,
     dash: Res<DashboardRes>,
     mut registry: ResMut<TileRegistry>,
-    existing: Query<(Entity, &PlotTile)>,
+    mut existing: Query<(
+        Entity,
+        &mut PlotTile,
+        &TileView,
+        Option<&View3D>,
+        Has<AutoFitted>,
+    )>,
 ) {
     let active = dash.0.active_plots();
 
-    // Generate plot IDs from active plots (respects tabs)
-    let plot_ids: Vec<PlotId> = active
-        .iter()
-        .enumerate()
-        .map(|(i, _)| PlotId(i as u64))
-        .collect();
+    for (entity, mut tile, view, view3d, fitted) in existing.iter_mut() {
+        let Some(index) = active.iter().position(|p| p.id() == tile.id) else {
+            // Plot left the active view (tab switch or removal): remember its view
+            if fitted {
+                registry
+                    .saved_views
+                    .insert(tile.id, (*view, view3d.copied()));
+            }
+            cleanup_tile(&mut commands, &mut registry, entity, tile.id);
+            continue;
+        };
 
-    // Remove tiles for plots that no longer exist
-    for (entity, tile) in existing.iter() {
-        if !plot_ids.contains(&tile.id) {
+        // A replaced plot may need a different tile kind (e.g. 2D -> 3D)
+        if plot_kind(&active[index]) != tile.kind {
             cleanup_tile(&mut commands, &mut registry, entity, tile.id);
+            continue;
+        }
+
+        // Plot moved within the grid
+        if tile.index != index {
+            tile.index = index;
+            registry.dirty.push_back(tile.id);
         }
     }
 
     // Create missing tiles
     for (i, plot) in active.iter().enumerate() {
-        let id = PlotId(i as u64);
+        let id = plot.id();
 
         if let std::collections::hash_map::Entry::Vacant(e) = registry.by_plot.entry(id) {
             let tile = spawn_tile(&mut commands, id, i, plot);
             e.insert(tile);
             registry.dirty.push_back(id);
+
+            if let Some((view, view3d)) = registry.saved_views.remove(&id) {
+                commands.entity(tile).insert((view, AutoFitted));
+                if let (Some(view3d), PlotKind::ThreeD) = (view3d, plot_kind(plot)) {
+                    commands.entity(tile).insert(view3d);
+                }
+            }
         }
     }
 }
 
-fn spawn_tile(
-    commands: &mut Commands,
-    id: PlotId,
-    index: usize,
-    plot: &crate::core::Plot,
-) -> Entity {
-    let kind = match plot {
+/// Apply updates received from a `LocalDashboardHandle` or `JsDashboard` and
+/// mark affected tiles dirty
+pub fn apply_live_updates(
+    mut commands: Commands,
+    live: Option<Res<LiveUpdates>>,
+    mut dash: ResMut<DashboardRes>,
+    mut registry: ResMut<TileRegistry>,
+    existing: Query<&PlotTile>,
+    mut selections: ResMut<Selections>,
+    mut selection_changed: MessageWriter<SelectionChanged>,
+    mut hover_index: ResMut<HoverIndex>,
+) {
+    let Some(live) = live else {
+        return;
+    };
+
+    let receiver = live.receiver.lock();
+    while let Ok(cmd) = receiver.try_recv() {
+        let target = cmd.plot_id();
+        // Layers whose points were swapped out, so their indices now name
+        // other points
+        let (replaced_layer, replaced_all) = match &cmd {
+            crate::core::UpdateCommand::ReplaceTrace2D { layer_idx, .. }
+            | crate::core::UpdateCommand::ReplaceTrace3D { layer_idx, .. } => {
+                (Some(*layer_idx), false)
+            }
+            crate::core::UpdateCommand::UpdatePlot { .. } => (None, true),
+            _ => (None, false),
+        };
+        let touched_layer = match &cmd {
+            crate::core::UpdateCommand::AppendPoints2D {
+                plot_id, layer_idx, ..
+            }
+            | crate::core::UpdateCommand::ReplaceTrace2D {
+                plot_id, layer_idx, ..
+            } => Some((PlotId(*plot_id), *layer_idx)),
+            _ => None,
+        };
+        if let Err(e) = dash.0.apply_update(cmd) {
+            warn!("Ignoring live update: {}", e);
+            continue;
+        }
+
+        // Only the hover index of changed 2D points goes stale
+        match (target, touched_layer) {
+            (_, Some((id, layer))) => hover_index.invalidate_layer(id, layer),
+            (Some(plot_id), None) if replaced_all => hover_index.invalidate_plot(PlotId(plot_id)),
+            (None, None) => hover_index.clear(),
+            _ => {}
+        }
+
+        let selected: Vec<PlotId> = match target {
+            Some(plot_id) => vec![PlotId(plot_id)],
+            None => selections.by_plot.keys().copied().collect(),
+        };
+        for id in selected {
+            let Some(layers) = selections.by_plot.get(&id) else {
+                continue;
+            };
+            let kept = kept_selection(dash.0.plot(id.0), layers, |layer| {
+                replaced_all || replaced_layer == Some(layer)
+            });
+            if kept == *layers {
+                continue;
+            }
+            if kept.is_empty() {
+                selections.by_plot.remove(&id);
+            } else {
+                selections.by_plot.insert(id, kept.clone());
+            }
+            selection_changed.write(SelectionChanged(crate::core::Selection {
+                plot_id: id.0,
+                layers: kept,
+            }));
+        }
+
+        match target {
+            Some(plot_id) => {
+                let id = PlotId(plot_id);
+                registry.dirty.push_back(id);
+
+                // Plots that follow their data are fitted again
+                let refit = dash.0.active_plots().iter().any(|plot| {
+                    matches!(plot, crate::core::Plot::Graph2D(graph)
+                        if graph.id == id && graph.refit_on_update)
+                });
+                if let Some(&tile) = registry.by_plot.get(&id).filter(|_| refit) {
+                    commands.entity(tile).try_remove::<AutoFitted>();
+                }
+            }
+            // Tiles of plots that survive the refresh keep their view
+            None => registry.dirty.extend(existing.iter().map(|tile| tile.id)),
+        }
+    }
+}
+
+fn plot_kind(plot: &crate::core::Plot) -> PlotKind {
+    match plot {
         crate::core::Plot::Graph2D(_) => PlotKind::TwoD,
         crate::core::Plot::Graph3D(_) => PlotKind::ThreeD,
         crate::core::Plot::Distribution(_) => PlotKind::TwoD,
@@ -65,7 +212,16 @@ fn spawn_tile(
         crate::core::Plot::Heatmap(_) => PlotKind::TwoD,
         crate::core::Plot::Radial(_) => PlotKind::TwoD,
         crate::core::Plot::Field(_) => PlotKind::TwoD,
-    };
+    }
+}
+
+fn spawn_tile(
+    commands: &mut Commands,
+    id: PlotId,
+    index: usize,
+    plot: &crate::core::Plot,
+) -> Entity {
+    let kind = plot_kind(plot);
 
     let tile = commands
         .spawn((
@@ -103,77 +259,123 @@ fn spawn_tile(
 
 /// Update tile layout when window resizes
 pub fn update_tile_layout(
-    windows: Query<&Window, With<PrimaryWindow>>,
+    surface: RenderSurface,
     mut registry: ResMut<TileRegistry>,
     mut tiles: Query<(&PlotTile, &mut TileRect)>,
     dash: Res<DashboardRes>,
 ) {
-    let Ok(window) = windows.single() else {
+    let Some((width, height, scale)) = surface.size() else {
         return;
     };
 
-    let plots = dash.0.active_plots();
-    let n = plots.len();
+    let rects = tile_rects(&dash.0, width, height, scale);
+
+    for (tile, mut rect) in tiles.iter_mut() {
+        let Some(new) = rects.get(tile.index) else {
+            continue;
+        };
+
+        // Only mark dirty if layout actually changed
+        let changed = rect.world_center != new.world_center
+            || rect.world_size != new.world_size
+            || rect.viewport.physical_position != new.viewport.physical_position
+            || rect.viewport.physical_size != new.viewport.physical_size;
+
+        if changed {
+            rect.world_center = new.world_center;
+            rect.world_size = new.world_size;
+            rect.content = new.content;
+            rect.viewport = new.viewport.clone();
+
+            registry.dirty.push_back(tile.id);
+        }
+    }
+}
+
+/// Grid layout of the active plots on a `width` x `height` surface (logical pixels)
+///
+/// Shared by the Bevy renderer and the vector exporters so both place tiles identically.
+pub(crate) fn tile_rects(
+    dash: &crate::core::Dashboard,
+    width: f32,
+    height: f32,
+    scale: f32,
+) -> Vec<TileRect> {
+    let n = dash.active_plots().len();
     if n == 0 {
-        return;
+        return Vec::new();
     }
 
-    let (cols, rows) = grid_dims(n, window.width() / window.height(), dash.0.active_columns());
+    let layout = dash.active_layout();
+    let (cols, rows, cells) = grid_cells(
+        dash.active_plots(),
+        layout,
+        width / height,
+        dash.active_columns(),
+    );
 
     let margin = 20.0;
     let gap = 10.0;
     // Reserve space for tab bar at top if using tabs
-    let tab_bar_height = if dash.0.has_tabs() { 40.0 } else { 0.0 };
-
-    let avail_w = window.width() - 2.0 * margin;
-    let avail_h = window.height() - 2.0 * margin - tab_bar_height;
-
-    let tile_w = (avail_w - (cols - 1) as f32 * gap) / cols as f32;
-    let tile_h = (avail_h - (rows - 1) as f32 * gap) / rows as f32;
-
-    for (tile, mut rect) in tiles.iter_mut() {
-        let col = tile.index % cols;
-        let row = tile.index / cols;
-
-        // Viewport in physical pixels (CRITICAL FIX)
-        let vp_x = margin + col as f32 * (tile_w + gap);
-        let vp_y = margin + tab_bar_height + row as f32 * (tile_h + gap);
-
-        let scale = window.resolution.scale_factor();
-        let phys_pos = UVec2::new((vp_x * scale).round() as u32, (vp_y * scale).round() as u32);
-        let phys_size = UVec2::new(
-            (tile_w * scale).round() as u32,
-            (tile_h * scale).round() as u32,
-        );
+    let tab_bar_height = if dash.has_tabs() { 40.0 } else { 0.0 };
 
-        // World coordinates (centered origin)
-        let world_center = Vec2::new(
-            vp_x + tile_w * 0.5 - window.width() * 0.5,
-            window.height() * 0.5 - vp_y - tile_h * 0.5,
-        );
-
-        let new_size = Vec2::new(tile_w, tile_h);
+    let avail_w = width - 2.0 * margin;
+    let avail_h = height - 2.0 * margin - tab_bar_height;
 
-        // Only mark dirty if layout actually changed
-        let changed = rect.world_center != world_center
-            || rect.world_size != new_size
-            || rect.viewport.physical_position != phys_pos
-            || rect.viewport.physical_size != phys_size;
+    let col_tracks = grid_tracks(
+        layout.map_or(&[][..], |l| &l.column_widths),
+        cols,
+        avail_w,
+        gap,
+    );
+    let row_tracks = grid_tracks(
+        layout.map_or(&[][..], |l| &l.row_heights),
+        rows,
+        avail_h,
+        gap,
+    );
 
-        if changed {
-            rect.world_center = world_center;
-            rect.world_size = new_size;
-            rect.content =
-                Rect::from_center_size(world_center, Vec2::new(tile_w - 30.0, tile_h - 30.0));
-            rect.viewport = Viewport {
-                physical_position: phys_pos,
-                physical_size: phys_size,
-                depth: 0.0..1.0,
-            };
+    cells
+        .into_iter()
+        .map(|cell| {
+            let (x0, _) = col_tracks[cell.min.x as usize];
+            let (x1, w1) = col_tracks[cell.max.x as usize - 1];
+            let (y0, _) = row_tracks[cell.min.y as usize];
+            let (y1, h1) = row_tracks[cell.max.y as usize - 1];
+            let tile_w = x1 + w1 - x0;
+            let tile_h = y1 + h1 - y0;
+
+            // Viewport in physical pixels (CRITICAL FIX)
+            let vp_x = margin + x0;
+            let vp_y = margin + tab_bar_height + y0;
+
+            // World coordinates (centered origin)
+            let world_center = Vec2::new(
+                vp_x + tile_w * 0.5 - width * 0.5,
+                height * 0.5 - vp_y - tile_h * 0.5,
+            );
 
-            registry.dirty.push_back(tile.id);
-        }
-    }
+            TileRect {
+                world_center,
+                world_size: Vec2::new(tile_w, tile_h),
+                content: Rect::from_center_size(
+                    world_center,
+                    Vec2::new(tile_w - 30.0, tile_h - 30.0),
+                ),
+                viewport: Viewport {
+                    physical_position: UVec2::new(
+                        (vp_x * scale).round() as u32,
+                        (vp_y * scale).round() as u32,
+                    ),
+                    physical_size: UVec2::new(
+                        (tile_w * scale).round() as u32,
+                        (tile_h * scale).round() as u32,
+                    ),
+                    depth: 0.0..1.0,
+                },
+            }
+        })
+        .collect()
 }
 
 /// Create/update cameras for each tile
@@ -183,7 +385,9 @@ pub fn sync_tile_cameras(
     tiles: Query<(Entity, &PlotTile, &TileRect, &PlotKind, Option<&View3D>)>,
     existing: Query<Entity, With<TileCamera>>,
     existing_overlay: Query<Entity, With<TileOverlayCamera>>,
+    surface: RenderSurface,
 ) {
+    let target = surface.target();
     let mut used = HashSet::new();
     let mut used_overlay = HashSet::new();
 
@@ -216,6 +420,7 @@ pub fn sync_tile_cameras(
                     Camera2d,
                     Camera {
                         viewport: Some(rect.viewport.clone()),
+                        target: target.clone(),
                         order: 10 + tile.index as isize,
                         ..default()
                     },
@@ -233,6 +438,7 @@ pub fn sync_tile_cameras(
                     Camera3d::default(),
                     Camera {
                         viewport: Some(rect.viewport.clone()),
+                        target: target.clone(),
                         order: 10 + tile.index as isize,
                         ..default()
                     },
@@ -265,6 +471,7 @@ pub fn sync_tile_cameras(
                     Camera2d,
                     Camera {
                         viewport: Some(rect.viewport.clone()),
+                        target: target.clone(),
                         order: 50 + tile.index as isize, // Render after all 3D cameras (10+N)
                         clear_color: ClearColorConfig::None, // Don't clear, overlay on top
                         ..default()
@@ -285,6 +492,7 @@ pub fn sync_tile_cameras(
                     Camera2d,
                     Camera {
                         viewport: Some(rect.viewport.clone()),
+                        target: target.clone(),
                         order: 10 + tile.index as isize,
                         ..default()
                     },
@@ -353,20 +561,48 @@ fn compute_orbit_transform(view: &View3D) -> Transform {
     Transform::from_translation(pos).looking_at(view.target, Vec3::Y)
 }
 
+/// Seconds between two clicks for them to count as a double-click
+const DOUBLE_CLICK_SECS: f64 = 0.35;
+
+/// Smallest box-zoom extent (world units) that zooms the axis it spans
+const MIN_ZOOM_BOX: f32 = 5.0;
+
+/// Zoom change per scroll-wheel step in 2D, before the plot's sensitivity
+const WHEEL_ZOOM_2D: f32 = 0.05;
+
+/// Camera distance change per scroll-wheel step in 3D
+const WHEEL_ZOOM_3D: f32 = 0.5;
+
+/// Keyboard pan speed, in tile sizes per second
+const KEY_PAN_SPEED: f32 = 0.8;
+
+/// Keyboard zoom speed (natural log of the zoom factor per second)
+const KEY_ZOOM_SPEED: f32 = 1.5;
+
 /// Handle user input for both 2D and 3D tiles
+///
+/// 2D tiles pan on drag and zoom on scroll; scrolling over an axis zooms only
+/// that axis. Shift-drag (or right-drag) a box to zoom into it, double-click
+/// to go back to the auto-fit view. Each plot's `Interaction` decides what is
+/// allowed; with keyboard shortcuts on, the hovered plot pans with the arrow
+/// keys, zooms with +/-, resets with `r` and fits to its data with `f`.
 pub fn handle_input(
-    mut tiles_2d: Query<(&PlotTile, &mut TileView), Without<View3D>>,
+    mut commands: Commands,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut tiles_2d: Query<(Entity, &PlotTile, &TileRect, &mut TileView), Without<View3D>>,
     mut tiles_3d: Query<(&PlotTile, &mut View3D)>,
     mut registry: ResMut<TileRegistry>,
+    dash: Res<DashboardRes>,
     hovered: Res<HoveredTile>,
     mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Real>>,
+    mut zoom_box: ResMut<ZoomBox>,
+    select_gesture: Res<SelectGesture>,
+    mut last_click: ResMut<LastClick>,
     mut wheel: MessageReader<MouseWheel>,
     mut motion: MessageReader<MouseMotion>,
 ) {
-    let Some(hovered_index) = hovered.0 else {
-        return;
-    };
-
     // Collect events first (they can only be read once)
     let mut zoom_delta = 0.0;
     for event in wheel.read() {
@@ -378,32 +614,179 @@ pub fn handle_input(
         motion_delta += event.delta;
     }
 
+    let cursor = windows.single().ok().and_then(|window| {
+        let screen = window.cursor_position()?;
+        Some(Vec2::new(
+            screen.x - window.width() * 0.5,
+            window.height() * 0.5 - screen.y,
+        ))
+    });
+
+    let plots = dash.0.active_plots();
+    let interaction_of = |index: usize| {
+        plots
+            .get(index)
+            .map(crate::core::Plot::interaction)
+            .unwrap_or_default()
+    };
+
+    // A box zoom follows the cursor until the button is released, even
+    // outside its tile
+    if let Some(drag) = &mut zoom_box.0 {
+        if let Some(cursor) = cursor {
+            drag.end = cursor;
+        }
+        if !mouse.pressed(MouseButton::Left) && !mouse.pressed(MouseButton::Right) {
+            let drag = *drag;
+            zoom_box.0 = None;
+            if let Some((_, tile, rect, mut view)) = tiles_2d
+                .iter_mut()
+                .find(|(_, tile, _, _)| tile.index == drag.tile_index)
+            {
+                let free = free_axes(&interaction_of(tile.index));
+                let equal_aspect = equal_aspect(plots, tile.index);
+                if zoom_to_box(&mut view, rect, drag.start, drag.end, free, equal_aspect) {
+                    registry.dirty.push_back(tile.id);
+                }
+            }
+        }
+        return;
+    }
+
+    // Dragging out a selection neither pans nor rotates
+    if select_gesture.drag.is_some() {
+        return;
+    }
+
+    let Some(hovered_index) = hovered.0 else {
+        return;
+    };
+    let interaction = interaction_of(hovered_index);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let dt = time.delta_secs();
+
+    // Held keys: arrows give a pan direction, +/- a zoom direction
+    let mut key_pan = Vec2::ZERO;
+    let mut key_zoom = 0.0;
+    if interaction.keyboard {
+        for (key, dir) in [
+            (KeyCode::ArrowLeft, Vec2::NEG_X),
+            (KeyCode::ArrowRight, Vec2::X),
+            (KeyCode::ArrowDown, Vec2::NEG_Y),
+            (KeyCode::ArrowUp, Vec2::Y),
+        ] {
+            if keys.pressed(key) {
+                key_pan += dir;
+            }
+        }
+        if keys.any_pressed([KeyCode::Equal, KeyCode::NumpadAdd]) {
+            key_zoom += 1.0;
+        }
+        if keys.any_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+            key_zoom -= 1.0;
+        }
+    }
+    let reset = interaction.keyboard && keys.just_pressed(KeyCode::KeyR);
+    let fit = interaction.keyboard && keys.just_pressed(KeyCode::KeyF);
+
     // Handle 2D tiles
-    for (tile, mut view) in tiles_2d.iter_mut() {
+    for (entity, tile, rect, mut view) in tiles_2d.iter_mut() {
         if tile.index != hovered_index {
             continue;
         }
+        let free = free_axes(&interaction);
+        let equal_aspect = equal_aspect(plots, tile.index);
 
-        let mut changed = false;
+        // Start a box zoom
+        if interaction.zoom
+            && (mouse.just_pressed(MouseButton::Right)
+                || (shift && mouse.just_pressed(MouseButton::Left)))
+        {
+            if let Some(cursor) = cursor {
+                zoom_box.0 = Some(ZoomBoxDrag {
+                    tile_index: tile.index,
+                    start: cursor,
+                    end: cursor,
+                });
+            }
+            continue;
+        }
+
+        // Double-click or `r`: back to the auto-fit view
+        let mut double = false;
+        if mouse.just_pressed(MouseButton::Left) {
+            let now = time.elapsed_secs_f64();
+            double = match (last_click.at, cursor) {
+                (Some((at, pos)), Some(cursor)) => {
+                    now - at < DOUBLE_CLICK_SECS && pos.distance(cursor) < 6.0
+                }
+                _ => false,
+            };
+            last_click.at = if double {
+                None
+            } else {
+                cursor.map(|c| (now, c))
+            };
+        }
+        if double || reset {
+            commands.entity(entity).remove::<AutoFitted>();
+            continue;
+        }
 
-        // Zoom toward center with proper offset adjustment
-        if zoom_delta != 0.0 {
-            let old_scale = view.scale;
-            let new_scale =
-                (view.scale * (1.0 + zoom_delta * 0.05)).clamp(view.min_scale, view.max_scale);
+        let mut changed = false;
 
-            if new_scale != old_scale {
-                view.offset = view.offset * new_scale / old_scale;
-                view.scale = new_scale;
+        // `f`: fit the data on every unlocked axis, as a reset does, while
+        // keeping a locked axis where it is
+        if fit && interaction.zoom && (!equal_aspect || free.all()) {
+            if let Some(fitted) = fit_view(plots, tile.index, rect.world_size) {
+                view.scale = Vec2::select(free, fitted.scale, view.scale);
+                view.offset = Vec2::select(free, fitted.offset, view.offset);
+                if free.y {
+                    view.y2_map = fitted.y2_map;
+                }
                 changed = true;
             }
         }
 
-        // Pan (offset is in world coordinates)
-        if mouse.pressed(MouseButton::Left) && motion_delta != Vec2::ZERO {
-            view.offset.x += motion_delta.x;
-            view.offset.y -= motion_delta.y;
-            changed = true;
+        // Zoom toward center with proper offset adjustment; over an axis,
+        // only that axis zooms (unless the aspect ratio is locked)
+        if interaction.zoom && zoom_delta != 0.0 {
+            let factor = 1.0 + zoom_delta * WHEEL_ZOOM_2D * interaction.wheel_sensitivity;
+            let spines = match plots.get(tile.index) {
+                Some(crate::core::Plot::Graph2D(graph)) => graph.axes.spines,
+                _ => crate::core::Spines::Origin,
+            };
+            let over_axis = cursor
+                .filter(|_| !equal_aspect)
+                .and_then(|c| axis_at(c, rect, &view, spines));
+            let factor = match over_axis {
+                Some(Axis2D::X) => Vec2::new(factor, 1.0),
+                Some(Axis2D::Y) => Vec2::new(1.0, factor),
+                None => Vec2::splat(factor),
+            };
+            changed |= zoom_view(&mut view, Vec2::select(free, factor, Vec2::ONE));
+        }
+        if interaction.zoom && key_zoom != 0.0 {
+            let factor = (key_zoom * KEY_ZOOM_SPEED * dt).exp();
+            changed |= zoom_view(
+                &mut view,
+                Vec2::select(free, Vec2::splat(factor), Vec2::ONE),
+            );
+        }
+
+        // Pan (offset is in world coordinates); the arrows move the view,
+        // so the data moves the other way
+        if interaction.pan {
+            let mut pan = Vec2::ZERO;
+            if mouse.pressed(MouseButton::Left) {
+                pan += Vec2::new(motion_delta.x, -motion_delta.y);
+            }
+            pan -= key_pan * rect.world_size * KEY_PAN_SPEED * dt;
+            let pan = Vec2::select(free, pan, Vec2::ZERO);
+            if pan != Vec2::ZERO {
+                view.offset += pan;
+                changed = true;
+            }
         }
 
         if changed {
@@ -421,27 +804,51 @@ pub fn handle_input(
         let orbit_speed = 0.008;
         let pan_speed = 0.01;
 
-        // Zoom (scroll wheel adjusts radius)
-        if zoom_delta != 0.0 {
-            view3d.radius = (view3d.radius - zoom_delta * 0.5).clamp(2.0, 50.0);
+        // `r` restores the initial camera, `f` re-frames the data while
+        // keeping the orientation
+        if reset {
+            *view3d = View3D::default();
+            changed = true;
+        } else if fit {
+            let initial = View3D::default();
+            view3d.target = initial.target;
+            view3d.radius = initial.radius;
             changed = true;
         }
 
+        // Zoom (scroll wheel and +/- adjust radius)
+        if interaction.zoom {
+            let delta = zoom_delta * WHEEL_ZOOM_3D * interaction.wheel_sensitivity
+                + key_zoom * KEY_ZOOM_SPEED * dt * view3d.radius;
+            if delta != 0.0 {
+                let (min_radius, max_radius) = radius_limits(&interaction);
+                view3d.radius = (view3d.radius - delta).max(min_radius).min(max_radius);
+                changed = true;
+            }
+        }
+
         // Orbit (left mouse button)
-        if mouse.pressed(MouseButton::Left) && motion_delta != Vec2::ZERO {
+        if interaction.rotate && mouse.pressed(MouseButton::Left) && motion_delta != Vec2::ZERO {
             view3d.yaw -= motion_delta.x * orbit_speed;
             view3d.pitch = (view3d.pitch - motion_delta.y * orbit_speed).clamp(-1.5, 1.5);
             changed = true;
         }
 
-        // Pan (right mouse button)
-        if mouse.pressed(MouseButton::Right) && motion_delta != Vec2::ZERO {
-            let right = Vec3::new(view3d.yaw.cos(), 0.0, -view3d.yaw.sin());
-            let up = Vec3::Y;
-            let radius = view3d.radius;
-            view3d.target +=
-                (-right * motion_delta.x + up * motion_delta.y) * pan_speed * radius * 0.1;
-            changed = true;
+        // Pan (right mouse button drags the scene, the arrows move the
+        // camera as fast as a 300 px/s drag)
+        if interaction.pan {
+            let mut pan = Vec2::ZERO;
+            if mouse.pressed(MouseButton::Right) {
+                pan += motion_delta;
+            }
+            pan += Vec2::new(-key_pan.x, key_pan.y) * 300.0 * dt;
+            if pan != Vec2::ZERO {
+                let right = Vec3::new(view3d.yaw.cos(), 0.0, -view3d.yaw.sin());
+                let up = Vec3::Y;
+                let radius = view3d.radius;
+                view3d.target += (-right * pan.x + up * pan.y) * pan_speed * radius * 0.1;
+                changed = true;
+            }
         }
 
         if changed {
@@ -452,116 +859,1010 @@ pub fn handle_input(
     }
 }
 
-/// Auto-fit tiles to their data bounds on first render
-pub fn auto_fit_tiles(
-    mut commands: Commands,
-    mut registry: ResMut<TileRegistry>,
-    mut tiles: Query<(Entity, &PlotTile, &TileRect, &mut TileView), Without<AutoFitted>>,
-    dash: Res<DashboardRes>,
-) {
-    for (entity, tile, rect, mut view) in tiles.iter_mut() {
-        // Get data bounds for this plot
-        let Some(plot) = dash.0.active_plots().get(tile.index) else {
-            continue;
-        };
+/// Whether the plot at `index` keeps one data unit equally long on both axes
+fn equal_aspect(plots: &[crate::core::Plot], index: usize) -> bool {
+    matches!(plots.get(index), Some(crate::core::Plot::Graph2D(graph)) if graph.equal_aspect)
+}
 
-        // Compute data bounds based on plot type
-        let mut min_x = f32::INFINITY;
-        let mut max_x = f32::NEG_INFINITY;
-        let mut min_y = f32::INFINITY;
-        let mut max_y = f32::NEG_INFINITY;
+/// Axes of a 2D plot that may pan and zoom
+fn free_axes(interaction: &crate::core::Interaction) -> BVec2 {
+    use crate::core::AxisLock;
+    BVec2::new(
+        interaction.lock != Some(AxisLock::X),
+        interaction.lock != Some(AxisLock::Y),
+    )
+}
 
-        match plot {
-            crate::core::Plot::Graph2D(graph) => {
-                for layer in &graph.layers {
-                    for pt in &layer.xy {
-                        min_x = min_x.min(pt.x);
-                        max_x = max_x.max(pt.x);
-                        min_y = min_y.min(pt.y);
-                        max_y = max_y.max(pt.y);
-                    }
-                    // Also consider lower_line for FillBetween geometry
-                    if let Some(lower_line) = &layer.lower_line {
-                        for pt in lower_line {
-                            min_x = min_x.min(pt.x);
-                            max_x = max_x.max(pt.x);
-                            min_y = min_y.min(pt.y);
-                            max_y = max_y.max(pt.y);
-                        }
-                    }
-                }
-            }
-            crate::core::Plot::Candlestick(candle) => {
-                for c in &candle.candles {
-                    min_x = min_x.min(c.x);
-                    max_x = max_x.max(c.x);
-                    min_y = min_y.min(c.low);
-                    max_y = max_y.max(c.high);
-                }
-            }
-            _ => {
-                // Mark as fitted even if not a zoomable type
-                commands.entity(entity).try_insert(AutoFitted);
-                continue;
-            }
-        }
+/// Scale a 2D view about the tile center, within its zoom limits
+///
+/// Returns whether the view changed.
+fn zoom_view(view: &mut TileView, factor: Vec2) -> bool {
+    let old_scale = view.scale;
+    let new_scale = view.clamp_scale(view.scale * factor);
+    if new_scale == old_scale {
+        return false;
+    }
+    view.offset = view.offset * new_scale / old_scale;
+    view.scale = new_scale;
+    true
+}
 
-        // Skip if no valid bounds
-        if !min_x.is_finite() || !max_x.is_finite() || !min_y.is_finite() || !max_y.is_finite() {
-            commands.entity(entity).try_insert(AutoFitted);
-            continue;
-        }
+/// Closest and furthest 3D camera distance allowed by a plot's zoom limits
+fn radius_limits(interaction: &crate::core::Interaction) -> (f32, f32) {
+    let fit = View3D::default().radius;
+    let (min_zoom, max_zoom) = interaction.zoom_range((fit / 50.0, fit / 2.0));
+    (fit / max_zoom, fit / min_zoom)
+}
+
+/// Zoom a 2D view so the world-space box from `a` to `b` fills the tile
+///
+/// An axis the box barely spans (or a locked one) keeps its zoom, so a flat
+/// horizontal drag zooms only x. With `equal_aspect` both axes take the zoom
+/// that fits the box. Returns whether the view changed.
+fn zoom_to_box(
+    view: &mut TileView,
+    rect: &TileRect,
+    a: Vec2,
+    b: Vec2,
+    free: BVec2,
+    equal_aspect: bool,
+) -> bool {
+    let half = rect.world_size * 0.5;
+    let tile_min = rect.world_center - half;
+    let tile_max = rect.world_center + half;
+    let a = a.clamp(tile_min, tile_max);
+    let b = b.clamp(tile_min, tile_max);
+
+    let spans = (b - a).abs().cmpge(Vec2::splat(MIN_ZOOM_BOX)) & free;
+    if !spans.any() {
+        return false;
+    }
 
-        let data_width = (max_x - min_x).max(0.01);
-        let data_height = (max_y - min_y).max(0.01);
-        let data_center = Vec2::new((min_x + max_x) * 0.5, (min_y + max_y) * 0.5);
+    let data_a = world_to_data(a, rect, view);
+    let data_b = world_to_data(b, rect, view);
+    let data_center = (data_a + data_b) * 0.5;
 
-        // Compute scale to fit data in viewport with some padding
-        let padding = 0.85; // Use 85% of available space
-        let available_size = rect.world_size * padding;
-        let scale_x = available_size.x / data_width;
-        let scale_y = available_size.y / data_height;
-        let fit_scale = scale_x.min(scale_y);
+    let box_scale = rect.world_size / (data_b - data_a).abs();
+    let scale = view.clamp_scale(if equal_aspect {
+        Vec2::splat(Vec2::select(spans, box_scale, Vec2::INFINITY).min_element())
+    } else {
+        Vec2::select(spans, box_scale, view.scale)
+    });
+    let signed_scale = Vec2::select(view.invert, -scale, scale);
+    let offset = Vec2::select(
+        spans,
+        -data_center * signed_scale,
+        view.offset * scale / view.scale,
+    );
 
-        // Set view to center on data with zoom limits
-        view.scale = fit_scale;
-        view.offset = -data_center * fit_scale;
-        view.min_scale = fit_scale * 0.5; // Can zoom out to 50% of fit
-        view.max_scale = fit_scale * 4.0; // Can zoom in to 4x of fit
+    view.scale = scale;
+    view.offset = offset;
+    true
+}
 
-        // Mark as fitted and dirty
-        commands.entity(entity).try_insert(AutoFitted);
-        registry.dirty.push_back(tile.id);
+/// Link group of one axis (0 for x, 1 for y) of the plot at `index`
+fn link_group(plots: &[crate::core::Plot], index: usize, axis: usize) -> Option<&str> {
+    match plots.get(index) {
+        Some(crate::core::Plot::Graph2D(graph)) if axis == 0 => graph.link_x.as_deref(),
+        Some(crate::core::Plot::Graph2D(graph)) => graph.link_y.as_deref(),
+        _ => None,
     }
 }
 
-/// Draw only dirty tiles
-pub fn draw_dirty_tiles(
-    mut commands: Commands,
+/// Copy pan and zoom of a changed 2D view to tiles whose axes are linked to it
+///
+/// Linked tiles show the same data range along the linked axis, as far as each
+/// target's own axis lock, zoom limits and equal aspect allow. When several
+/// tiles of a group changed at once, the hovered one wins.
+pub fn sync_linked_views(
     mut registry: ResMut<TileRegistry>,
-    tiles: Query<(Entity, &PlotTile, &TileRect, &TileView)>,
-    children_q: Query<&Children>,
-    is_root_q: Query<(), With<TileRenderRoot>>,
     dash: Res<DashboardRes>,
-    unit: Res<UnitMeshes>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut std_materials: ResMut<Assets<StandardMaterial>>,
-    mut scatter_points: ResMut<ScatterPoints3D>,
-    mut axis_info_store: ResMut<AxisInfo3DStore>,
+    hovered: Res<HoveredTile>,
+    mut tiles: Query<(&PlotTile, &TileRect, &mut TileView), Without<View3D>>,
 ) {
-    while let Some(id) = registry.dirty.pop_front() {
-        // PlotId -> tile entity
-        let Some(&tile_entity) = registry.by_plot.get(&id) else {
-            continue;
-        };
+    let plots = dash.0.active_plots();
 
-        // Pull current tile state
-        let Ok((_e, tile, rect, view)) = tiles.get(tile_entity) else {
-            continue;
-        };
+    let mut changed: Vec<(usize, Vec2, TileView)> = tiles
+        .iter_mut()
+        .filter(|(_, _, view)| view.is_changed())
+        .map(|(tile, rect, view)| (tile.index, rect.world_size, *view))
+        .collect();
+    if changed.is_empty() {
+        return;
+    }
+    changed.sort_by_key(|(index, _, _)| Some(*index) != hovered.0);
 
-        // 1) Remove previous render root(s) under this tile (but keep the tile!)
+    let close = |a: f32, b: f32| (a - b).abs() <= 1e-5 * a.abs().max(b.abs()).max(1.0);
+    let mut synced: Vec<(usize, &str)> = Vec::new();
+    for (source, source_size, source_view) in changed {
+        for axis in 0..2 {
+            let Some(group) = link_group(plots, source, axis) else {
+                continue;
+            };
+            if synced.contains(&(axis, group)) {
+                continue;
+            }
+            synced.push((axis, group));
+
+            // Visible data range of the source along the linked axis
+            let span = source_size[axis] / source_view.scale[axis];
+            let center = -source_view.offset[axis] / source_view.signed_scale()[axis];
+
+            for (tile, rect, mut view) in tiles.iter_mut() {
+                if tile.index == source || link_group(plots, tile.index, axis) != Some(group) {
+                    continue;
+                }
+                // Follow the source the way input would move this tile, so
+                // its own axis lock, zoom limits, equal aspect and inverted
+                // axes still hold
+                let interaction = plots
+                    .get(tile.index)
+                    .map(crate::core::Plot::interaction)
+                    .unwrap_or_default();
+                let free = free_axes(&interaction);
+                if !free.test(axis) {
+                    continue;
+                }
+                let mut next = *view;
+                if interaction.zoom {
+                    let zoom = rect.world_size[axis] / span / next.scale[axis];
+                    let eq = equal_aspect(plots, tile.index);
+                    if !eq || free.all() {
+                        let along = BVec2::new(eq || axis == 0, eq || axis == 1);
+                        zoom_view(&mut next, Vec2::select(along, Vec2::splat(zoom), Vec2::ONE));
+                    }
+                }
+                if interaction.pan {
+                    next.offset[axis] = -center * next.signed_scale()[axis];
+                }
+                let unchanged = (0..2).all(|i| {
+                    close(view.scale[i], next.scale[i]) && close(view.offset[i], next.offset[i])
+                });
+                if unchanged {
+                    continue;
+                }
+                *view = next;
+                registry.dirty.push_back(tile.id);
+            }
+        }
+    }
+}
+
+/// Draw the rectangle of a box zoom in progress
+pub fn update_zoom_box_overlay(
+    mut commands: Commands,
+    zoom_box: Res<ZoomBox>,
+    tiles: Query<(&PlotTile, &TileRect)>,
+    overlays: Query<Entity, With<ZoomBoxOverlay>>,
+    unit: Res<UnitMeshes>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    // Recreated every frame, like the crosshair
+    for entity in overlays.iter() {
+        commands.entity(entity).try_despawn();
+    }
+
+    let Some(drag) = zoom_box.0 else {
+        return;
+    };
+    let Some((tile, rect)) = tiles.iter().find(|(tile, _)| tile.index == drag.tile_index) else {
+        return;
+    };
+
+    let half = rect.world_size * 0.5;
+    let a = drag
+        .start
+        .clamp(rect.world_center - half, rect.world_center + half);
+    let b = drag
+        .end
+        .clamp(rect.world_center - half, rect.world_center + half);
+    let min = a.min(b);
+    let size = (b - a).abs();
+    let center = min + size * 0.5;
+
+    let layer = RenderLayers::layer(tile.index % 32);
+    let fill = materials.add(ColorMaterial::from(Color::srgba(0.4, 0.7, 1.0, 0.12)));
+    let edge = materials.add(ColorMaterial::from(Color::srgba(0.4, 0.7, 1.0, 0.8)));
+
+    let mut quad = |center: Vec2, size: Vec2, material: &Handle<ColorMaterial>, z: f32| {
+        commands.spawn((
+            ZoomBoxOverlay,
+            Mesh2d(unit.quad.clone()),
+            MeshMaterial2d(material.clone()),
+            Transform {
+                translation: center.extend(z),
+                scale: size.max(Vec2::ONE).extend(1.0),
+                ..default()
+            },
+            layer.clone(),
+        ));
+    };
+    quad(center, size, &fill, 4.0);
+    quad(
+        Vec2::new(center.x, min.y),
+        Vec2::new(size.x, 1.0),
+        &edge,
+        4.1,
+    );
+    quad(
+        Vec2::new(center.x, min.y + size.y),
+        Vec2::new(size.x, 1.0),
+        &edge,
+        4.1,
+    );
+    quad(
+        Vec2::new(min.x, center.y),
+        Vec2::new(1.0, size.y),
+        &edge,
+        4.1,
+    );
+    quad(
+        Vec2::new(min.x + size.x, center.y),
+        Vec2::new(1.0, size.y),
+        &edge,
+        4.1,
+    );
+}
+
+/// How close (in pixels) a click must land to a point to select it
+const SELECT_CLICK_RADIUS: f32 = 8.0;
+
+/// Farthest the cursor may move between press and release for a click
+const CLICK_SLOP: f32 = 4.0;
+
+/// Select points by clicking, Ctrl-dragging a box or Alt-dragging a lasso
+pub fn handle_selection(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    tiles_2d: Query<(&PlotTile, &TileRect, &TileView), Without<View3D>>,
+    tiles_3d: Query<(&PlotTile, &TileRect, &View3D)>,
+    dash: Res<DashboardRes>,
+    hovered: Res<HoveredTile>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    zoom_box: Res<ZoomBox>,
+    mut gesture: ResMut<SelectGesture>,
+    mut selections: ResMut<Selections>,
+    mut changed: MessageWriter<SelectionChanged>,
+) {
+    let cursor = windows.single().ok().and_then(|window| {
+        let screen = window.cursor_position()?;
+        Some(Vec2::new(
+            screen.x - window.width() * 0.5,
+            window.height() * 0.5 - screen.y,
+        ))
+    });
+    let plots = dash.0.active_plots();
+
+    // Points of a tile with their world position, in whichever view it has
+    let points_of = |index: usize| -> Option<(&TileRect, Vec<(usize, usize, Vec2)>)> {
+        let plot = plots.get(index)?;
+        if let Some((_, rect, view)) = tiles_2d.iter().find(|(tile, ..)| tile.index == index) {
+            return Some((rect, selectable_points_2d(plot, rect, view)));
+        }
+        let (_, rect, view) = tiles_3d.iter().find(|(tile, ..)| tile.index == index)?;
+        Some((rect, selectable_points_3d(plot, rect, view)))
+    };
+
+    // A box or lasso follows the cursor until the button is released
+    if let Some(drag) = &mut gesture.drag {
+        if let Some(cursor) = cursor {
+            if drag
+                .path
+                .last()
+                .is_none_or(|last| last.distance(cursor) >= 2.0)
+            {
+                drag.path.push(cursor);
+            }
+        }
+        if mouse.pressed(MouseButton::Left) {
+            return;
+        }
+        let Some(drag) = gesture.drag.take() else {
+            return;
+        };
+        let Some((_, points)) = points_of(drag.tile_index) else {
+            return;
+        };
+        let inside = |p: Vec2| {
+            if drag.lasso {
+                in_polygon(p, &drag.path)
+            } else {
+                let (a, b) = (drag.path[0], drag.path[drag.path.len() - 1]);
+                Rect::from_corners(a, b).contains(p)
+            }
+        };
+        let picked = points
+            .into_iter()
+            .filter(|(.., world)| inside(*world))
+            .map(|(layer, index, _)| (layer, index));
+        select(
+            plots,
+            drag.tile_index,
+            group_by_layer(picked),
+            &mut selections,
+            &mut changed,
+        );
+        return;
+    }
+
+    let Some(hovered_index) = hovered.0 else {
+        gesture.press = None;
+        return;
+    };
+    let interaction = plots
+        .get(hovered_index)
+        .map(crate::core::Plot::interaction)
+        .unwrap_or_default();
+    if !interaction.select || zoom_box.0.is_some() {
+        gesture.press = None;
+        return;
+    }
+    let Some(cursor) = cursor else {
+        return;
+    };
+
+    let ctrl = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    let alt = keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    if mouse.just_pressed(MouseButton::Left) {
+        if (ctrl || alt) && !shift {
+            gesture.press = None;
+            gesture.drag = Some(SelectDrag {
+                tile_index: hovered_index,
+                lasso: alt,
+                path: vec![cursor],
+            });
+        } else if !shift {
+            gesture.press = Some((hovered_index, cursor));
+        }
+        return;
+    }
+
+    // A click without dragging picks the nearest point, or clears the
+    // selection when there is none close by
+    if mouse.just_released(MouseButton::Left) {
+        let Some((index, at)) = gesture.press.take() else {
+            return;
+        };
+        if index != hovered_index || at.distance(cursor) > CLICK_SLOP {
+            return;
+        }
+        let Some((_, points)) = points_of(index) else {
+            return;
+        };
+        let nearest = points
+            .into_iter()
+            .map(|(layer, i, world)| (layer, i, world.distance_squared(cursor)))
+            .filter(|(.., d)| *d <= SELECT_CLICK_RADIUS * SELECT_CLICK_RADIUS)
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        let picked = nearest.map(|(layer, i, _)| (layer, i));
+        select(
+            plots,
+            index,
+            group_by_layer(picked),
+            &mut selections,
+            &mut changed,
+        );
+    }
+}
+
+/// Points of a 2D plot's layers in world coordinates, as
+/// `(layer, index, world)`
+fn selectable_points_2d(
+    plot: &crate::core::Plot,
+    rect: &TileRect,
+    view: &TileView,
+) -> Vec<(usize, usize, Vec2)> {
+    let crate::core::Plot::Graph2D(graph) = plot else {
+        return Vec::new();
+    };
+    let mut points = Vec::new();
+    for (layer_index, layer) in graph.layers.iter().enumerate() {
+        if matches!(layer.geometry, crate::core::Geometry2D::FillBetween) {
+            continue;
+        }
+        let layer = on_primary_axis(layer, view);
+        points.extend(
+            layer
+                .xy
+                .iter()
+                .enumerate()
+                .map(|(i, &p)| (layer_index, i, data_to_world(p, rect, view))),
+        );
+    }
+    points
+}
+
+/// Points of a 3D plot's point layers projected into the tile, as
+/// `(layer, index, world)`
+fn selectable_points_3d(
+    plot: &crate::core::Plot,
+    rect: &TileRect,
+    view: &View3D,
+) -> Vec<(usize, usize, Vec2)> {
+    let crate::core::Plot::Graph3D(graph) = plot else {
+        return Vec::new();
+    };
+    let bounds = compute_3d_bounds(&graph.layers);
+    let cam_transform = compute_orbit_transform(view);
+    let mut points = Vec::new();
+    for (layer_index, layer) in graph.layers.iter().enumerate() {
+        if !matches!(layer.geometry, crate::core::Geometry3D::Points) {
+            continue;
+        }
+        points.extend(layer.xyz.iter().enumerate().filter_map(|(i, &p)| {
+            let world = project_3d(normalize_point(p, &bounds), rect, &cam_transform)?;
+            Some((layer_index, i, world))
+        }));
+    }
+    points
+}
+
+/// Whether `p` lies inside the polygon `path` (even-odd rule)
+fn in_polygon(p: Vec2, path: &[Vec2]) -> bool {
+    let mut inside = false;
+    let mut j = path.len().wrapping_sub(1);
+    for (i, &a) in path.iter().enumerate() {
+        let b = path[j];
+        if (a.y > p.y) != (b.y > p.y) && p.x < (b.x - a.x) * (p.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Collect `(layer, index)` pairs into sorted per-layer index lists
+fn group_by_layer(
+    picked: impl IntoIterator<Item = (usize, usize)>,
+) -> Vec<crate::core::SelectedPoints> {
+    let mut by_layer = std::collections::BTreeMap::<usize, Vec<usize>>::new();
+    for (layer, index) in picked {
+        by_layer.entry(layer).or_default().push(index);
+    }
+    by_layer
+        .into_iter()
+        .map(|(layer, mut indices)| {
+            indices.sort_unstable();
+            indices.dedup();
+            crate::core::SelectedPoints { layer, indices }
+        })
+        .collect()
+}
+
+/// Record a selection on the plot at `index`, report it and mirror its
+/// point indices onto plots in the same selection link group
+fn select(
+    plots: &[crate::core::Plot],
+    index: usize,
+    layers: Vec<crate::core::SelectedPoints>,
+    selections: &mut Selections,
+    changed: &mut MessageWriter<SelectionChanged>,
+) {
+    let Some(plot) = plots.get(index) else {
+        return;
+    };
+    let id = plot.id();
+    // Clearing an empty selection is not news
+    if layers.is_empty() && !selections.by_plot.contains_key(&id) {
+        return;
+    }
+
+    let rows: std::collections::BTreeSet<usize> = layers
+        .iter()
+        .flat_map(|l| l.indices.iter().copied())
+        .collect();
+    if let Some(group) = selection_group(plot) {
+        for other in plots {
+            if other.id() == id || selection_group(other) != Some(group) {
+                continue;
+            }
+            let mirrored: Vec<_> = layer_lengths(other)
+                .into_iter()
+                .enumerate()
+                .map(|(layer, len)| crate::core::SelectedPoints {
+                    layer,
+                    indices: rows.range(..len).copied().collect(),
+                })
+                .filter(|l| !l.indices.is_empty())
+                .collect();
+            if mirrored.is_empty() {
+                selections.by_plot.remove(&other.id());
+            } else {
+                selections.by_plot.insert(other.id(), mirrored);
+            }
+        }
+    }
+
+    if layers.is_empty() {
+        selections.by_plot.remove(&id);
+    } else {
+        selections.by_plot.insert(id, layers.clone());
+    }
+    changed.write(SelectionChanged(crate::core::Selection {
+        plot_id: id.0,
+        layers,
+    }));
+}
+
+fn selection_group(plot: &crate::core::Plot) -> Option<&str> {
+    match plot {
+        crate::core::Plot::Graph2D(graph) => graph.link_selection.as_deref(),
+        crate::core::Plot::Graph3D(graph) => graph.link_selection.as_deref(),
+        _ => None,
+    }
+}
+
+/// Number of points in each selectable layer of a plot (0 for the others)
+/// Selected points of `plot` that still exist after a data update, leaving
+/// out layers for which `replaced` holds
+fn kept_selection(
+    plot: Option<&crate::core::Plot>,
+    layers: &[crate::core::SelectedPoints],
+    replaced: impl Fn(usize) -> bool,
+) -> Vec<crate::core::SelectedPoints> {
+    let lengths = plot.map(layer_lengths).unwrap_or_default();
+    layers
+        .iter()
+        .filter(|l| !replaced(l.layer))
+        .filter_map(|l| {
+            let len = *lengths.get(l.layer)?;
+            let indices: Vec<usize> = l.indices.iter().copied().filter(|&i| i < len).collect();
+            (!indices.is_empty()).then_some(crate::core::SelectedPoints {
+                layer: l.layer,
+                indices,
+            })
+        })
+        .collect()
+}
+
+fn layer_lengths(plot: &crate::core::Plot) -> Vec<usize> {
+    match plot {
+        crate::core::Plot::Graph2D(graph) => graph
+            .layers
+            .iter()
+            .map(|l| match l.geometry {
+                crate::core::Geometry2D::FillBetween => 0,
+                _ => l.xy.len(),
+            })
+            .collect(),
+        crate::core::Plot::Graph3D(graph) => graph
+            .layers
+            .iter()
+            .map(|l| match l.geometry {
+                crate::core::Geometry3D::Points => l.xyz.len(),
+                _ => 0,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Opaque color of each layer of a plot, for selection highlights
+fn layer_colors(plot: &crate::core::Plot) -> Vec<crate::core::Color> {
+    match plot {
+        crate::core::Plot::Graph2D(graph) => graph
+            .layers
+            .iter()
+            .map(|l| l.style.color.with_a(1.0))
+            .collect(),
+        crate::core::Plot::Graph3D(graph) => graph
+            .layers
+            .iter()
+            .map(|l| l.style.color.with_a(1.0))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Pass selections to the callbacks registered on the dashboard handle
+pub fn notify_selection_listeners(
+    mut changed: MessageReader<SelectionChanged>,
+    live: Option<Res<LiveUpdates>>,
+) {
+    let Some(live) = live else {
+        changed.clear();
+        return;
+    };
+    for SelectionChanged(selection) in changed.read() {
+        live.on_select.notify(selection);
+    }
+}
+
+/// Highlight selected points and draw the box or lasso being dragged out
+///
+/// Highlights are rebuilt only when the selection, a view or the data
+/// changes; the drag outline every frame while it lasts.
+pub fn update_selection_overlay(
+    mut commands: Commands,
+    tiles_2d: Query<(&PlotTile, &TileRect, &TileView), Without<View3D>>,
+    tiles_3d: Query<(&PlotTile, &TileRect, &View3D)>,
+    moved: Query<(), Or<(Changed<TileView>, Changed<View3D>, Changed<TileRect>)>>,
+    dash: Res<DashboardRes>,
+    selections: Res<Selections>,
+    gesture: Res<SelectGesture>,
+    markers: Query<Entity, With<SelectionMarker>>,
+    unit: Res<UnitMeshes>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let dragging = gesture.drag.is_some();
+    if !(selections.is_changed() || gesture.is_changed() || dash.is_changed() || dragging)
+        && moved.is_empty()
+    {
+        return;
+    }
+    for entity in markers.iter() {
+        commands.entity(entity).try_despawn();
+    }
+
+    let plots = dash.0.active_plots();
+    let ring = materials.add(ColorMaterial::from(Color::srgba(1.0, 1.0, 1.0, 0.95)));
+    let edge = materials.add(ColorMaterial::from(Color::srgba(0.4, 0.7, 1.0, 0.8)));
+
+    let tiles = tiles_2d
+        .iter()
+        .map(|(tile, rect, view)| {
+            let points = plots
+                .get(tile.index)
+                .filter(|plot| selections.by_plot.contains_key(&plot.id()))
+                .map(|plot| selectable_points_2d(plot, rect, view))
+                .unwrap_or_default();
+            (tile, rect, points, RenderLayers::layer(tile.index % 32))
+        })
+        .chain(tiles_3d.iter().map(|(tile, rect, view)| {
+            let points = plots
+                .get(tile.index)
+                .filter(|plot| selections.by_plot.contains_key(&plot.id()))
+                .map(|plot| selectable_points_3d(plot, rect, view))
+                .unwrap_or_default();
+            (
+                tile,
+                rect,
+                points,
+                RenderLayers::layer((tile.index + 16) % 32),
+            )
+        }));
+
+    for (tile, rect, points, layer) in tiles {
+        let Some(plot) = plots.get(tile.index) else {
+            continue;
+        };
+        let bounds = Rect::from_center_size(rect.world_center, rect.world_size);
+
+        if let Some(selected) = selections.by_plot.get(&plot.id()) {
+            let is_selected = |layer: usize, index: usize| {
+                selected
+                    .iter()
+                    .find(|l| l.layer == layer)
+                    .is_some_and(|l| l.indices.binary_search(&index).is_ok())
+            };
+            // A white ring around a dot in the layer's own color
+            let fills: Vec<_> = layer_colors(plot)
+                .into_iter()
+                .map(|c| materials.add(ColorMaterial::from(Color::from(c))))
+                .collect();
+            commands
+                .spawn((
+                    SelectionMarker,
+                    Transform::default(),
+                    Visibility::Visible,
+                    InheritedVisibility::default(),
+                    ViewVisibility::default(),
+                ))
+                .with_children(|parent| {
+                    for (layer_index, index, world) in points {
+                        if !bounds.contains(world) || !is_selected(layer_index, index) {
+                            continue;
+                        }
+                        parent.spawn((
+                            Mesh2d(unit.quad.clone()),
+                            MeshMaterial2d(ring.clone()),
+                            Transform {
+                                translation: world.extend(4.0),
+                                scale: Vec3::splat(8.0),
+                                ..default()
+                            },
+                            layer.clone(),
+                        ));
+                        if let Some(fill) = fills.get(layer_index) {
+                            parent.spawn((
+                                Mesh2d(unit.quad.clone()),
+                                MeshMaterial2d(fill.clone()),
+                                Transform {
+                                    translation: world.extend(4.05),
+                                    scale: Vec3::splat(5.0),
+                                    ..default()
+                                },
+                                layer.clone(),
+                            ));
+                        }
+                    }
+                });
+        }
+
+        // The box or lasso being dragged out
+        let Some(drag) = gesture.drag.as_ref().filter(|d| d.tile_index == tile.index) else {
+            continue;
+        };
+        let corners;
+        let outline: &[Vec2] = if drag.lasso {
+            &drag.path
+        } else {
+            let (a, b) = (drag.path[0], drag.path[drag.path.len() - 1]);
+            corners = [a, Vec2::new(b.x, a.y), b, Vec2::new(a.x, b.y)];
+            &corners
+        };
+        for (i, &from) in outline.iter().enumerate() {
+            let to = outline[(i + 1) % outline.len()];
+            let (from, to) = (
+                from.clamp(bounds.min, bounds.max),
+                to.clamp(bounds.min, bounds.max),
+            );
+            commands.spawn((
+                SelectionMarker,
+                Mesh2d(unit.quad.clone()),
+                MeshMaterial2d(edge.clone()),
+                Transform {
+                    translation: ((from + to) * 0.5).extend(4.1),
+                    rotation: Quat::from_rotation_z((to - from).to_angle()),
+                    scale: Vec3::new(from.distance(to).max(1.0), 1.0, 1.0),
+                },
+                layer.clone(),
+            ));
+        }
+    }
+}
+
+/// Auto-fit tiles to their data bounds on first render
+pub fn auto_fit_tiles(
+    mut commands: Commands,
+    mut registry: ResMut<TileRegistry>,
+    mut tiles: Query<(Entity, &PlotTile, &TileRect, &mut TileView), Without<AutoFitted>>,
+    dash: Res<DashboardRes>,
+) {
+    for (entity, tile, rect, mut view) in tiles.iter_mut() {
+        if tile.index >= dash.0.active_plots().len() {
+            continue;
+        }
+
+        // Mark as fitted even if not a zoomable type or there is no data
+        commands.entity(entity).try_insert(AutoFitted);
+
+        if let Some(fitted) = fit_view(dash.0.active_plots(), tile.index, rect.world_size) {
+            *view = fitted;
+            registry.dirty.push_back(tile.id);
+        }
+    }
+}
+
+/// View that fits the data of `plots[index]` into a tile of `world_size`
+///
+/// Axes linked to other plots fit the data of the whole group, so linked
+/// tiles start out aligned. A 2D graph's fixed ranges, padding, inverted
+/// axes and equal aspect are applied on top. Returns `None` for plot types
+/// that are not zoomable or have no finite bounds.
+pub(crate) fn fit_view(
+    plots: &[crate::core::Plot],
+    index: usize,
+    world_size: Vec2,
+) -> Option<TileView> {
+    let plot = plots.get(index)?;
+    let graph = match plot {
+        crate::core::Plot::Graph2D(graph) => Some(graph),
+        crate::core::Plot::Candlestick(_) => None,
+        _ => return None,
+    };
+    let (mut min, mut max) = data_bounds(plot).unwrap_or((Vec2::INFINITY, Vec2::NEG_INFINITY));
+
+    let Some(graph) = graph else {
+        return fitted_view(
+            plot,
+            min,
+            max,
+            world_size,
+            Vec2::splat(1.0 - 2.0 * FIT_PADDING),
+        );
+    };
+
+    for other in plots {
+        let crate::core::Plot::Graph2D(other_graph) = other else {
+            continue;
+        };
+        let link_x = graph.link_x.is_some() && other_graph.link_x == graph.link_x;
+        let link_y = graph.link_y.is_some() && other_graph.link_y == graph.link_y;
+        if !link_x && !link_y {
+            continue;
+        }
+        let Some((other_min, other_max)) = data_bounds(other) else {
+            continue;
+        };
+        let linked = BVec2::new(link_x, link_y);
+        min = Vec2::select(linked, min.min(other_min), min);
+        max = Vec2::select(linked, max.max(other_max), max);
+    }
+
+    // Fixed bounds replace fitted ones; fully fixed axes are not padded
+    for (axis, range) in [graph.x_range, graph.y_range].into_iter().enumerate() {
+        if let Some(bound) = range[0] {
+            min[axis] = bound;
+        }
+        if let Some(bound) = range[1] {
+            max[axis] = bound;
+        }
+    }
+    let fixed = BVec2::new(
+        graph.x_range.iter().all(Option::is_some),
+        graph.y_range.iter().all(Option::is_some),
+    );
+    let fill = Vec2::select(
+        fixed,
+        Vec2::ONE,
+        Vec2::splat((1.0 - 2.0 * graph.padding).clamp(0.05, 1.0)),
+    );
+
+    let mut view = fitted_view(plot, min, max, world_size, fill)?;
+    if graph.equal_aspect {
+        let scale = view.scale.min_element();
+        let zoom = view.max_scale / view.scale;
+        let zoom_out = view.min_scale / view.scale;
+        view.scale = Vec2::splat(scale);
+        view.min_scale = Vec2::splat(scale) * zoom_out;
+        view.max_scale = Vec2::splat(scale) * zoom;
+    }
+    view.invert = BVec2::new(graph.invert_x, graph.invert_y);
+    view.offset = -(min + max) * 0.5 * view.signed_scale();
+
+    // The secondary axis spans its own data (or fixed range) over the same
+    // height as the primary one
+    if graph.has_secondary_y() {
+        let (secondary_min, secondary_max) = layer_bounds(graph, crate::core::YAxis::Secondary);
+        let y2_min = graph.y2_range[0].unwrap_or(secondary_min.y);
+        let y2_max = graph.y2_range[1].unwrap_or(secondary_max.y);
+        view.y2_map = Some(Vec2::X);
+        if y2_min.is_finite() && y2_max.is_finite() {
+            view.align_y2(span_range(min.y, max.y), span_range(y2_min, y2_max));
+        }
+    }
+    Some(view)
+}
+
+/// View centered on `min..=max`, filling `fill` of the tile along each axis
+fn fitted_view(
+    plot: &crate::core::Plot,
+    min: Vec2,
+    max: Vec2,
+    world_size: Vec2,
+    fill: Vec2,
+) -> Option<TileView> {
+    if !min.is_finite() || !max.is_finite() {
+        return None;
+    }
+
+    // Fit each axis on its own
+    let data_size = Vec2::new(fit_span(min.x, max.x), fit_span(min.y, max.y));
+    let fit_scale = world_size * fill / data_size;
+
+    // Center on data with the plot's zoom limits
+    let (min_zoom, max_zoom) = plot.interaction().zoom_range((0.5, 4.0));
+    Some(TileView {
+        offset: -(min + max) * 0.5 * fit_scale,
+        scale: fit_scale,
+        min_scale: fit_scale * min_zoom,
+        max_scale: fit_scale * max_zoom,
+        invert: BVec2::FALSE,
+        y2_map: None,
+    })
+}
+
+/// Data bounds of the layers plotted against one y axis (infinite if none)
+fn layer_bounds(graph: &crate::core::Graph2D, y_axis: crate::core::YAxis) -> (Vec2, Vec2) {
+    let mut min = Vec2::INFINITY;
+    let mut max = Vec2::NEG_INFINITY;
+    for layer in graph.layers.iter().filter(|l| l.y_axis == y_axis) {
+        // Also consider lower_line for FillBetween geometry
+        for &pt in layer.xy.iter().chain(layer.lower_line.iter().flatten()) {
+            min = min.min(pt);
+            max = max.max(pt);
+        }
+    }
+    (min, max)
+}
+
+/// `min..=max` widened the way fitting widens a flat series
+fn span_range(min: f32, max: f32) -> (f32, f32) {
+    let center = (min + max) * 0.5;
+    let half = fit_span(min, max) * 0.5;
+    (center - half, center + half)
+}
+
+/// Share of the tile left empty around fitted data, for plots without their
+/// own padding setting
+const FIT_PADDING: f32 = 0.075;
+
+/// Smallest and largest data coordinates of a zoomable plot
+fn data_bounds(plot: &crate::core::Plot) -> Option<(Vec2, Vec2)> {
+    // Compute data bounds based on plot type
+    let mut min_x = f32::INFINITY;
+    let mut max_x = f32::NEG_INFINITY;
+    let mut min_y = f32::INFINITY;
+    let mut max_y = f32::NEG_INFINITY;
+
+    match plot {
+        crate::core::Plot::Graph2D(graph) => {
+            // y follows the primary axis, or the secondary one when it has
+            // all the data
+            let (primary_min, primary_max) = layer_bounds(graph, crate::core::YAxis::Primary);
+            let (secondary_min, secondary_max) = layer_bounds(graph, crate::core::YAxis::Secondary);
+            min_x = primary_min.x.min(secondary_min.x);
+            max_x = primary_max.x.max(secondary_max.x);
+            (min_y, max_y) = if primary_min.y.is_finite() {
+                (primary_min.y, primary_max.y)
+            } else {
+                (secondary_min.y, secondary_max.y)
+            };
+        }
+        crate::core::Plot::Candlestick(candle) => {
+            for c in &candle.candles {
+                min_x = min_x.min(c.x);
+                max_x = max_x.max(c.x);
+                min_y = min_y.min(c.low);
+                max_y = max_y.max(c.high);
+            }
+        }
+        _ => return None,
+    }
+
+    // Skip if no valid bounds
+    if !min_x.is_finite() || !max_x.is_finite() || !min_y.is_finite() || !max_y.is_finite() {
+        return None;
+    }
+
+    Some((Vec2::new(min_x, min_y), Vec2::new(max_x, max_y)))
+}
+
+/// Extent of `min..=max` to fit; a flat series gets a unit-sized span
+fn fit_span(min: f32, max: f32) -> f32 {
+    let extent = max - min;
+    if extent > f32::EPSILON * max.abs().max(min.abs()).max(1.0) {
+        extent
+    } else {
+        (max.abs() * 0.2).max(1.0)
+    }
+}
+
+/// Draw only dirty tiles
+pub fn draw_dirty_tiles(
+    mut commands: Commands,
+    mut registry: ResMut<TileRegistry>,
+    tiles: Query<(Entity, &PlotTile, &TileRect, &TileView)>,
+    children_q: Query<&Children>,
+    is_root_q: Query<(), With<TileRenderRoot>>,
+    dash: Res<DashboardRes>,
+    unit: Res<UnitMeshes>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut std_materials: ResMut<Assets<StandardMaterial>>,
+    mut scatter_points: ResMut<ScatterPoints3D>,
+    mut axis_info_store: ResMut<AxisInfo3DStore>,
+) {
+    while let Some(id) = registry.dirty.pop_front() {
+        // PlotId -> tile entity
+        let Some(&tile_entity) = registry.by_plot.get(&id) else {
+            continue;
+        };
+
+        // Pull current tile state
+        let Ok((_e, tile, rect, view)) = tiles.get(tile_entity) else {
+            continue;
+        };
+
+        // 1) Remove previous render root(s) under this tile (but keep the tile!)
         if let Ok(children) = children_q.get(tile_entity) {
             for child in children.iter() {
                 if is_root_q.get(child).is_ok() {
@@ -601,6 +1902,17 @@ pub fn draw_dirty_tiles(
                         root,
                         rect,
                         view,
+                        &graph.axes,
+                        &unit,
+                        &mut materials,
+                        layer.clone(),
+                    );
+                    draw_annotations(
+                        &mut commands,
+                        root,
+                        &graph.annotations,
+                        rect,
+                        view,
                         &unit,
                         &mut materials,
                         layer,
@@ -614,6 +1926,7 @@ pub fn draw_dirty_tiles(
                         style,
                         x_label,
                         y_label,
+                        ..
                     } => {
                         draw_plot_title(&mut commands, root, meta, rect, layer.clone());
                         draw_histogram(
@@ -637,6 +1950,7 @@ pub fn draw_dirty_tiles(
                         style,
                         x_label,
                         y_label,
+                        ..
                     } => {
                         draw_plot_title(&mut commands, root, meta, rect, layer.clone());
                         draw_pdf(
@@ -660,6 +1974,7 @@ pub fn draw_dirty_tiles(
                         style,
                         x_label,
                         y_label,
+                        ..
                     } => {
                         draw_plot_title(&mut commands, root, meta, rect, layer.clone());
                         draw_boxplot(
@@ -682,6 +1997,7 @@ pub fn draw_dirty_tiles(
                         style,
                         x_label,
                         y_label,
+                        ..
                     } => {
                         draw_plot_title(&mut commands, root, meta, rect, layer.clone());
                         draw_ecdf(
@@ -710,6 +2026,16 @@ pub fn draw_dirty_tiles(
                         view,
                         &unit,
                         &mut materials,
+                        layer.clone(),
+                    );
+                    draw_annotations(
+                        &mut commands,
+                        root,
+                        &candle.annotations,
+                        rect,
+                        view,
+                        &unit,
+                        &mut materials,
                         layer,
                     );
                 }
@@ -772,6 +2098,158 @@ pub fn draw_dirty_tiles(
 }
 
 // Utility functions for grid layout
+
+/// Most columns a grid gets, whatever the dashboard asks for
+const MAX_GRID_COLUMNS: usize = 64;
+
+/// Cells covered by each plot, as `min..max` in (column, row) grid units,
+/// along with the grid's column and row counts
+fn grid_cells(
+    plots: &[crate::core::Plot],
+    layout: Option<&crate::core::GridLayout>,
+    aspect: f32,
+    configured_cols: Option<usize>,
+) -> (usize, usize, Vec<URect>) {
+    let areas: Vec<Vec<&str>> = layout
+        .map(|l| {
+            l.areas
+                .iter()
+                .map(|row| row.split_whitespace().collect())
+                .collect()
+        })
+        .unwrap_or_default();
+    let area_cols = areas.iter().map(Vec::len).max().unwrap_or(0);
+
+    let cols = if area_cols > 0 {
+        area_cols
+    } else if let Some(widths) = layout.map(|l| l.column_widths.len()).filter(|&n| n > 0) {
+        widths
+    } else {
+        grid_dims(plots.len(), aspect, configured_cols).0
+    }
+    .clamp(1, MAX_GRID_COLUMNS);
+    // Every plot fits below the areas, so no row or span needs to reach past
+    // this; pinned rows and spans from dashboard JSON are clamped to it
+    let max_rows = plots.len() + areas.len();
+    let span_of = |spec: &crate::core::GridCell, column: usize, row: usize| {
+        UVec2::new(
+            spec.column_span.clamp(1, cols - column) as u32,
+            spec.row_span.clamp(1, max_rows - row) as u32,
+        )
+    };
+
+    // Bounding box of each named area
+    let area_rect = |name: &str| -> Option<URect> {
+        if name == "." {
+            return None;
+        }
+        let mut found: Option<URect> = None;
+        for (r, row) in areas.iter().enumerate() {
+            for (c, _) in row.iter().enumerate().filter(|(_, n)| **n == name) {
+                let cell = URect::new(c as u32, r as u32, c as u32 + 1, r as u32 + 1);
+                found = Some(found.map_or(cell, |f| f.union(cell)));
+            }
+        }
+        found
+    };
+
+    let mut taken: Vec<Vec<bool>> = Vec::new();
+    let occupy = |taken: &mut Vec<Vec<bool>>, cell: URect| {
+        while taken.len() < cell.max.y as usize {
+            taken.push(vec![false; cols]);
+        }
+        for row in &mut taken[cell.min.y as usize..cell.max.y as usize] {
+            for slot in &mut row[cell.min.x as usize..(cell.max.x as usize).min(cols)] {
+                *slot = true;
+            }
+        }
+    };
+    let is_free = |taken: &[Vec<bool>], cell: URect| {
+        (cell.min.y..cell.max.y).all(|r| {
+            taken
+                .get(r as usize)
+                .is_none_or(|row| (cell.min.x..cell.max.x).all(|c| !row[c as usize]))
+        })
+    };
+
+    // Areas and pinned plots first, so auto-placed ones flow around them
+    let mut cells: Vec<Option<URect>> = plots
+        .iter()
+        .map(|plot| {
+            let spec = &plot.meta().cell;
+            if let Some(rect) = spec.area.as_deref().and_then(area_rect) {
+                return Some(rect);
+            }
+            let (row, column) = (spec.row?, spec.column?);
+            let (row, column) = (row.min(max_rows - 1), column.min(cols - 1));
+            let min = UVec2::new(column as u32, row as u32);
+            Some(URect::from_corners(
+                min,
+                min.saturating_add(span_of(spec, column, row)),
+            ))
+        })
+        .collect();
+    for cell in cells.iter().flatten() {
+        occupy(&mut taken, *cell);
+    }
+
+    for (plot, cell) in plots.iter().zip(&mut cells) {
+        if cell.is_some() {
+            continue;
+        }
+        let spec = &plot.meta().cell;
+        let span = span_of(spec, 0, 0);
+        // A lone row or column still pins that edge
+        let fits = |r: usize, c: usize| {
+            spec.row.is_none_or(|row| row == r)
+                && spec.column.is_none_or(|col| col == c)
+                && c + span.x as usize <= cols
+        };
+        // A row past everything placed so far always has room, unless the
+        // plot is pinned to a full row or a column too narrow for its span
+        let place = (0..=taken.len())
+            .flat_map(|r| (0..cols).map(move |c| (r, c)))
+            .filter(|&(r, c)| fits(r, c))
+            .map(|(r, c)| {
+                let min = UVec2::new(c as u32, r as u32);
+                URect::from_corners(min, min.saturating_add(span))
+            })
+            .find(|rect| is_free(&taken, *rect))
+            .unwrap_or_else(|| {
+                let min = UVec2::new(0, taken.len() as u32);
+                URect::from_corners(min, min.saturating_add(span))
+            });
+        occupy(&mut taken, place);
+        *cell = Some(place);
+    }
+
+    let row_heights = layout.map_or(0, |l| l.row_heights.len());
+    let rows = taken.len().max(areas.len()).max(row_heights).max(1);
+    (cols, rows, cells.into_iter().flatten().collect())
+}
+
+/// Offset and size of each of `count` tracks sharing `extent` pixels in
+/// proportion to `weights` (1 where missing), with `gap` between tracks
+fn grid_tracks(weights: &[f32], count: usize, extent: f32, gap: f32) -> Vec<(f32, f32)> {
+    let weight = |i: usize| {
+        weights
+            .get(i)
+            .copied()
+            .filter(|w| w.is_finite() && *w > 0.0)
+            .unwrap_or(1.0)
+    };
+    let total: f32 = (0..count).map(weight).sum();
+    let free = extent - count.saturating_sub(1) as f32 * gap;
+    let mut offset = 0.0;
+    (0..count)
+        .map(|i| {
+            let size = free * weight(i) / total;
+            let track = (offset, size);
+            offset += size + gap;
+            track
+        })
+        .collect()
+}
 fn grid_dims(n: usize, aspect: f32, configured_cols: Option<usize>) -> (usize, usize) {
     // If columns are explicitly configured, use that
     if let Some(cols) = configured_cols {
@@ -817,29 +2295,48 @@ fn cleanup_tile(commands: &mut Commands, registry: &mut TileRegistry, entity: En
     }
 }
 
-/// Find nearest data point on any trace in the graph
-fn find_nearest_point(cursor_data: Vec2, graph: &crate::core::Graph2D) -> Option<Vec2> {
-    let mut nearest: Option<(Vec2, f32)> = None;
+/// Remove every tile and forget saved views, so the next dashboard starts fresh
+pub fn clear_tiles(
+    mut commands: Commands,
+    mut registry: ResMut<TileRegistry>,
+    mut hover_index: ResMut<HoverIndex>,
+    tiles: Query<(Entity, &PlotTile)>,
+) {
+    for (entity, tile) in tiles.iter() {
+        cleanup_tile(&mut commands, &mut registry, entity, tile.id);
+    }
+    registry.saved_views.clear();
+    registry.dirty.clear();
+    hover_index.clear();
+}
 
-    for layer in &graph.layers {
-        // For FillBetween geometry, skip - we want to snap to actual trace points
-        if matches!(layer.geometry, crate::core::Geometry2D::FillBetween) {
+/// Find nearest data point on any trace in the graph, measured in data
+/// units or, with `screen` set, in world units on screen
+///
+/// Returns the point in primary-axis coordinates and whether it belongs to a
+/// layer on the secondary y axis.
+fn find_nearest_point(
+    cursor_data: Vec2,
+    graph: &crate::core::Graph2D,
+    view: &TileView,
+    index: &[SortedLayer],
+    screen: bool,
+) -> Option<(Vec2, bool)> {
+    let weight = if screen { view.scale } else { Vec2::ONE };
+    let mut nearest: Option<(Vec2, bool, f32)> = None;
+
+    for (layer, sorted) in graph.layers.iter().zip(index) {
+        let secondary = layer.y_axis == crate::core::YAxis::Secondary;
+        let map_y = |y| if secondary { view.y2_to_primary(y) } else { y };
+        let Some((pt, dist_sq)) = sorted.nearest(cursor_data, weight, map_y) else {
             continue;
-        }
-
-        for &pt in &layer.xy {
-            let dist_sq = (pt.x - cursor_data.x).powi(2) + (pt.y - cursor_data.y).powi(2);
-            let should_update = match &nearest {
-                Some((_, best_dist)) => dist_sq < *best_dist,
-                None => true,
-            };
-            if should_update {
-                nearest = Some((pt, dist_sq));
-            }
+        };
+        if nearest.is_none_or(|(_, _, best)| dist_sq < best) {
+            nearest = Some((Vec2::new(pt.x, map_y(pt.y)), secondary, dist_sq));
         }
     }
 
-    nearest.map(|(pt, _)| pt)
+    nearest.map(|(pt, secondary, _)| (pt, secondary))
 }
 
 /// Update crosshair position and visibility - snaps to nearest data point
@@ -850,6 +2347,7 @@ pub fn update_crosshair(
     hovered: Res<HoveredTile>,
     dash: Res<DashboardRes>,
     mut cursor_pos: ResMut<CursorWorldPos>,
+    mut hover_index: ResMut<HoverIndex>,
     crosshairs: Query<(Entity, &Crosshair)>,
     tooltips_3d: Query<(Entity, &Tooltip3D)>,
     scatter_points: Res<ScatterPoints3D>,
@@ -886,6 +2384,7 @@ pub fn update_crosshair(
     let Some(hovered_index) = hovered.0 else {
         return;
     };
+    let mut linked_cursor = None;
 
     for (tile, rect, view, view_3d) in tiles.iter() {
         if tile.index != hovered_index {
@@ -918,12 +2417,50 @@ pub fn update_crosshair(
             crate::core::Plot::Graph2D(graph) => {
                 // Convert cursor to data coordinates
                 let cursor_data = world_to_data(cursor_world, rect, view);
+                let index = hover_index.layers_of(graph);
+
+                if graph.hover == crate::core::HoverMode::XUnified {
+                    cursor_pos.data_coords = Some(cursor_data);
+                    if let Some(group) = &graph.link_x {
+                        linked_cursor = Some((group.as_str(), cursor_data.x));
+                    }
+                    spawn_unified_tooltip(
+                        &mut commands,
+                        tile.index,
+                        rect,
+                        view,
+                        cursor_world,
+                        graph,
+                        index,
+                        &unit,
+                        &mut materials,
+                        RenderLayers::layer(tile.index % 32),
+                    );
+                    continue;
+                }
 
                 // Find nearest data point
-                let snap_data = find_nearest_point(cursor_data, graph).unwrap_or(cursor_data);
+                let screen = graph.hover == crate::core::HoverMode::NearestScreen;
+                let (snap_data, secondary) =
+                    find_nearest_point(cursor_data, graph, view, index, screen)
+                        .unwrap_or((cursor_data, false));
                 let snap_world = data_to_world(snap_data, rect, view);
 
+                // Values are reported on the axis of the snapped layer
+                let coords = if secondary {
+                    format!(
+                        "({:.2}, {:.2} right)",
+                        snap_data.x,
+                        view.primary_to_y2(snap_data.y)
+                    )
+                } else {
+                    format!("({:.2}, {:.2})", snap_data.x, snap_data.y)
+                };
+
                 cursor_pos.data_coords = Some(snap_data);
+                if let Some(group) = &graph.link_x {
+                    linked_cursor = Some((group.as_str(), snap_data.x));
+                }
 
                 // Spawn crosshair with dashed lines
                 spawn_dashed_crosshair(
@@ -931,7 +2468,7 @@ pub fn update_crosshair(
                     tile.index,
                     rect,
                     snap_world,
-                    snap_data,
+                    coords,
                     &unit,
                     &mut materials,
                     RenderLayers::layer(tile.index % 32),
@@ -990,6 +2527,330 @@ pub fn update_crosshair(
             _ => {}
         }
     }
+
+    // Mirror the cursor's x position on tiles linked to the hovered one
+    let Some((group, x)) = linked_cursor else {
+        return;
+    };
+    for (tile, rect, view, _) in tiles.iter() {
+        if tile.index == hovered_index {
+            continue;
+        }
+        let Some(crate::core::Plot::Graph2D(graph)) = dash.0.active_plots().get(tile.index) else {
+            continue;
+        };
+        if graph.link_x.as_deref() != Some(group) {
+            continue;
+        }
+        spawn_linked_cursor(
+            &mut commands,
+            tile.index,
+            rect,
+            view,
+            x,
+            graph,
+            hover_index.layers_of(graph),
+            &unit,
+            &mut materials,
+            RenderLayers::layer(tile.index % 32),
+        );
+    }
+}
+
+/// Vertical cursor at data `x` on a linked tile, marking each layer's value
+/// nearest to it
+fn spawn_linked_cursor(
+    commands: &mut Commands,
+    tile_index: usize,
+    rect: &TileRect,
+    view: &TileView,
+    x: f32,
+    graph: &crate::core::Graph2D,
+    index: &[SortedLayer],
+    unit: &UnitMeshes,
+    materials: &mut Assets<ColorMaterial>,
+    layers: RenderLayers,
+) {
+    let world_x = data_to_world(Vec2::new(x, 0.0), rect, view).x;
+    let half = rect.world_size * 0.5;
+    if world_x < rect.world_center.x - half.x || world_x > rect.world_center.x + half.x {
+        return;
+    }
+
+    let line_mat = materials.add(ColorMaterial::from(Color::srgba(1.0, 1.0, 1.0, 0.5)));
+    let dash_length = 4.0;
+    let gap_length = 3.0;
+
+    commands
+        .spawn((
+            Crosshair { tile_index },
+            Transform::default(),
+            Visibility::Visible,
+            InheritedVisibility::default(),
+            ViewVisibility::default(),
+        ))
+        .with_children(|parent| {
+            let v_start = rect.world_center.y - half.y;
+            let v_end = rect.world_center.y + half.y;
+            let mut y = v_start;
+            while y < v_end {
+                let dash_end = (y + dash_length).min(v_end);
+                parent.spawn((
+                    Mesh2d(unit.quad.clone()),
+                    MeshMaterial2d(line_mat.clone()),
+                    Transform {
+                        translation: Vec3::new(world_x, (y + dash_end) / 2.0, 5.0),
+                        scale: Vec3::new(1.0, dash_end - y, 1.0),
+                        ..default()
+                    },
+                    CrosshairVLine,
+                    layers.clone(),
+                ));
+                y += dash_length + gap_length;
+            }
+
+            for (layer, sorted) in graph.layers.iter().zip(index) {
+                let Some(pt) = sorted.at_x(x, false) else {
+                    continue;
+                };
+                let plotted = match layer.y_axis {
+                    crate::core::YAxis::Primary => pt,
+                    crate::core::YAxis::Secondary => Vec2::new(pt.x, view.y2_to_primary(pt.y)),
+                };
+                let world = data_to_world(plotted, rect, view);
+                if world.y < v_start || world.y > v_end {
+                    continue;
+                }
+
+                let color = layer.style.color;
+                let point_mat = materials.add(ColorMaterial::from(Color::srgba(
+                    color.r, color.g, color.b, 0.95,
+                )));
+                parent.spawn((
+                    Mesh2d(unit.quad.clone()),
+                    MeshMaterial2d(point_mat),
+                    Transform {
+                        translation: world.extend(5.5),
+                        scale: Vec3::splat(5.0),
+                        ..default()
+                    },
+                    layers.clone(),
+                ));
+
+                let text = match &layer.label {
+                    Some(label) => format!("{}: {:.2}", label, pt.y),
+                    None => format!("{:.2}", pt.y),
+                };
+                let text_width = text.chars().count() as f32 * 7.0;
+                parent.spawn((
+                    Text2d::new(text),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgba(1.0, 1.0, 1.0, 0.9)),
+                    // Text is centered; shift it right of the marker by
+                    // about half its width
+                    Transform::from_translation(Vec3::new(
+                        world.x + 8.0 + text_width * 0.5,
+                        world.y,
+                        6.0,
+                    )),
+                    CrosshairCoordText,
+                    layers.clone(),
+                ));
+            }
+        });
+}
+
+/// Vertical cursor at the cursor x with one tooltip listing every layer's
+/// value there, marked in the layer's color
+fn spawn_unified_tooltip(
+    commands: &mut Commands,
+    tile_index: usize,
+    rect: &TileRect,
+    view: &TileView,
+    cursor_world: Vec2,
+    graph: &crate::core::Graph2D,
+    index: &[SortedLayer],
+    unit: &UnitMeshes,
+    materials: &mut Assets<ColorMaterial>,
+    layers: RenderLayers,
+) {
+    let x = world_to_data(cursor_world, rect, view).x;
+    let half = rect.world_size * 0.5;
+    let (v_start, v_end) = (rect.world_center.y - half.y, rect.world_center.y + half.y);
+
+    // Each layer's value at x, on its own axis, and where it is drawn
+    let mut rows = Vec::new();
+    for (i, (layer, sorted)) in graph.layers.iter().zip(index).enumerate() {
+        let interpolate = matches!(
+            layer.geometry,
+            crate::core::Geometry2D::Line | crate::core::Geometry2D::Area
+        );
+        let Some(pt) = sorted.at_x(x, interpolate) else {
+            continue;
+        };
+        let secondary = layer.y_axis == crate::core::YAxis::Secondary;
+        let plotted = if secondary {
+            Vec2::new(pt.x, view.y2_to_primary(pt.y))
+        } else {
+            pt
+        };
+        let name = match &layer.label {
+            Some(label) => label.clone(),
+            None => format!("layer {}", i + 1),
+        };
+        let text = format!(
+            "{}: {}{}",
+            name,
+            format_hover_value(pt.y),
+            if secondary { " (right)" } else { "" }
+        );
+        rows.push((text, layer.style.color, data_to_world(plotted, rect, view)));
+    }
+    if rows.is_empty() {
+        return;
+    }
+
+    let line_mat = materials.add(ColorMaterial::from(Color::srgba(1.0, 1.0, 1.0, 0.5)));
+    let box_mat = materials.add(ColorMaterial::from(Color::srgba(0.08, 0.08, 0.1, 0.85)));
+    let dash_length = 4.0;
+    let gap_length = 3.0;
+
+    // Tooltip box beside the cursor, flipped left near the right edge
+    let font_size = 12.0;
+    let row_height = 16.0;
+    let header = format!("x = {}", format_hover_value(x));
+    let char_width = 7.0;
+    let widest = rows
+        .iter()
+        .map(|(text, ..)| text.chars().count() + 2)
+        .chain([header.chars().count()])
+        .max()
+        .unwrap_or(0);
+    let box_size = Vec2::new(
+        widest as f32 * char_width + 16.0,
+        (rows.len() + 1) as f32 * row_height + 8.0,
+    );
+    let mut box_min = cursor_world + Vec2::new(16.0, -box_size.y * 0.5);
+    if box_min.x + box_size.x > rect.world_center.x + half.x {
+        box_min.x = cursor_world.x - 16.0 - box_size.x;
+    }
+    box_min.y = box_min.y.clamp(v_start, (v_end - box_size.y).max(v_start));
+
+    commands
+        .spawn((
+            Crosshair { tile_index },
+            Transform::default(),
+            Visibility::Visible,
+            InheritedVisibility::default(),
+            ViewVisibility::default(),
+        ))
+        .with_children(|parent| {
+            let mut y = v_start;
+            while y < v_end {
+                let dash_end = (y + dash_length).min(v_end);
+                parent.spawn((
+                    Mesh2d(unit.quad.clone()),
+                    MeshMaterial2d(line_mat.clone()),
+                    Transform {
+                        translation: Vec3::new(cursor_world.x, (y + dash_end) / 2.0, 5.0),
+                        scale: Vec3::new(1.0, dash_end - y, 1.0),
+                        ..default()
+                    },
+                    CrosshairVLine,
+                    layers.clone(),
+                ));
+                y += dash_length + gap_length;
+            }
+
+            parent.spawn((
+                Mesh2d(unit.quad.clone()),
+                MeshMaterial2d(box_mat),
+                Transform {
+                    translation: (box_min + box_size * 0.5).extend(5.8),
+                    scale: box_size.extend(1.0),
+                    ..default()
+                },
+                layers.clone(),
+            ));
+
+            // Text is centered; shift each line right by about half its
+            // width so the column reads left-aligned
+            let text_left = box_min.x + 8.0;
+            let mut row_y = box_min.y + box_size.y - 4.0 - row_height * 0.5;
+            parent.spawn((
+                Text2d::new(header.clone()),
+                TextFont {
+                    font_size,
+                    ..default()
+                },
+                TextColor(Color::srgba(1.0, 1.0, 1.0, 0.9)),
+                Transform::from_translation(Vec3::new(
+                    text_left + header.chars().count() as f32 * char_width * 0.5,
+                    row_y,
+                    6.0,
+                )),
+                CrosshairCoordText,
+                layers.clone(),
+            ));
+
+            for (text, color, world) in rows {
+                let layer_color = Color::srgba(color.r, color.g, color.b, 0.95);
+                let swatch_mat = materials.add(ColorMaterial::from(layer_color));
+                row_y -= row_height;
+
+                if world.y >= v_start && world.y <= v_end {
+                    parent.spawn((
+                        Mesh2d(unit.quad.clone()),
+                        MeshMaterial2d(swatch_mat.clone()),
+                        Transform {
+                            translation: world.extend(5.5),
+                            scale: Vec3::splat(5.0),
+                            ..default()
+                        },
+                        layers.clone(),
+                    ));
+                }
+                parent.spawn((
+                    Mesh2d(unit.quad.clone()),
+                    MeshMaterial2d(swatch_mat),
+                    Transform {
+                        translation: Vec3::new(text_left + 4.0, row_y, 6.0),
+                        scale: Vec3::new(8.0, 8.0, 1.0),
+                        ..default()
+                    },
+                    layers.clone(),
+                ));
+                let text_x = text_left + 2.0 * char_width;
+                parent.spawn((
+                    Text2d::new(text.clone()),
+                    TextFont {
+                        font_size,
+                        ..default()
+                    },
+                    TextColor(Color::srgba(1.0, 1.0, 1.0, 0.9)),
+                    Transform::from_translation(Vec3::new(
+                        text_x + text.chars().count() as f32 * char_width * 0.5,
+                        row_y,
+                        6.0,
+                    )),
+                    CrosshairCoordText,
+                    layers.clone(),
+                ));
+            }
+        });
+}
+
+/// Hover value with two decimals, or in scientific notation when that would
+/// hide it
+fn format_hover_value(v: f32) -> String {
+    if v != 0.0 && !(1e-2..1e6).contains(&v.abs()) {
+        format!("{:.2e}", v)
+    } else {
+        format!("{:.2}", v)
+    }
 }
 
 fn spawn_dashed_crosshair(
@@ -997,7 +2858,7 @@ fn spawn_dashed_crosshair(
     tile_index: usize,
     rect: &TileRect,
     snap_world: Vec2,
-    snap_data: Vec2,
+    coords: String,
     unit: &UnitMeshes,
     materials: &mut Assets<ColorMaterial>,
     layers: RenderLayers,
@@ -1079,7 +2940,7 @@ fn spawn_dashed_crosshair(
 
             // Coordinate text near the snapped point (offset to avoid overlap)
             parent.spawn((
-                Text2d::new(format!("({:.2}, {:.2})", snap_data.x, snap_data.y)),
+                Text2d::new(coords),
                 TextFont {
                     font_size: 12.0,
                     ..default()
@@ -1629,7 +3490,7 @@ fn spawn_candlestick_tooltip(
     let candle_low_world = data_to_world(Vec2::new(c.x, c.low), rect, view);
 
     // Candle width in world space
-    let candle_world_width = candle_data_width * view.scale;
+    let candle_world_width = candle_data_width * view.scale.x;
 
     let is_up = c.close >= c.open;
     let highlight_color = if is_up {
@@ -2022,6 +3883,29 @@ fn spawn_radial_tooltip(
 }
 
 /// Spawn 3D scatter point tooltip
+/// Where a point of a 3D tile's normalized scene appears in world
+/// coordinates, using a simplified projection through the orbit camera
+/// `cam_transform` (see `compute_orbit_transform`); `None` behind the camera
+fn project_3d(point: Vec3, rect: &TileRect, cam_transform: &Transform) -> Option<Vec2> {
+    let fov = std::f32::consts::FRAC_PI_4; // 45 degrees
+    let aspect = rect.world_size.x / rect.world_size.y;
+    let tan_half_fov = (fov * 0.5).tan();
+
+    // Vector from camera to point
+    let to_point = point - cam_transform.translation;
+    let depth = to_point.dot(*cam_transform.forward());
+    if depth <= 0.1 {
+        return None;
+    }
+
+    // Project to normalized device coords, then into the tile
+    let ndc = Vec2::new(
+        to_point.dot(*cam_transform.right()) / (depth * tan_half_fov * aspect),
+        to_point.dot(*cam_transform.up()) / (depth * tan_half_fov),
+    );
+    Some(rect.world_center + ndc * 0.5 * rect.world_size)
+}
+
 fn spawn_3d_tooltip(
     commands: &mut Commands,
     tile_index: usize,
@@ -2032,21 +3916,9 @@ fn spawn_3d_tooltip(
     unit: &UnitMeshes,
     materials: &mut Assets<ColorMaterial>,
 ) {
-    // Project 3D points to 2D screen space and find nearest to cursor
-    // We use a simplified projection based on the View3D camera
-
-    // Get camera transform
-    let cam_transform = compute_orbit_transform(view);
-    let cam_pos = cam_transform.translation;
-    let cam_forward = cam_transform.forward();
-    let cam_right = cam_transform.right();
-    let cam_up = cam_transform.up();
-
     // Viewport dimensions (use tile size as approximate)
     let vp_width = rect.world_size.x;
     let vp_height = rect.world_size.y;
-    let fov = std::f32::consts::FRAC_PI_4; // 45 degrees
-    let aspect = vp_width / vp_height;
 
     // Convert cursor from world to viewport-relative coords
     let cursor_vp = Vec2::new(
@@ -2060,43 +3932,23 @@ fn spawn_3d_tooltip(
     }
 
     // Find nearest point by projecting to screen
+    let cam_transform = compute_orbit_transform(view);
     let mut nearest: Option<(Vec3, Vec2, f32)> = None; // (original_coords, screen_pos, dist_sq)
-    let tan_half_fov = (fov * 0.5).tan();
 
     for &(original, normalized) in points {
-        // Vector from camera to point
-        let to_point = normalized - cam_pos;
-        let depth = to_point.dot(*cam_forward);
-
-        if depth <= 0.1 {
-            continue; // Point is behind camera
-        }
-
-        // Project to normalized device coords
-        let right_dist = to_point.dot(*cam_right);
-        let up_dist = to_point.dot(*cam_up);
-
-        let ndc_x = right_dist / (depth * tan_half_fov * aspect);
-        let ndc_y = up_dist / (depth * tan_half_fov);
-
-        // Convert to viewport coords [0, 1]
-        let screen_x = (ndc_x + 1.0) * 0.5;
-        let screen_y = (ndc_y + 1.0) * 0.5;
+        let Some(world_screen) = project_3d(normalized, rect, &cam_transform) else {
+            continue;
+        };
 
-        // Distance from cursor
-        let dx = screen_x - cursor_vp.x;
-        let dy = screen_y - cursor_vp.y;
-        let dist_sq = dx * dx + dy * dy;
+        // Distance from cursor, in viewport fractions
+        let d = (world_screen - cursor_world) / rect.world_size;
+        let dist_sq = d.length_squared();
 
         let should_update = match &nearest {
             Some((_, _, best_dist)) => dist_sq < *best_dist,
             None => true,
         };
         if should_update {
-            let world_screen = Vec2::new(
-                rect.world_center.x + (screen_x - 0.5) * vp_width,
-                rect.world_center.y + (screen_y - 0.5) * vp_height,
-            );
             nearest = Some((original, world_screen, dist_sq));
         }
     }
@@ -2368,7 +4220,7 @@ pub fn update_3d_axis_labels(
 /// Spawn or update the tab bar UI
 pub fn update_tab_bar(
     mut commands: Commands,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    surface: RenderSurface,
     dash: Res<DashboardRes>,
     existing_tabs: Query<Entity, With<TabBar>>,
     unit: Res<UnitMeshes>,
@@ -2384,7 +4236,7 @@ pub fn update_tab_bar(
         return;
     }
 
-    let Ok(window) = windows.single() else {
+    let Some((width, height, _)) = surface.size() else {
         return;
     };
 
@@ -2415,12 +4267,8 @@ pub fn update_tab_bar(
                 Mesh2d(unit.quad.clone()),
                 MeshMaterial2d(bar_mat),
                 Transform {
-                    translation: Vec3::new(
-                        0.0,
-                        window.height() * 0.5 - margin - tab_bar_height * 0.5,
-                        0.0,
-                    ),
-                    scale: Vec3::new(window.width() - margin * 2.0, tab_bar_height, 1.0),
+                    translation: Vec3::new(0.0, height * 0.5 - margin - tab_bar_height * 0.5, 0.0),
+                    scale: Vec3::new(width - margin * 2.0, tab_bar_height, 1.0),
                     ..default()
                 },
                 layers.clone(),
@@ -2433,7 +4281,7 @@ pub fn update_tab_bar(
             for (i, name) in tab_names.iter().enumerate() {
                 let is_active = i == active_tab;
                 let tab_x = start_x + i as f32 * (tab_width + tab_gap);
-                let tab_y = window.height() * 0.5 - margin - tab_bar_height * 0.5;
+                let tab_y = height * 0.5 - margin - tab_bar_height * 0.5;
 
                 // Tab button background
                 let tab_color = if is_active {
@@ -2523,24 +4371,154 @@ pub fn handle_tab_clicks(
     }
 }
 
-/// Detect tab changes and refresh tiles
-pub fn detect_tab_change(
-    mut commands: Commands,
-    dash: Res<DashboardRes>,
-    mut prev_tab: ResMut<PreviousActiveTab>,
-    mut registry: ResMut<TileRegistry>,
-    existing: Query<(Entity, &PlotTile)>,
-) {
-    if !dash.0.has_tabs() {
-        return;
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::GridCell;
+    use crate::dash::{GridPlacement, dash};
 
-    if dash.0.active_tab != prev_tab.0 {
-        prev_tab.0 = dash.0.active_tab;
+    fn line(
+        cell: GridCell,
+    ) -> impl FnOnce(crate::dash::Plot2DBuilder) -> crate::dash::Plot2DBuilder {
+        move |p| p.line(vec![Vec2::ZERO, Vec2::ONE], None).grid_cell(cell)
+    }
 
-        // Clear all existing tiles to force respawn
-        for (entity, tile) in existing.iter() {
-            cleanup_tile(&mut commands, &mut registry, entity, tile.id);
+    #[test]
+    fn grid_clamps_out_of_range_cells() {
+        let huge = GridCell::at(usize::MAX, usize::MAX).span(usize::MAX, usize::MAX);
+        let lone_row = GridCell {
+            row: Some(u32::MAX as usize),
+            row_span: usize::MAX,
+            ..GridCell::default()
+        };
+        let dashboard = dash()
+            .add_2d(line(huge))
+            .add_2d(line(lone_row))
+            .add_2d(line(GridCell::default()))
+            .build();
+
+        let (cols, rows, cells) = grid_cells(&dashboard.plots, None, 1.0, None);
+        assert_eq!(cells.len(), 3);
+        assert!(rows <= 3 * dashboard.plots.len());
+        for cell in &cells {
+            assert!(cell.min.x < cell.max.x && cell.max.x as usize <= cols);
+            assert!(cell.min.y < cell.max.y && cell.max.y as usize <= rows);
         }
+
+        let rects = tile_rects(&dashboard, 800.0, 600.0, 1.0);
+        assert_eq!(rects.len(), 3);
+        assert!(rects.iter().all(|r| r.world_size.is_finite()));
+    }
+
+    #[test]
+    fn zoom_limits_stay_ordered() {
+        let mut interaction = crate::core::Interaction {
+            max_zoom: Some(0.1),
+            ..Default::default()
+        };
+        let (near, far) = radius_limits(&interaction);
+        assert!(near <= far);
+
+        interaction.set_zoom_limits(2.0, 0.5);
+        assert_eq!(interaction.zoom_range((0.5, 4.0)), (0.5, 2.0));
+        let (near, far) = radius_limits(&interaction);
+        assert!(near <= far);
+
+        let parsed = serde_json::from_str::<crate::core::Interaction>(r#"{"min_zoom": -1.0}"#);
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn listeners_may_register_and_remove_from_a_callback() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let listeners = SelectionListeners::default();
+        let calls = std::sync::Arc::new(AtomicUsize::new(0));
+        let (inner, count) = (listeners.clone(), calls.clone());
+        let id = listeners.add(move |_| {
+            count.fetch_add(1, Ordering::SeqCst);
+            inner.add(|_| {});
+        });
+        let (inner, removed) = (listeners.clone(), id);
+        listeners.add(move |_| {
+            inner.remove(removed);
+        });
+
+        let selection = crate::core::Selection::default();
+        listeners.notify(&selection);
+        listeners.notify(&selection);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(!listeners.remove(id));
+    }
+
+    #[test]
+    fn data_updates_drop_stale_selections() {
+        use crate::core::SelectedPoints;
+
+        let dashboard = dash()
+            .add_2d(|p| {
+                p.scatter(vec![Vec2::ZERO; 3], None)
+                    .scatter(vec![Vec2::ONE; 5], None)
+            })
+            .build();
+        let plot = dashboard.plots.first();
+        let layers = [
+            SelectedPoints {
+                layer: 0,
+                indices: vec![1, 4],
+            },
+            SelectedPoints {
+                layer: 1,
+                indices: vec![2],
+            },
+            SelectedPoints {
+                layer: 2,
+                indices: vec![0],
+            },
+        ];
+
+        let kept = kept_selection(plot, &layers, |_| false);
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].indices, [1]);
+        assert_eq!(kept[1].indices, [2]);
+
+        let kept = kept_selection(plot, &layers, |layer| layer == 1);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].layer, 0);
+
+        assert!(kept_selection(None, &layers, |_| false).is_empty());
+    }
+
+    #[test]
+    fn hover_index_resorts_only_touched_layers() {
+        let mut dashboard = dash()
+            .add_2d(|p| {
+                p.scatter(vec![Vec2::new(2.0, 0.0), Vec2::ZERO], None)
+                    .scatter(vec![Vec2::ONE], None)
+            })
+            .build();
+        let crate::core::Plot::Graph2D(graph) = &mut dashboard.plots[0] else {
+            unreachable!()
+        };
+        let mut index = HoverIndex::default();
+        assert_eq!(index.layers_of(graph)[0].points[0], Vec2::ZERO);
+
+        graph.layers[0].xy.push(Vec2::new(-1.0, 0.0));
+        graph.layers[1].xy.push(Vec2::ZERO);
+        index.invalidate_layer(graph.id, 0);
+        let layers = index.layers_of(graph);
+        assert_eq!(layers[0].points[0], Vec2::new(-1.0, 0.0));
+        assert_eq!(layers[1].points.len(), 1);
+
+        index.invalidate_plot(graph.id);
+        assert_eq!(index.layers_of(graph)[1].points.len(), 2);
+    }
+
+    #[test]
+    fn grid_caps_configured_columns() {
+        let dashboard = dash().add_2d(line(GridCell::default())).build();
+        let (cols, _, cells) = grid_cells(&dashboard.plots, None, 1.0, Some(usize::MAX));
+        assert_eq!(cols, MAX_GRID_COLUMNS);
+        assert_eq!(cells.len(), 1);
     }
 }
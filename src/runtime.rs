@@ -1,26 +1,52 @@
 use bevy::prelude::*;
 
 use crate::core::Dashboard;
-use crate::render::{DashRenderPlugin, DashboardRes};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::core::Plot;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::render::OffscreenTarget;
+use crate::render::{DashRenderPlugin, DashboardRes, LiveUpdates};
 
 #[cfg(not(target_arch = "wasm32"))]
 pub fn run_dashboard(dashboard: Dashboard) {
+    native_app(dashboard).run();
+}
+
+/// Run a dashboard natively, applying updates received on `updates` while it is open
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run_dashboard_live(dashboard: Dashboard, updates: LiveUpdates) {
+    native_app(dashboard).insert_resource(updates).run();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn native_app(dashboard: Dashboard) -> App {
     let bg = dashboard.background;
-    App::new()
-        .insert_resource(ClearColor(Color::srgb(bg.r, bg.g, bg.b)))
+    let mut app = App::new();
+    app.insert_resource(ClearColor(Color::srgb(bg.r, bg.g, bg.b)))
         .insert_resource(DashboardRes::new(dashboard))
         .add_plugins((
             DefaultPlugins.set(ImagePlugin::default_nearest()),
             DashRenderPlugin,
-        ))
-        .run();
+        ));
+    app
 }
 
 #[cfg(target_arch = "wasm32")]
 pub fn run_dashboard(dashboard: Dashboard, canvas_id: &str) {
+    web_app(dashboard, canvas_id).run();
+}
+
+/// Run a dashboard in the browser, applying updates received on `updates` while it is open
+#[cfg(target_arch = "wasm32")]
+pub fn run_dashboard_live(dashboard: Dashboard, canvas_id: &str, updates: LiveUpdates) {
+    web_app(dashboard, canvas_id).insert_resource(updates).run();
+}
+
+#[cfg(target_arch = "wasm32")]
+fn web_app(dashboard: Dashboard, canvas_id: &str) -> App {
     let bg = dashboard.background;
-    App::new()
-        .insert_resource(ClearColor(Color::srgb(bg.r, bg.g, bg.b)))
+    let mut app = App::new();
+    app.insert_resource(ClearColor(Color::srgb(bg.r, bg.g, bg.b)))
         .insert_resource(DashboardRes::new(dashboard))
         .add_plugins((
             DefaultPlugins
@@ -34,6 +60,272 @@ pub fn run_dashboard(dashboard: Dashboard, canvas_id: &str) {
                 })
                 .set(ImagePlugin::default_nearest()),
             DashRenderPlugin,
-        ))
-        .run();
+        ));
+    app
+}
+
+/// Frames rendered before reading back, so tiles are laid out, fitted, drawn
+/// and their text shaped
+#[cfg(not(target_arch = "wasm32"))]
+const WARMUP_FRAMES: usize = 8;
+
+/// Frames to wait for the GPU readback before giving up
+#[cfg(not(target_arch = "wasm32"))]
+const READBACK_FRAMES: usize = 60;
+
+/// Render a dashboard offscreen and encode it as PNG (native only)
+///
+/// No window is opened, so this works on headless machines. wgpu picks the best
+/// adapter available and falls back to a software one (llvmpipe, lavapipe) on
+/// CPU-only boxes; `WGPU_BACKEND` and `WGPU_ADAPTER_NAME` override the choice.
+/// `size` is in pixels.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn render_dashboard_png(dashboard: Dashboard, size: UVec2) -> Result<Vec<u8>, String> {
+    let pixels = render_dashboard_rgba(dashboard, size)?;
+    encode_png(pixels, size)
+}
+
+/// Render a single plot offscreen and encode it as PNG (native only)
+#[cfg(not(target_arch = "wasm32"))]
+pub fn render_plot_png(plot: &Plot, size: UVec2) -> Result<Vec<u8>, String> {
+    let dashboard = Dashboard {
+        plots: vec![plot.clone()],
+        ..Dashboard::default()
+    };
+    render_dashboard_png(dashboard, size)
+}
+
+/// Render a dashboard offscreen into tightly packed sRGB RGBA8 pixels (native only)
+#[cfg(not(target_arch = "wasm32"))]
+pub fn render_dashboard_rgba(dashboard: Dashboard, size: UVec2) -> Result<Vec<u8>, String> {
+    use std::sync::OnceLock;
+    use std::sync::mpsc;
+
+    // Bevy's renderer can only be initialized once per process, so every export
+    // goes through one offscreen app living on its own thread
+    static EXPORTER: OnceLock<mpsc::Sender<ExportJob>> = OnceLock::new();
+
+    if size.x == 0 || size.y == 0 {
+        return Err(format!("Invalid image size {}x{}", size.x, size.y));
+    }
+
+    let exporter = EXPORTER.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("vidi-export".to_string())
+            .spawn(move || export_worker(receiver))
+            .expect("Failed to spawn export thread");
+        sender
+    });
+
+    // The worker survives failing jobs, so losing it is a bug rather than a
+    // missing adapter
+    let stopped = || "Headless export worker stopped unexpectedly".to_string();
+    let (reply, result) = mpsc::channel();
+    exporter
+        .send(ExportJob {
+            dashboard,
+            size,
+            reply,
+        })
+        .map_err(|_| stopped())?;
+    result.recv().map_err(|_| stopped())?
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct ExportJob {
+    dashboard: Dashboard,
+    size: UVec2,
+    reply: std::sync::mpsc::Sender<Result<Vec<u8>, String>>,
+}
+
+/// Run export jobs on one headless app, keeping the thread alive when setup or
+/// a job panics
+#[cfg(not(target_arch = "wasm32"))]
+fn export_worker(jobs: std::sync::mpsc::Receiver<ExportJob>) {
+    use bevy::app::PluginsState;
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    let setup = catch_unwind(|| {
+        let mut app = headless_app();
+
+        // Normally done by the runner: wait for the renderer, then finish plugin setup
+        while app.plugins_state() == PluginsState::Adding {
+            bevy::tasks::tick_global_task_pools_on_main_thread();
+        }
+        app.finish();
+        app.cleanup();
+        app
+    });
+    let mut app = match setup {
+        Ok(app) => app,
+        Err(panic) => {
+            let error = format!(
+                "Headless renderer is unavailable (no usable GPU adapter: {})",
+                panic_message(&*panic)
+            );
+            for job in jobs {
+                job.reply.send(Err(error.clone())).ok();
+            }
+            return;
+        }
+    };
+
+    for job in jobs {
+        // The next job starts by clearing every tile, so a panic mid-render
+        // leaves nothing behind that later exports would trip over
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            render_offscreen(&mut app, job.dashboard, job.size)
+        }))
+        .unwrap_or_else(|panic| Err(format!("Rendering failed: {}", panic_message(&*panic))));
+        job.reply.send(result).ok();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("panic")
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn headless_app() -> App {
+    use bevy::render::RenderPlugin;
+    use bevy::render::pipelined_rendering::PipelinedRenderingPlugin;
+    use bevy::render::render_resource::{TextureFormat, TextureUsages};
+    use bevy::window::ExitCondition;
+    use bevy::winit::WinitPlugin;
+
+    let mut app = App::new();
+    app.insert_resource(DashboardRes::new(Dashboard::default()))
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                    ..default()
+                })
+                .set(RenderPlugin {
+                    // Draw every frame with all pipelines ready
+                    synchronous_pipeline_compilation: true,
+                    ..default()
+                })
+                .set(ImagePlugin::default_nearest())
+                .disable::<WinitPlugin>()
+                .disable::<PipelinedRenderingPlugin>(),
+        );
+
+    // Resized for each export; cameras keep pointing at the same handle
+    let mut target = Image::new_target_texture(1, 1, TextureFormat::Rgba8UnormSrgb);
+    target.texture_descriptor.usage |= TextureUsages::COPY_SRC;
+    let image = app.world_mut().resource_mut::<Assets<Image>>().add(target);
+
+    // The offscreen target must exist before DashRenderPlugin's startup systems run
+    app.insert_resource(OffscreenTarget {
+        image,
+        size: UVec2::ONE,
+    })
+    .add_plugins(DashRenderPlugin);
+    app
+}
+
+/// Swap `dashboard` into the headless app, render it and read the image back
+#[cfg(not(target_arch = "wasm32"))]
+fn render_offscreen(app: &mut App, dashboard: Dashboard, size: UVec2) -> Result<Vec<u8>, String> {
+    use bevy::render::gpu_readback::{Readback, ReadbackComplete};
+    use bevy::render::render_resource::Extent3d;
+    use bevy::render::renderer::RenderDevice;
+    use std::sync::{Arc, Mutex};
+
+    let max = app
+        .world()
+        .resource::<RenderDevice>()
+        .limits()
+        .max_texture_dimension_2d;
+    if size.x > max || size.y > max {
+        return Err(format!(
+            "Image size {}x{} exceeds the renderer's limit of {max} pixels per side",
+            size.x, size.y
+        ));
+    }
+
+    let world = app.world_mut();
+    let mut target = world.resource_mut::<OffscreenTarget>();
+    target.size = size;
+    let image = target.image.clone();
+    world
+        .resource_mut::<Assets<Image>>()
+        .get_mut(&image)
+        .ok_or_else(|| "Offscreen image is missing".to_string())?
+        .resize(Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        });
+
+    world
+        .run_system_cached(crate::render::systems::clear_tiles)
+        .map_err(|e| format!("Failed to reset tiles: {}", e))?;
+    let bg = dashboard.background;
+    world.insert_resource(ClearColor(Color::srgb(bg.r, bg.g, bg.b)));
+    world.insert_resource(DashboardRes::new(dashboard));
+
+    for _ in 0..WARMUP_FRAMES {
+        app.update();
+    }
+
+    let result: Arc<Mutex<Option<Vec<u8>>>> = Arc::default();
+    let sink = result.clone();
+    let readback = app
+        .world_mut()
+        .spawn(Readback::texture(image))
+        .observe(move |readback: On<ReadbackComplete>| {
+            sink.lock()
+                .unwrap()
+                .get_or_insert_with(|| readback.data.clone());
+        })
+        .id();
+
+    let mut pixels = None;
+    for _ in 0..READBACK_FRAMES {
+        app.update();
+        pixels = result.lock().unwrap().take();
+        if pixels.is_some() {
+            break;
+        }
+    }
+    app.world_mut().despawn(readback);
+
+    pixels
+        .map(|data| unpad_rows(data, size))
+        .ok_or_else(|| "Timed out waiting for the rendered image".to_string())
+}
+
+/// Strip the row padding GPU buffer copies add (rows are aligned to 256 bytes)
+#[cfg(not(target_arch = "wasm32"))]
+fn unpad_rows(data: Vec<u8>, size: UVec2) -> Vec<u8> {
+    let row = size.x as usize * 4;
+    let padded = data.len() / size.y as usize;
+    if padded == row {
+        return data;
+    }
+    data.chunks_exact(padded)
+        .flat_map(|chunk| &chunk[..row])
+        .copied()
+        .collect()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn encode_png(pixels: Vec<u8>, size: UVec2) -> Result<Vec<u8>, String> {
+    let image = image::RgbaImage::from_raw(size.x, size.y, pixels)
+        .ok_or_else(|| "Rendered image has an unexpected size".to_string())?;
+    let mut png = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut png, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    Ok(png.into_inner())
 }
@@ -5,13 +5,67 @@
 
 #![cfg(target_arch = "wasm32")]
 
-use bevy_math::Vec2;
 use parking_lot::Mutex;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::mpsc::{self, Sender};
 use wasm_bindgen::prelude::*;
 
-use crate::core::{Dashboard, Plot};
-use crate::runtime::run_dashboard;
+use crate::core::{Annotation, Dashboard, Plot, Selection, UpdateCommand};
+use crate::render::LiveUpdates;
+use crate::runtime::run_dashboard_live;
+
+thread_local! {
+    /// Selection callbacks by canvas ID, with the ID each was registered
+    /// under. JS functions cannot leave the main thread, so the app's
+    /// listener looks them up here instead.
+    static SELECT_CALLBACKS: RefCell<HashMap<String, Vec<(u32, js_sys::Function)>>> =
+        RefCell::new(HashMap::new());
+    static NEXT_CALLBACK_ID: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Call the JS selection callbacks of a canvas with the selection as an object
+fn call_select_callbacks(canvas_id: &str, selection: &Selection) {
+    let Ok(json) = serde_json::to_string(selection) else {
+        return;
+    };
+    let Ok(value) = js_sys::JSON::parse(&json) else {
+        return;
+    };
+    // Callbacks may register or remove callbacks, so none is borrowed while
+    // JS runs
+    let registered = |id: u32| {
+        SELECT_CALLBACKS.with(|callbacks| {
+            callbacks
+                .borrow()
+                .get(canvas_id)
+                .is_some_and(|list| list.iter().any(|(c, _)| *c == id))
+        })
+    };
+    let callbacks = SELECT_CALLBACKS
+        .with(|callbacks| callbacks.borrow().get(canvas_id).cloned())
+        .unwrap_or_default();
+    for (id, callback) in callbacks {
+        if !registered(id) {
+            continue;
+        }
+        if let Err(e) = callback.call1(&JsValue::NULL, &value) {
+            web_sys::console::error_1(&e);
+        }
+    }
+}
+
+/// Parse dashboard JSON and settle its plot IDs
+///
+/// IDs are fixed here, once, so the local copy and the running app hold the
+/// same ones; the app's own check then finds nothing to change.
+fn parse_dashboard(json: &str) -> Result<Dashboard, JsValue> {
+    let mut dashboard: Dashboard = serde_json::from_str(json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse dashboard JSON: {}", e)))?;
+    dashboard.ensure_unique_ids();
+    Ok(dashboard)
+}
 
 /// JavaScript-accessible dashboard wrapper
 #[wasm_bindgen]
@@ -20,8 +74,8 @@ pub struct JsDashboard {
     dashboard: Arc<Mutex<Dashboard>>,
     /// Canvas ID for rendering
     canvas_id: String,
-    /// Whether the Bevy app has started
-    started: bool,
+    /// Channel into the running Bevy app (set by `start`)
+    updates: Option<Sender<UpdateCommand>>,
 }
 
 #[wasm_bindgen]
@@ -33,13 +87,12 @@ impl JsDashboard {
     /// * `canvas_id` - HTML canvas element ID (without #)
     #[wasm_bindgen(constructor)]
     pub fn new(json: &str, canvas_id: &str) -> Result<JsDashboard, JsValue> {
-        let dashboard: Dashboard = serde_json::from_str(json)
-            .map_err(|e| JsValue::from_str(&format!("Failed to parse dashboard JSON: {}", e)))?;
+        let dashboard = parse_dashboard(json)?;
 
         Ok(JsDashboard {
             dashboard: Arc::new(Mutex::new(dashboard)),
             canvas_id: canvas_id.to_string(),
-            started: false,
+            updates: None,
         })
     }
 
@@ -49,37 +102,31 @@ impl JsDashboard {
     /// methods to modify the dashboard data.
     #[wasm_bindgen]
     pub fn start(&mut self) {
-        if self.started {
+        if self.updates.is_some() {
             web_sys::console::warn_1(&"Dashboard already started".into());
             return;
         }
 
         let dashboard = self.dashboard.lock().clone();
-        self.started = true;
-
-        // Note: In the current architecture, run_dashboard takes ownership
-        // and runs the Bevy app loop. For real-time updates, we'd need to
-        // use Bevy's ECS system to mutate the DashboardRes resource.
-        // This is a simplified implementation.
-        run_dashboard(dashboard, &self.canvas_id);
+        let (sender, receiver) = mpsc::channel();
+        self.updates = Some(sender);
+
+        // On the web the event loop is handed to the browser and this returns
+        // immediately, so later updates reach the app through the channel
+        let updates = LiveUpdates::new(receiver);
+        let canvas_id = self.canvas_id.clone();
+        updates
+            .on_select
+            .add(move |selection| call_select_callbacks(&canvas_id, selection));
+        run_dashboard_live(dashboard, &self.canvas_id, updates);
     }
 
     /// Replace the entire dashboard
-    ///
-    /// Note: This currently requires recreating the Bevy app.
-    /// A future implementation could use Bevy events/resources for hot updates.
     #[wasm_bindgen]
     pub fn set_dashboard(&mut self, json: &str) -> Result<(), JsValue> {
-        let dashboard: Dashboard = serde_json::from_str(json)
-            .map_err(|e| JsValue::from_str(&format!("Failed to parse dashboard JSON: {}", e)))?;
-
-        *self.dashboard.lock() = dashboard;
-
-        // TODO: Send update to running Bevy app via channel/event
-        // For now, log that an update was received
-        web_sys::console::log_1(&"Dashboard updated (requires restart to take effect)".into());
+        let dashboard = parse_dashboard(json)?;
 
-        Ok(())
+        self.apply(UpdateCommand::RefreshAll { dashboard })
     }
 
     /// Append points to a 2D layer
@@ -95,48 +142,12 @@ impl JsDashboard {
         layer_idx: usize,
         points: &[f32],
     ) -> Result<(), JsValue> {
-        if points.len() % 2 != 0 {
-            return Err(JsValue::from_str(
-                "Points array length must be even (x,y pairs)",
-            ));
-        }
-
-        let mut dashboard = self.dashboard.lock();
-
-        // Find the plot and append points
-        for plot in dashboard.plots.iter_mut() {
-            if let Plot::Graph2D(graph) = plot {
-                if graph.id.0 == plot_id {
-                    if let Some(layer) = graph.layers.get_mut(layer_idx) {
-                        for chunk in points.chunks(2) {
-                            layer.xy.push(Vec2::new(chunk[0], chunk[1]));
-                        }
-                        // TODO: Mark tile as dirty in running Bevy app
-                        return Ok(());
-                    } else {
-                        return Err(JsValue::from_str(&format!("Layer {} not found", layer_idx)));
-                    }
-                }
-            }
-        }
-
-        // Also check tabs
-        for tab in dashboard.tabs.iter_mut() {
-            for plot in tab.plots.iter_mut() {
-                if let Plot::Graph2D(graph) = plot {
-                    if graph.id.0 == plot_id {
-                        if let Some(layer) = graph.layers.get_mut(layer_idx) {
-                            for chunk in points.chunks(2) {
-                                layer.xy.push(Vec2::new(chunk[0], chunk[1]));
-                            }
-                            return Ok(());
-                        }
-                    }
-                }
-            }
-        }
-
-        Err(JsValue::from_str(&format!("Plot {} not found", plot_id)))
+        let points = pairs(points)?;
+        self.apply(UpdateCommand::AppendPoints2D {
+            plot_id,
+            layer_idx,
+            points,
+        })
     }
 
     /// Replace all points in a 2D layer
@@ -147,84 +158,72 @@ impl JsDashboard {
         layer_idx: usize,
         points: &[f32],
     ) -> Result<(), JsValue> {
-        if points.len() % 2 != 0 {
-            return Err(JsValue::from_str(
-                "Points array length must be even (x,y pairs)",
-            ));
-        }
+        let points = pairs(points)?;
+        self.apply(UpdateCommand::ReplaceTrace2D {
+            plot_id,
+            layer_idx,
+            points,
+        })
+    }
 
-        let mut dashboard = self.dashboard.lock();
-
-        // Convert flat array to Vec2
-        let new_points: Vec<Vec2> = points
-            .chunks(2)
-            .map(|chunk| Vec2::new(chunk[0], chunk[1]))
-            .collect();
-
-        // Find and update the layer
-        for plot in dashboard.plots.iter_mut() {
-            if let Plot::Graph2D(graph) = plot {
-                if graph.id.0 == plot_id {
-                    if let Some(layer) = graph.layers.get_mut(layer_idx) {
-                        layer.xy = new_points;
-                        return Ok(());
-                    }
-                }
-            }
-        }
+    /// Add an annotation (JSON) to a 2D or candlestick plot
+    #[wasm_bindgen]
+    pub fn add_annotation(&mut self, plot_id: u64, json: &str) -> Result<(), JsValue> {
+        let annotation: Annotation = serde_json::from_str(json)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse annotation JSON: {}", e)))?;
 
-        for tab in dashboard.tabs.iter_mut() {
-            for plot in tab.plots.iter_mut() {
-                if let Plot::Graph2D(graph) = plot {
-                    if graph.id.0 == plot_id {
-                        if let Some(layer) = graph.layers.get_mut(layer_idx) {
-                            layer.xy = new_points;
-                            return Ok(());
-                        }
-                    }
-                }
-            }
-        }
+        self.apply(UpdateCommand::AddAnnotation {
+            plot_id,
+            annotation,
+        })
+    }
 
-        Err(JsValue::from_str(&format!("Plot {} not found", plot_id)))
+    /// Register a callback for selections made on the canvas
+    ///
+    /// The callback receives `{ plot_id, layers: [{ layer, indices }] }`;
+    /// `layers` is empty when the selection is cleared. Returns an ID for
+    /// `off_select`.
+    #[wasm_bindgen]
+    pub fn on_select(&self, callback: js_sys::Function) -> u32 {
+        let id = NEXT_CALLBACK_ID.with(|next| next.replace(next.get() + 1));
+        SELECT_CALLBACKS.with(|callbacks| {
+            callbacks
+                .borrow_mut()
+                .entry(self.canvas_id.clone())
+                .or_default()
+                .push((id, callback));
+        });
+        id
     }
 
-    /// Update an entire plot by ID
+    /// Remove a callback registered with `on_select`; returns whether it was
+    /// registered
+    #[wasm_bindgen]
+    pub fn off_select(&self, id: u32) -> bool {
+        SELECT_CALLBACKS.with(|callbacks| {
+            let mut callbacks = callbacks.borrow_mut();
+            let Some(list) = callbacks.get_mut(&self.canvas_id) else {
+                return false;
+            };
+            let before = list.len();
+            list.retain(|(c, _)| *c != id);
+            list.len() != before
+        })
+    }
+
+    /// Update an entire plot by ID (any plot type)
     #[wasm_bindgen]
     pub fn update_plot(&mut self, plot_id: u64, json: &str) -> Result<(), JsValue> {
-        let new_plot: Plot = serde_json::from_str(json)
+        let plot: Plot = serde_json::from_str(json)
             .map_err(|e| JsValue::from_str(&format!("Failed to parse plot JSON: {}", e)))?;
 
-        let mut dashboard = self.dashboard.lock();
-
-        // Find and replace the plot
-        for plot in dashboard.plots.iter_mut() {
-            let id = match plot {
-                Plot::Graph2D(g) => g.id.0,
-                Plot::Graph3D(g) => g.id.0,
-                _ => continue,
-            };
-            if id == plot_id {
-                *plot = new_plot;
-                return Ok(());
-            }
-        }
-
-        for tab in dashboard.tabs.iter_mut() {
-            for plot in tab.plots.iter_mut() {
-                let id = match plot {
-                    Plot::Graph2D(g) => g.id.0,
-                    Plot::Graph3D(g) => g.id.0,
-                    _ => continue,
-                };
-                if id == plot_id {
-                    *plot = new_plot;
-                    return Ok(());
-                }
-            }
-        }
+        self.apply(UpdateCommand::UpdatePlot { plot_id, plot })
+    }
 
-        Err(JsValue::from_str(&format!("Plot {} not found", plot_id)))
+    /// Look up a plot's ID by its user-chosen key
+    #[wasm_bindgen]
+    pub fn plot_id(&self, key: &str) -> Option<u64> {
+        self.dashboard.lock().plot_id_by_key(key)
     }
 
     /// Get the current dashboard as JSON
@@ -244,6 +243,33 @@ impl JsDashboard {
     /// Check if the dashboard has been started
     #[wasm_bindgen(getter)]
     pub fn is_started(&self) -> bool {
-        self.started
+        self.updates.is_some()
+    }
+}
+
+impl JsDashboard {
+    /// Apply an update to the local copy, then forward it to the running app
+    fn apply(&mut self, cmd: UpdateCommand) -> Result<(), JsValue> {
+        self.dashboard
+            .lock()
+            .apply_update(cmd.clone())
+            .map_err(|e| JsValue::from_str(&e))?;
+
+        if let Some(sender) = &self.updates {
+            sender
+                .send(cmd)
+                .map_err(|_| JsValue::from_str("Dashboard app is no longer running"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Convert a flat [x1, y1, x2, y2, ...] array into points
+fn pairs(points: &[f32]) -> Result<Vec<[f32; 2]>, JsValue> {
+    if points.len() % 2 != 0 {
+        return Err(JsValue::from_str(
+            "Points array length must be even (x,y pairs)",
+        ));
     }
+    Ok(points.chunks(2).map(|c| [c[0], c[1]]).collect())
 }
@@ -0,0 +1,266 @@
+//! Golden-image tests for headless PNG export
+//!
+//! Each plot type is rendered offscreen and compared with a reference image in
+//! `tests/golden/`. Runs on CPU-only machines through a software adapter
+//! (Mesa llvmpipe). A renderer that fails to start fails the tests; set
+//! `VIDI_SKIP_GOLDEN=1` to skip them on machines without any usable adapter.
+//!
+//! After an intended visual change, regenerate the references with
+//! `VIDI_BLESS=1 cargo test --test golden` and review the new images.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use bevy_math::{UVec2, Vec2, Vec3};
+use std::path::PathBuf;
+use vidi_charts::prelude::*;
+
+const SIZE: UVec2 = UVec2::new(320, 240);
+
+/// Largest per-channel difference (0-255) for two pixels to count as equal
+const CHANNEL_TOLERANCE: u8 = 24;
+
+/// Share of pixels allowed to differ, for rasterizer and driver noise
+const MAX_DIFF_RATIO: f64 = 0.01;
+
+fn style(i: usize) -> Style {
+    Style::default().color(Color::palette(i))
+}
+
+fn only_plot(builder: DashBuilder) -> Plot {
+    builder.build().plots.remove(0)
+}
+
+fn wave(n: usize, phase: f32) -> Vec<Vec2> {
+    (0..n)
+        .map(|i| {
+            let x = i as f32 * 0.25;
+            Vec2::new(x, (x + phase).sin())
+        })
+        .collect()
+}
+
+/// Fixed, evenly spread samples (no RNG, so renders are repeatable)
+fn samples(n: usize) -> Vec<f32> {
+    (0..n)
+        .map(|i| {
+            let t = (i as f32 + 0.5) / n as f32;
+            // Inverse of a logistic CDF: bell-shaped but deterministic
+            (t / (1.0 - t)).ln()
+        })
+        .collect()
+}
+
+/// Render `plot` and compare it with `tests/golden/<name>.png`
+fn check(name: &str, plot: Plot) {
+    if std::env::var_os("VIDI_SKIP_GOLDEN").is_some() {
+        eprintln!("skipping golden test {name}: VIDI_SKIP_GOLDEN is set");
+        return;
+    }
+    let png =
+        render_plot_png(&plot, SIZE).unwrap_or_else(|e| panic!("rendering {name} failed: {e}"));
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.png"));
+    if std::env::var_os("VIDI_BLESS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &png).unwrap();
+        return;
+    }
+
+    let reference = image::open(&path)
+        .unwrap_or_else(|e| {
+            panic!(
+                "missing reference {} ({e}); run with VIDI_BLESS=1 to create it",
+                path.display()
+            )
+        })
+        .to_rgba8();
+    let actual = image::load_from_memory(&png).unwrap().to_rgba8();
+    assert_eq!(
+        actual.dimensions(),
+        reference.dimensions(),
+        "{name}: image size changed"
+    );
+
+    let differing = actual
+        .pixels()
+        .zip(reference.pixels())
+        .filter(|(a, b)| {
+            a.0.iter()
+                .zip(b.0.iter())
+                .any(|(a, b)| a.abs_diff(*b) > CHANNEL_TOLERANCE)
+        })
+        .count();
+    let ratio = differing as f64 / actual.pixels().len() as f64;
+    if ratio > MAX_DIFF_RATIO {
+        let out = std::env::temp_dir().join(format!("vidi-golden-{name}.png"));
+        std::fs::write(&out, &png).ok();
+        panic!(
+            "{name}: {:.2}% of pixels differ from {} (allowed {:.2}%); got {}",
+            ratio * 100.0,
+            path.display(),
+            MAX_DIFF_RATIO * 100.0,
+            out.display()
+        );
+    }
+}
+
+#[test]
+fn line_2d() {
+    check(
+        "line_2d",
+        only_plot(dash().add_2d(|p| {
+            p.line(wave(60, 0.0), style(0))
+                .line(wave(60, 1.5), style(1))
+                .title("Line")
+        })),
+    );
+}
+
+#[test]
+fn scatter_2d() {
+    check(
+        "scatter_2d",
+        only_plot(dash().add_2d(|p| p.scatter(wave(40, 0.5), style(2)).title("Scatter"))),
+    );
+}
+
+#[test]
+fn points_3d() {
+    let xyz = (0..200)
+        .map(|i| {
+            let t = i as f32 * 0.1;
+            Vec3::new(t.cos(), t.sin(), t * 0.1)
+        })
+        .collect();
+    check(
+        "points_3d",
+        only_plot(dash().add_3d(|p| p.points(xyz, style(0)).title("Helix"))),
+    );
+}
+
+#[test]
+fn surface_3d() {
+    let n = 20;
+    let xyz = (0..n * n)
+        .map(|i| {
+            let (x, y) = ((i % n) as f32 / n as f32, (i / n) as f32 / n as f32);
+            Vec3::new(x, y, (x * 6.0).sin() * (y * 6.0).cos())
+        })
+        .collect();
+    check(
+        "surface_3d",
+        only_plot(dash().add_3d(|p| p.surface(xyz, n, n, style(4)).title("Surface"))),
+    );
+}
+
+#[test]
+fn histogram() {
+    check(
+        "histogram",
+        only_plot(dash().add_distribution(|d| d.histogram(samples(500)).bins(20).style(style(0)))),
+    );
+}
+
+#[test]
+fn pdf() {
+    check(
+        "pdf",
+        only_plot(dash().add_distribution(|d| d.pdf(samples(500)).style(style(1)))),
+    );
+}
+
+#[test]
+fn boxplot() {
+    check(
+        "boxplot",
+        only_plot(dash().add_distribution(|d| {
+            d.boxplot(vec![
+                ("a", samples(100)),
+                ("b", samples(60).iter().map(|v| v * 2.0 + 1.0).collect()),
+            ])
+            .style(style(2))
+        })),
+    );
+}
+
+#[test]
+fn ecdf() {
+    check(
+        "ecdf",
+        only_plot(dash().add_distribution(|d| d.ecdf(samples(200)).style(style(3)))),
+    );
+}
+
+#[test]
+fn field() {
+    let (nx, ny) = (16, 12);
+    let values = (0..nx * ny)
+        .map(|i| ((i % nx) as f32 * 0.4).sin() + ((i / nx) as f32 * 0.5).cos())
+        .collect();
+    check(
+        "field",
+        only_plot(dash().add_field(UVec2::new(nx, ny), values, -2.0, 2.0)),
+    );
+}
+
+#[test]
+fn pie() {
+    check(
+        "pie",
+        only_plot(dash().add_radial(|r| r.pie(vec![("a", 3.0), ("b", 2.0), ("c", 1.0)]))),
+    );
+}
+
+#[test]
+fn radar() {
+    check(
+        "radar",
+        only_plot(dash().add_radial(|r| {
+            r.radar(
+                vec!["speed", "power", "range", "cost", "size"],
+                vec![0.9, 0.6, 0.7, 0.3, 0.5],
+            )
+            .style(style(5))
+        })),
+    );
+}
+
+#[test]
+fn candlestick() {
+    let candles = (0..30)
+        .map(|i| {
+            let t = i as f32;
+            let open = 100.0 + (t * 0.4).sin() * 5.0;
+            let close = open + (t * 0.9).cos() * 3.0;
+            (t, open, open.max(close) + 1.5, open.min(close) - 1.5, close)
+        })
+        .collect();
+    check(
+        "candlestick",
+        only_plot(dash().add_candlestick(|c| c.data(candles).title("OHLC"))),
+    );
+}
+
+#[test]
+fn heatmap() {
+    let rows = (0..6)
+        .map(|r| (0..8).map(|c| ((r * 8 + c) as f32 * 0.37).sin()).collect())
+        .collect();
+    check(
+        "heatmap",
+        only_plot(dash().add_heatmap(|h| h.from_2d(rows).vmin(-1.0).vmax(1.0))),
+    );
+}
+
+#[test]
+fn oversized_export_leaves_renderer_usable() {
+    if std::env::var_os("VIDI_SKIP_GOLDEN").is_some() {
+        return;
+    }
+    let plot = only_plot(dash().add_2d(|p| p.line(wave(10, 0.0), style(0))));
+    let error = render_plot_png(&plot, UVec2::new(1 << 20, 16)).unwrap_err();
+    assert!(error.contains("exceeds"), "{error}");
+    render_plot_png(&plot, SIZE).expect("renderer still works after a rejected job");
+}
@@ -1,29 +1,43 @@
 //! Build script for dashboard-template
 //!
-//! Copies the dashboard.json file to OUT_DIR for inclusion in the binary.
+//! Copies the dashboard JSON (from `VIDI_DASHBOARD_JSON` or the crate's
+//! dashboard.json) to OUT_DIR for inclusion in the binary.
 
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 fn main() {
     // Get the OUT_DIR where we'll copy the dashboard JSON
     let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
     let dest_path = Path::new(&out_dir).join("dashboard.json");
 
-    // Look for dashboard.json in the crate root
-    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
-    let src_path = Path::new(&manifest_dir).join("dashboard.json");
+    // vidi-server points each compile job at its own JSON file; otherwise
+    // look for dashboard.json in the crate root
+    println!("cargo:rerun-if-env-changed=VIDI_DASHBOARD_JSON");
+    let src_path = match env::var("VIDI_DASHBOARD_JSON") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => {
+            let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+            Path::new(&manifest_dir).join("dashboard.json")
+        }
+    };
 
     if src_path.exists() {
         fs::copy(&src_path, &dest_path).expect("Failed to copy dashboard.json");
         println!("cargo:rerun-if-changed={}", src_path.display());
     } else {
-        // Create a default empty dashboard if no JSON provided
+        // Create a default empty dashboard file if no JSON provided
         let default_dashboard = r#"{
-            "plots": [],
-            "tabs": [],
-            "layout": { "cols": 1, "rows": 1 }
+            "format": "vidi-dashboard",
+            "version": 1,
+            "dashboard": {
+                "background": { "r": 0.05, "g": 0.05, "b": 0.09, "a": 1.0 },
+                "plots": [],
+                "columns": null,
+                "tabs": [],
+                "active_tab": 0
+            }
         }"#;
         fs::write(&dest_path, default_dashboard).expect("Failed to write default dashboard.json");
     }
@@ -20,9 +20,9 @@ pub fn start() {
     // Set up panic hook for better error messages in console
     console_error_panic_hook::set_once();
 
-    // Parse the baked-in dashboard
-    let dashboard: Dashboard =
-        serde_json::from_str(DASHBOARD_JSON).expect("Failed to parse baked-in dashboard JSON");
+    // Parse the baked-in dashboard (bare JSON or a versioned dashboard file)
+    let dashboard = Dashboard::from_file_bytes(DASHBOARD_JSON.as_bytes())
+        .expect("Failed to parse baked-in dashboard JSON");
 
     // Run the dashboard with the default canvas ID
     run_dashboard(dashboard, "dashboard-canvas");
@@ -0,0 +1,4 @@
+-- Per-dashboard WASM compilation status (baked mode)
+
+ALTER TABLE dashboards ADD COLUMN wasm_status TEXT NOT NULL DEFAULT 'pending';
+ALTER TABLE dashboards ADD COLUMN wasm_error TEXT;
@@ -0,0 +1,24 @@
+-- Dashboard version history: one snapshot per create, replace and restore
+
+CREATE TABLE IF NOT EXISTS dashboard_versions (
+    dashboard_id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    author TEXT,
+    created_at TEXT NOT NULL,
+    plot_count INTEGER NOT NULL DEFAULT 0,
+    dashboard_json TEXT NOT NULL,
+    PRIMARY KEY (dashboard_id, version)
+);
+
+-- Existing dashboards start their history at version 1
+INSERT INTO dashboard_versions (
+    dashboard_id, version, author, created_at, plot_count, dashboard_json
+)
+SELECT
+    id, 1, user, updated_at,
+    COALESCE(json_array_length(dashboard_json, '$.plots'), 0)
+        + (SELECT COALESCE(SUM(json_array_length(value, '$.plots')), 0)
+           FROM json_each(dashboard_json, '$.tabs')),
+    dashboard_json
+FROM dashboards
+WHERE id NOT IN (SELECT dashboard_id FROM dashboard_versions);
@@ -0,0 +1,17 @@
+-- Full-text search index over dashboard names, tags, plot titles,
+-- descriptions, axis labels, tab names and plot types.
+-- Rows are written by the server whenever a dashboard is stored; dashboards
+-- stored before this migration are indexed when the server starts.
+
+CREATE VIRTUAL TABLE IF NOT EXISTS dashboard_search USING fts5(
+    dashboard_id UNINDEXED,
+    xp_name,
+    user,
+    tags,
+    titles,
+    descriptions,
+    labels,
+    tabs,
+    plot_types,
+    prefix = '2 3'
+);
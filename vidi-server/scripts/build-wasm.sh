@@ -1,6 +1,10 @@
 #!/bin/bash
 # Build WASM artifacts for vidi-server
 #
+# Produces the prebuilt viewer bundle (wasm/vidi.js + wasm/vidi_bg.wasm) that
+# loads dashboard JSON at runtime. This is all the server needs in its default
+# `--wasm-mode viewer`.
+#
 # Prerequisites:
 #   rustup target add wasm32-unknown-unknown
 #   cargo install wasm-bindgen-cli
@@ -24,9 +28,10 @@ mkdir -p "$WASM_OUT"
 
 # Generate JS bindings with wasm-bindgen
 echo "Generating JS bindings..."
-wasm-bindgen target/wasm32-unknown-unknown/release/vidi.wasm \
+wasm-bindgen target/wasm32-unknown-unknown/release/vidi_charts.wasm \
   --target web \
   --out-dir "$WASM_OUT" \
+  --out-name vidi \
   --no-typescript
 
 # Optimize WASM if wasm-opt is available (optional, may fail with newer WASM features)
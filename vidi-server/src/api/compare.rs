@@ -0,0 +1,388 @@
+//! Run comparison handlers
+
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{
+        Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    response::IntoResponse,
+    routing::get,
+};
+use futures::{SinkExt, StreamExt};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+use vidi::prelude::{Dashboard, Plot};
+
+use crate::AppState;
+use crate::error::{Result, ServerError};
+use crate::models::{
+    ClientMessage, CompareQuery, Comparison, DashboardRecord, ListQuery, RunInfo, ServerMessage,
+    UpdateCommand,
+};
+use crate::storage::DashboardStore;
+
+/// Most runs a single comparison may hold
+const MAX_RUNS: usize = 32;
+
+/// Build the comparison API router
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/compare", get(compare))
+}
+
+/// GET /api/v1/compare?ids=a,b or ?xp_name=x - Build a comparison dashboard
+async fn compare(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CompareQuery>,
+) -> Result<Json<Comparison>> {
+    let records = load_runs(&state, &query).await?;
+    Ok(Json(build(&records)))
+}
+
+/// Fetch the dashboards selected by a comparison query, oldest first for
+/// `xp_name` and in the given order for `ids`
+async fn load_runs(state: &AppState, query: &CompareQuery) -> Result<Vec<DashboardRecord>> {
+    let records = if let Some(ids) = query.ids.as_deref().filter(|s| !s.trim().is_empty()) {
+        let mut records = Vec::new();
+        for id in ids.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let id = Uuid::parse_str(id)
+                .map_err(|_| ServerError::BadRequest(format!("Invalid dashboard ID '{}'", id)))?;
+            if records.iter().any(|r: &DashboardRecord| r.meta.id == id) {
+                continue;
+            }
+            let record = state
+                .store
+                .get(id)
+                .await?
+                .ok_or_else(|| ServerError::NotFound(id.to_string()))?;
+            records.push(record);
+        }
+        records
+    } else if let Some(xp_name) = &query.xp_name {
+        let summaries = state
+            .store
+            .list(&ListQuery {
+                xp_name: Some(xp_name.clone()),
+                sort: "created_at".into(),
+                order: "asc".into(),
+                limit: MAX_RUNS as u32 + 1,
+                ..ListQuery::default()
+            })
+            .await?;
+        let mut records = Vec::new();
+        for summary in summaries {
+            if let Some(record) = state.store.get(summary.id).await? {
+                records.push(record);
+            }
+        }
+        records
+    } else {
+        return Err(ServerError::BadRequest(
+            "Pass either ids or xp_name".to_string(),
+        ));
+    };
+
+    if records.is_empty() {
+        return Err(ServerError::NotFound(
+            "No dashboards to compare".to_string(),
+        ));
+    }
+    if records.len() > MAX_RUNS {
+        return Err(ServerError::BadRequest(format!(
+            "Too many runs to compare (at most {})",
+            MAX_RUNS
+        )));
+    }
+    Ok(records)
+}
+
+fn build(records: &[DashboardRecord]) -> Comparison {
+    let metas: Vec<_> = records.iter().map(|r| &r.meta).collect();
+    let dashboards: Vec<Dashboard> = records.iter().map(|r| r.dashboard.clone()).collect();
+    Comparison::build(RunInfo::name_all(&metas), &dashboards)
+}
+
+/// WebSocket upgrade handler for live comparisons
+pub async fn ws_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CompareQuery>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_socket(state, query, socket))
+}
+
+/// Stream updates of every compared run, rewritten to the comparison's plots
+async fn handle_socket(state: Arc<AppState>, query: CompareQuery, socket: WebSocket) {
+    let records = match load_runs(&state, &query).await {
+        Ok(records) => records,
+        Err(e) => {
+            warn!("Comparison WebSocket rejected: {}", e);
+            return;
+        }
+    };
+    let run_ids: Vec<Uuid> = records.iter().map(|r| r.meta.id).collect();
+    info!("Comparison WebSocket connected for {} runs", run_ids.len());
+
+    // Fan the runs' broadcast channels into one queue tagged with the run
+    // index; `None` means updates of that run were missed
+    let (tx, mut rx) = mpsc::channel::<(usize, Option<ServerMessage>)>(256);
+    let mut run_tasks = Vec::new();
+    for (run, &id) in run_ids.iter().enumerate() {
+        let _ = state.store.touch(id).await;
+        let mut updates = state.broadcast_hub.subscribe(id);
+        let tx = tx.clone();
+        run_tasks.push(tokio::spawn(async move {
+            loop {
+                match updates.recv().await {
+                    Ok(msg) => {
+                        if tx.send((run, Some(msg))).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("Comparison missed {} updates of run {}, resyncing", n, id);
+                        if tx.send((run, None)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }));
+    }
+    drop(tx);
+
+    let (mut sender, mut receiver) = socket.split();
+    let mut records = records;
+    let mut sources: Vec<Dashboard> = records.iter().map(|r| r.dashboard.clone()).collect();
+    let mut comparison = build(&records);
+    let mut seq = 0;
+
+    let connected = ServerMessage::Connected {
+        seq,
+        dashboard_id: run_ids
+            .iter()
+            .map(Uuid::to_string)
+            .collect::<Vec<_>>()
+            .join(","),
+    };
+    let mut open = send(&mut sender, &connected).await.is_ok();
+
+    while open {
+        let reply = tokio::select! {
+            update = rx.recv() => {
+                let Some((run, msg)) = update else { break };
+                seq += 1;
+                match msg {
+                    Some(msg) => match apply(&mut sources, &records, &mut comparison, run, msg, seq) {
+                        Some(reply) => reply,
+                        None => continue,
+                    },
+                    // Missed updates cannot be replayed: start over from the
+                    // stored runs, as on connect
+                    None => {
+                        reload(&state, &mut records, &mut sources).await;
+                        rebuild(&sources, &records, &mut comparison, seq)
+                    }
+                }
+            }
+            msg = receiver.next() => match msg {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(ClientMessage::Sync { .. }) | Ok(ClientMessage::GetState) => {
+                        seq += 1;
+                        ServerMessage::RefreshAll {
+                            seq,
+                            dashboard: comparison.dashboard.clone(),
+                        }
+                    }
+                    Ok(ClientMessage::Ack { .. }) => continue,
+                    Err(e) => {
+                        warn!("Invalid client message: {}", e);
+                        continue;
+                    }
+                },
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Err(e)) => {
+                    error!("WebSocket error: {}", e);
+                    break;
+                }
+                Some(Ok(_)) => continue,
+            },
+        };
+        open = send(&mut sender, &reply).await.is_ok();
+    }
+
+    // Cleanup
+    for task in run_tasks {
+        task.abort();
+    }
+    for id in run_ids {
+        state.broadcast_hub.unsubscribe(id);
+    }
+    info!("Comparison WebSocket disconnected");
+}
+
+/// Apply a run's update to its source copy and translate it for the
+/// comparison; structural changes rebuild the whole comparison
+fn apply(
+    sources: &mut [Dashboard],
+    records: &[DashboardRecord],
+    comparison: &mut Comparison,
+    run: usize,
+    msg: ServerMessage,
+    seq: u64,
+) -> Option<ServerMessage> {
+    let source = &mut sources[run];
+    match msg {
+        ServerMessage::AppendPoints {
+            plot_id,
+            layer_idx,
+            points,
+            ..
+        } => {
+            let cmd = points_update(source, plot_id, layer_idx, &points, true)?;
+            source.apply_update(cmd).ok()?;
+            let (plot_id, layer_idx) = comparison.target(run, plot_id, layer_idx)?;
+            let cmd = points_update(&comparison.dashboard, plot_id, layer_idx, &points, true)?;
+            comparison.dashboard.apply_update(cmd).ok()?;
+            Some(ServerMessage::AppendPoints {
+                seq,
+                plot_id,
+                layer_idx,
+                points,
+            })
+        }
+        ServerMessage::ReplaceTrace {
+            plot_id,
+            layer_idx,
+            points,
+            ..
+        } => {
+            let cmd = points_update(source, plot_id, layer_idx, &points, false)?;
+            source.apply_update(cmd).ok()?;
+            let (plot_id, layer_idx) = comparison.target(run, plot_id, layer_idx)?;
+            let cmd = points_update(&comparison.dashboard, plot_id, layer_idx, &points, false)?;
+            comparison.dashboard.apply_update(cmd).ok()?;
+            Some(ServerMessage::ReplaceTrace {
+                seq,
+                plot_id,
+                layer_idx,
+                points,
+            })
+        }
+        ServerMessage::AddAnnotation {
+            plot_id,
+            annotation,
+            ..
+        } => {
+            source
+                .apply_update(UpdateCommand::AddAnnotation {
+                    plot_id,
+                    annotation,
+                })
+                .ok()?;
+            Some(rebuild(sources, records, comparison, seq))
+        }
+        ServerMessage::UpdatePlot { plot_id, plot, .. } => {
+            source
+                .apply_update(UpdateCommand::UpdatePlot { plot_id, plot })
+                .ok()?;
+            Some(rebuild(sources, records, comparison, seq))
+        }
+        ServerMessage::RefreshAll { dashboard, .. } => {
+            source
+                .apply_update(UpdateCommand::RefreshAll { dashboard })
+                .ok()?;
+            Some(rebuild(sources, records, comparison, seq))
+        }
+        ServerMessage::WasmStatus { .. }
+        | ServerMessage::Error { .. }
+        | ServerMessage::Connected { .. } => None,
+    }
+}
+
+/// Reread every compared run from storage, keeping the copy held so far for
+/// runs that can no longer be read
+async fn reload(state: &AppState, records: &mut [DashboardRecord], sources: &mut [Dashboard]) {
+    for (record, source) in records.iter_mut().zip(sources) {
+        match state.store.get(record.meta.id).await {
+            Ok(Some(fresh)) => {
+                *source = fresh.dashboard.clone();
+                *record = fresh;
+            }
+            Ok(None) => debug!(
+                "Compared run {} is gone, keeping its last state",
+                record.meta.id
+            ),
+            Err(e) => warn!("Failed to reload compared run {}: {}", record.meta.id, e),
+        }
+    }
+}
+
+fn rebuild(
+    sources: &[Dashboard],
+    records: &[DashboardRecord],
+    comparison: &mut Comparison,
+    seq: u64,
+) -> ServerMessage {
+    let metas: Vec<_> = records.iter().map(|r| &r.meta).collect();
+    *comparison = Comparison::build(RunInfo::name_all(&metas), sources);
+    ServerMessage::RefreshAll {
+        seq,
+        dashboard: comparison.dashboard.clone(),
+    }
+}
+
+/// Turn flattened points back into an update command, 2D or 3D by the plot
+fn points_update(
+    dashboard: &Dashboard,
+    plot_id: u64,
+    layer_idx: usize,
+    points: &[f32],
+    append: bool,
+) -> Option<UpdateCommand> {
+    let plot = dashboard
+        .plots
+        .iter()
+        .chain(dashboard.tabs.iter().flat_map(|t| &t.plots))
+        .find(|p| p.id().0 == plot_id)?;
+    let cmd = match (plot, append) {
+        (Plot::Graph2D(_), true) => UpdateCommand::AppendPoints2D {
+            plot_id,
+            layer_idx,
+            points: points.chunks_exact(2).map(|p| [p[0], p[1]]).collect(),
+        },
+        (Plot::Graph2D(_), false) => UpdateCommand::ReplaceTrace2D {
+            plot_id,
+            layer_idx,
+            points: points.chunks_exact(2).map(|p| [p[0], p[1]]).collect(),
+        },
+        (Plot::Graph3D(_), true) => UpdateCommand::AppendPoints3D {
+            plot_id,
+            layer_idx,
+            points: points.chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect(),
+        },
+        (Plot::Graph3D(_), false) => UpdateCommand::ReplaceTrace3D {
+            plot_id,
+            layer_idx,
+            points: points.chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect(),
+        },
+        _ => return None,
+    };
+    Some(cmd)
+}
+
+async fn send(
+    sender: &mut futures::stream::SplitSink<WebSocket, Message>,
+    msg: &ServerMessage,
+) -> std::result::Result<(), ()> {
+    let json =
+        serde_json::to_string(msg).map_err(|e| error!("Failed to serialize message: {}", e))?;
+    sender
+        .send(Message::Text(json.into()))
+        .await
+        .map_err(|_| ())
+}
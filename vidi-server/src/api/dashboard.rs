@@ -5,18 +5,21 @@ use std::sync::Arc;
 use axum::{
     Json, Router,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::IntoResponse,
     routing::{delete, get, patch, post, put},
 };
-use tracing::{error, info};
+use glam::UVec2;
 use uuid::Uuid;
 
 use crate::AppState;
+use crate::config::WasmMode;
 use crate::error::{Result, ServerError};
+use crate::lifecycle;
 use crate::models::{
-    CreateDashboardRequest, DashboardMeta, DashboardRecord, DashboardSummary, ListQuery,
-    UpdateCommand, UpdateMetaRequest, WasmStatus,
+    CreateDashboardRequest, DashboardDiff, DashboardMeta, DashboardRecord, DashboardSummary,
+    DashboardVersion, DiffQuery, ExportQuery, ListQuery, RestoreQuery, SearchFilter, SearchQuery,
+    SearchResults, UpdateCommand, UpdateMetaRequest, VersionSummary, WasmStatus,
 };
 use crate::storage::DashboardStore;
 
@@ -25,6 +28,7 @@ pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .route("/dashboards", post(create_dashboard))
         .route("/dashboards", get(list_dashboards))
+        .route("/dashboards/search", get(search_dashboards))
         .route("/dashboards/{id}", get(get_dashboard))
         .route("/dashboards/{id}", put(replace_dashboard))
         .route("/dashboards/{id}", patch(update_meta))
@@ -33,6 +37,16 @@ pub fn router() -> Router<Arc<AppState>> {
         .route("/dashboards/{id}/update", post(push_update))
         .route("/dashboards/{id}/wasm-status", get(get_wasm_status))
         .route("/dashboards/{id}/recompile", post(trigger_recompile))
+        .route("/dashboards/{id}/export.svg", get(export_svg))
+        .route("/dashboards/{id}/export.pdf", get(export_pdf))
+        .route("/dashboards/{id}/export.html", get(export_html))
+        .route("/dashboards/{id}/versions", get(list_versions))
+        .route("/dashboards/{id}/versions/{version}", get(get_version))
+        .route(
+            "/dashboards/{id}/versions/{version}/restore",
+            post(restore_version),
+        )
+        .route("/dashboards/{id}/diff", get(diff_versions))
 }
 
 /// POST /api/v1/dashboards - Create a new dashboard
@@ -40,6 +54,7 @@ async fn create_dashboard(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreateDashboardRequest>,
 ) -> Result<impl IntoResponse> {
+    let author = req.author.or_else(|| req.user.clone());
     let meta = DashboardMeta::new()
         .with_xp_name(req.xp_name.unwrap_or_default())
         .with_user(req.user.unwrap_or_default())
@@ -52,77 +67,29 @@ async fn create_dashboard(
     };
 
     let record = DashboardRecord::new(req.dashboard).with_meta(meta);
-    let created = state.store.create(record).await?;
+    let created = state.store.create(record, author).await?;
 
-    // Trigger async WASM compilation
-    let id = created.meta.id;
-    spawn_wasm_compilation(state, id);
+    // Queue WASM compilation
+    queue_wasm_compilation(&state, created.meta.id).await?;
 
     Ok((StatusCode::CREATED, Json(created)))
 }
 
-/// Spawn async WASM compilation task
-fn spawn_wasm_compilation(state: Arc<AppState>, id: Uuid) {
-    tokio::spawn(async move {
-        info!("Starting WASM compilation for dashboard {}", id);
-
-        // Update status to compiling
-        if let Err(e) = state
-            .store
-            .update_wasm_status(id, WasmStatus::Compiling, None)
-            .await
-        {
-            error!("Failed to update wasm_status to compiling: {}", e);
-            return;
-        }
+/// Queue baked WASM compilation (no-op unless the server runs in baked mode)
+///
+/// Status changes are persisted and pushed to clients by `lifecycle::compile_status_task`.
+async fn queue_wasm_compilation(state: &AppState, id: Uuid) -> Result<()> {
+    if state.config.wasm_mode != WasmMode::Baked {
+        return Ok(());
+    }
 
-        // Get dashboard JSON for compilation
-        let dashboard_json = match state.store.get_dashboard_json(id).await {
-            Ok(Some(json)) => json,
-            Ok(None) => {
-                error!("Dashboard {} not found for WASM compilation", id);
-                let _ = state
-                    .store
-                    .update_wasm_status(id, WasmStatus::Failed, Some("Dashboard not found".into()))
-                    .await;
-                return;
-            }
-            Err(e) => {
-                error!("Failed to get dashboard JSON for {}: {}", id, e);
-                let _ = state
-                    .store
-                    .update_wasm_status(
-                        id,
-                        WasmStatus::Failed,
-                        Some(format!("Failed to get dashboard: {}", e)),
-                    )
-                    .await;
-                return;
-            }
-        };
+    let dashboard_json = state
+        .store
+        .get_dashboard_json(id)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(id.to_string()))?;
 
-        // Compile WASM
-        match state
-            .wasm_compiler
-            .compile_dashboard(id, &dashboard_json)
-            .await
-        {
-            Ok(()) => {
-                info!("WASM compilation successful for dashboard {}", id);
-                let _ = state
-                    .store
-                    .update_wasm_status(id, WasmStatus::Ready, None)
-                    .await;
-            }
-            Err(e) => {
-                error!("WASM compilation failed for dashboard {}: {}", id, e);
-                let _ = state
-                    .store
-                    .update_wasm_status(id, WasmStatus::Failed, Some(e.to_string()))
-                    .await;
-            }
-        }
-    });
+    state.wasm_compiler.enqueue(id, dashboard_json)
 }
 
 /// GET /api/v1/dashboards - List dashboards with optional filters
@@ -134,6 +101,16 @@ async fn list_dashboards(
     Ok(Json(results))
 }
 
+/// GET /api/v1/dashboards/search - Full-text search with tag, plot type and date filters
+async fn search_dashboards(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<SearchResults>> {
+    let filter = SearchFilter::try_from(query)?;
+    let results = state.store.search(&filter).await?;
+    Ok(Json(results))
+}
+
 /// GET /api/v1/dashboards/:id - Get a single dashboard
 async fn get_dashboard(
     State(state): State<Arc<AppState>>,
@@ -151,6 +128,102 @@ async fn get_dashboard(
     Ok(Json(record))
 }
 
+/// Largest page side accepted by the export endpoints
+const MAX_EXPORT_SIZE: u32 = 8192;
+
+/// GET /api/v1/dashboards/:id/export.svg - Render the dashboard as SVG
+async fn export_svg(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse> {
+    let (dashboard, size) = export_source(&state, id, &query).await?;
+    // Rendering is CPU bound, keep it off the async workers
+    let svg =
+        tokio::task::spawn_blocking(move || vidi::export::render_dashboard_svg(&dashboard, size))
+            .await
+            .map_err(|e| ServerError::Internal(e.to_string()))?
+            .map_err(ServerError::BadRequest)?;
+    Ok(([(header::CONTENT_TYPE, "image/svg+xml")], svg))
+}
+
+/// GET /api/v1/dashboards/:id/export.pdf - Render the dashboard as a one-page PDF
+async fn export_pdf(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse> {
+    let (dashboard, size) = export_source(&state, id, &query).await?;
+    let pdf =
+        tokio::task::spawn_blocking(move || vidi::export::render_dashboard_pdf(&dashboard, size))
+            .await
+            .map_err(|e| ServerError::Internal(e.to_string()))?
+            .map_err(ServerError::BadRequest)?;
+    let disposition = format!("inline; filename=\"{}.pdf\"", id);
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        pdf,
+    ))
+}
+
+/// GET /api/v1/dashboards/:id/export.html - Download a standalone HTML page
+/// embedding the WASM viewer (the page size parameters are ignored)
+async fn export_html(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse> {
+    let (dashboard, _) = export_source(&state, id, &query).await?;
+    let dir = state.wasm_compiler.viewer_dir().to_path_buf();
+    let viewer = tokio::task::spawn_blocking(move || vidi::export::Viewer::load(dir))
+        .await
+        .map_err(|e| ServerError::Internal(e.to_string()))?
+        .map_err(ServerError::Internal)?;
+    let html =
+        vidi::export::render_dashboard_html(&dashboard, &viewer).map_err(ServerError::Internal)?;
+    let disposition = format!("attachment; filename=\"{}.html\"", id);
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/html; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        html,
+    ))
+}
+
+/// Load the dashboard to export and validate the requested page
+async fn export_source(
+    state: &AppState,
+    id: Uuid,
+    query: &ExportQuery,
+) -> Result<(vidi::core::Dashboard, UVec2)> {
+    if query.width > MAX_EXPORT_SIZE || query.height > MAX_EXPORT_SIZE {
+        return Err(ServerError::BadRequest(format!(
+            "Export size is limited to {0}x{0}",
+            MAX_EXPORT_SIZE
+        )));
+    }
+
+    let mut dashboard = state
+        .store
+        .get(id)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(id.to_string()))?
+        .dashboard;
+
+    if let Some(tab) = query.tab {
+        if tab >= dashboard.tabs.len() {
+            return Err(ServerError::BadRequest(format!("No tab {}", tab)));
+        }
+        dashboard.active_tab = tab;
+    }
+
+    Ok((dashboard, UVec2::new(query.width, query.height)))
+}
+
 /// PUT /api/v1/dashboards/:id - Replace entire dashboard
 async fn replace_dashboard(
     State(state): State<Arc<AppState>>,
@@ -164,6 +237,7 @@ async fn replace_dashboard(
         .await?
         .ok_or_else(|| ServerError::NotFound(id.to_string()))?;
 
+    let author = req.author.or_else(|| req.user.clone());
     let meta = DashboardMeta {
         id,
         xp_name: req.xp_name.or(existing.meta.xp_name),
@@ -187,7 +261,19 @@ async fn replace_dashboard(
         dashboard: req.dashboard,
     };
 
-    let updated = state.store.replace(id, record).await?;
+    let updated = store_revision(&state, record, author).await?;
+    Ok(Json(updated))
+}
+
+/// Save a new revision of an existing dashboard: record a version, refresh
+/// connected clients and rebuild its WASM
+async fn store_revision(
+    state: &AppState,
+    record: DashboardRecord,
+    author: Option<String>,
+) -> Result<DashboardRecord> {
+    let id = record.meta.id;
+    let updated = state.store.replace(id, record, author).await?;
 
     // Broadcast refresh to connected clients
     state.broadcast_hub.broadcast(
@@ -197,12 +283,92 @@ async fn replace_dashboard(
         },
     );
 
-    // Trigger async WASM recompilation
-    spawn_wasm_compilation(Arc::clone(&state), id);
+    // Queue WASM recompilation (supersedes any in-flight build)
+    queue_wasm_compilation(state, id).await?;
+
+    Ok(updated)
+}
+
+/// GET /api/v1/dashboards/:id/versions - List recorded versions, oldest first
+async fn list_versions(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<VersionSummary>>> {
+    // Verify dashboard exists
+    let _ = state
+        .store
+        .get(id)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(id.to_string()))?;
+
+    let versions = state.store.list_versions(id).await?;
+    Ok(Json(versions))
+}
+
+/// GET /api/v1/dashboards/:id/versions/:version - Get one version of a dashboard
+async fn get_version(
+    State(state): State<Arc<AppState>>,
+    Path((id, version)): Path<(Uuid, u32)>,
+) -> Result<Json<DashboardVersion>> {
+    let version = find_version(&state, id, version).await?;
+    Ok(Json(version))
+}
 
+/// POST /api/v1/dashboards/:id/versions/:version/restore - Make an old version
+/// current again, recorded as a new version
+async fn restore_version(
+    State(state): State<Arc<AppState>>,
+    Path((id, version)): Path<(Uuid, u32)>,
+    Query(query): Query<RestoreQuery>,
+) -> Result<Json<DashboardRecord>> {
+    let mut record = state
+        .store
+        .get(id)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(id.to_string()))?;
+    let version = find_version(&state, id, version).await?;
+
+    record.dashboard = version.dashboard;
+    record.meta.updated_at = chrono::Utc::now();
+    record.meta.wasm_status = WasmStatus::Pending; // Reset for recompilation
+    record.meta.wasm_error = None;
+
+    let updated = store_revision(&state, record, query.author).await?;
     Ok(Json(updated))
 }
 
+/// GET /api/v1/dashboards/:id/diff?from=&to= - Structural diff between two
+/// versions (`to` defaults to the latest)
+async fn diff_versions(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<DiffQuery>,
+) -> Result<Json<DashboardDiff>> {
+    let to = match query.to {
+        Some(to) => to,
+        None => state
+            .store
+            .list_versions(id)
+            .await?
+            .last()
+            .map(|v| v.version)
+            .ok_or_else(|| ServerError::NotFound(id.to_string()))?,
+    };
+
+    let from = find_version(&state, id, query.from).await?;
+    let to = find_version(&state, id, to).await?;
+    let diff = DashboardDiff::between(&from, &to)?;
+    Ok(Json(diff))
+}
+
+async fn find_version(state: &AppState, id: Uuid, version: u32) -> Result<DashboardVersion> {
+    state
+        .store
+        .get_version(id, version)
+        .await?
+        .ok_or_else(|| ServerError::NotFound(format!("{} version {}", id, version)))
+}
+
 /// PATCH /api/v1/dashboards/:id - Update metadata only
 async fn update_meta(
     State(state): State<Arc<AppState>>,
@@ -221,14 +387,7 @@ async fn delete_dashboard(
     let deleted = state.store.delete(id).await?;
 
     if deleted {
-        // Remove from broadcast hub
-        state.broadcast_hub.remove_dashboard(id);
-
-        // Clean up WASM files
-        if let Err(e) = state.wasm_compiler.delete_wasm(id).await {
-            error!("Failed to delete WASM for dashboard {}: {}", id, e);
-        }
-
+        lifecycle::release_dashboard(&state, id).await;
         Ok(StatusCode::NO_CONTENT)
     } else {
         Err(ServerError::NotFound(id.to_string()))
@@ -273,9 +432,12 @@ async fn push_update(
 /// Response for WASM status endpoint
 #[derive(serde::Serialize)]
 struct WasmStatusResponse {
+    mode: WasmMode,
     status: WasmStatus,
     error: Option<String>,
     wasm_ready: bool,
+    /// Module to import (the shared viewer, or this dashboard's baked bundle)
+    wasm_url: String,
 }
 
 /// GET /api/v1/dashboards/:id/wasm-status - Get WASM compilation status
@@ -289,13 +451,31 @@ async fn get_wasm_status(
         .await?
         .ok_or_else(|| ServerError::NotFound(id.to_string()))?;
 
-    let wasm_ready = state.wasm_compiler.wasm_exists(id);
+    let response = match state.config.wasm_mode {
+        WasmMode::Viewer => {
+            let wasm_ready = state.wasm_compiler.viewer_exists();
+            WasmStatusResponse {
+                mode: WasmMode::Viewer,
+                status: if wasm_ready {
+                    WasmStatus::Ready
+                } else {
+                    WasmStatus::Failed
+                },
+                error: (!wasm_ready).then(|| "Prebuilt WASM viewer is not installed".to_string()),
+                wasm_ready,
+                wasm_url: "/wasm/vidi.js".to_string(),
+            }
+        }
+        WasmMode::Baked => WasmStatusResponse {
+            mode: WasmMode::Baked,
+            status: record.meta.wasm_status,
+            error: record.meta.wasm_error,
+            wasm_ready: state.wasm_compiler.wasm_exists(id),
+            wasm_url: format!("/wasm/{}/vidi.js", id),
+        },
+    };
 
-    Ok(Json(WasmStatusResponse {
-        status: record.meta.wasm_status,
-        error: record.meta.wasm_error,
-        wasm_ready,
-    }))
+    Ok(Json(response))
 }
 
 /// POST /api/v1/dashboards/:id/recompile - Manually trigger WASM recompilation
@@ -310,13 +490,19 @@ async fn trigger_recompile(
         .await?
         .ok_or_else(|| ServerError::NotFound(id.to_string()))?;
 
-    // Check if already compiling
+    if state.config.wasm_mode != WasmMode::Baked {
+        return Err(ServerError::BadRequest(
+            "Per-dashboard compilation is disabled (server runs with --wasm-mode viewer)".into(),
+        ));
+    }
+
+    // Check if already queued or compiling
     if state.wasm_compiler.is_compiling(id) {
         return Ok(StatusCode::ACCEPTED); // Already in progress
     }
 
-    // Trigger recompilation
-    spawn_wasm_compilation(Arc::clone(&state), id);
+    // Queue recompilation
+    queue_wasm_compilation(&state, id).await?;
 
     Ok(StatusCode::ACCEPTED)
 }
@@ -1,5 +1,6 @@
 //! API handlers
 
+pub mod compare;
 pub mod dashboard;
 pub mod portal;
 pub mod stream;
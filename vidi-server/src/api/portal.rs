@@ -34,3 +34,9 @@ pub async fn dashboard_view(
         }
     }
 }
+
+/// GET /compare - Run comparison page
+pub async fn compare_view(State(_state): State<Arc<AppState>>) -> impl IntoResponse {
+    let html = include_str!("../../static/compare.html");
+    Html(html)
+}
@@ -17,7 +17,7 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::AppState;
-use crate::models::{ClientMessage, ServerMessage, UpdateCommand};
+use crate::models::{ClientMessage, ServerMessage, UpdateCommand, WasmStatus};
 use crate::storage::DashboardStore;
 
 const CHANNEL_CAPACITY: usize = 256;
@@ -81,7 +81,7 @@ impl BroadcastHub {
     /// Broadcast an update to all connected clients
     pub fn broadcast(&self, id: Uuid, cmd: UpdateCommand) {
         let seq = self.next_seq(id);
-        let msg = cmd.to_server_message(seq);
+        let msg = ServerMessage::from_update(&cmd, seq);
 
         if let Some(sender) = self.channels.get(&id) {
             // Ignore errors (no receivers is fine)
@@ -89,6 +89,15 @@ impl BroadcastHub {
         }
     }
 
+    /// Notify connected clients that a dashboard's WASM compilation status changed
+    pub fn notify_wasm_status(&self, id: Uuid, status: WasmStatus, error: Option<String>) {
+        let seq = self.next_seq(id);
+
+        if let Some(sender) = self.channels.get(&id) {
+            let _ = sender.send(ServerMessage::WasmStatus { seq, status, error });
+        }
+    }
+
     /// Remove a dashboard's channel
     pub fn remove_dashboard(&self, id: Uuid) {
         self.channels.remove(&id);
@@ -5,7 +5,7 @@ use std::io::BufReader;
 use std::sync::Arc;
 
 use axum_server::tls_rustls::RustlsConfig;
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use rustls::pki_types::CertificateDer;
 
 /// Vidi XP Dashboard Server
@@ -13,6 +13,9 @@ use rustls::pki_types::CertificateDer;
 #[command(name = "vidi-server")]
 #[command(about = "A server for hosting Vidi dashboards with real-time streaming")]
 pub struct Config {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Host to bind to
     #[arg(long, default_value = "0.0.0.0")]
     pub host: String,
@@ -22,7 +25,7 @@ pub struct Config {
     pub port: u16,
 
     /// Path to SQLite database
-    #[arg(long, default_value = "dashboards.db")]
+    #[arg(long, global = true, default_value = "dashboards.db")]
     pub db_path: String,
 
     /// Path to static files directory
@@ -33,6 +36,19 @@ pub struct Config {
     #[arg(long, default_value = "vidi-server/wasm")]
     pub wasm_dir: String,
 
+    /// How dashboards get their WASM bundle
+    #[arg(long, value_enum, default_value_t = WasmMode::Viewer)]
+    pub wasm_mode: WasmMode,
+
+    /// Number of concurrent baked WASM compilations
+    #[arg(long, default_value = "1")]
+    pub compile_workers: usize,
+
+    /// Size limit of the baked WASM build cache in MiB (0 for no limit);
+    /// least recently used builds are removed first
+    #[arg(long, default_value = "1024")]
+    pub wasm_cache_mb: u64,
+
     /// TLS certificate path (PEM format)
     #[arg(long)]
     pub tls_cert: Option<String>,
@@ -50,6 +66,27 @@ pub struct Config {
     pub cleanup_interval: u64,
 }
 
+/// Maintenance commands; without one the server starts
+#[derive(Subcommand, Clone, Debug)]
+pub enum Command {
+    /// Apply pending database migrations and exit
+    Migrate {
+        /// List applied and pending migrations without changing the database
+        #[arg(long)]
+        status: bool,
+    },
+}
+
+/// How the browser obtains WASM for a dashboard
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WasmMode {
+    /// One prebuilt viewer bundle (`<wasm_dir>/vidi.js`) loads dashboard JSON at runtime
+    Viewer,
+    /// Compile a bundle per dashboard with its JSON baked in (needs a Rust toolchain)
+    Baked,
+}
+
 /// Load TLS configuration from cert and key files
 pub fn load_tls_config(cert_path: &str, key_path: &str) -> anyhow::Result<RustlsConfig> {
     let cert_file = File::open(cert_path)?;
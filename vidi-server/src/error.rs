@@ -13,7 +13,6 @@ pub enum ServerError {
     #[error("Dashboard not found: {0}")]
     NotFound(String),
 
-    #[allow(dead_code)]
     #[error("Invalid request: {0}")]
     BadRequest(String),
 
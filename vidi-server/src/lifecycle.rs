@@ -1,9 +1,11 @@
-//! Dashboard lifecycle management - TTL cleanup
+//! Dashboard lifecycle management - TTL cleanup and WASM compile status tracking
 
 use std::sync::Arc;
 use std::time::Duration;
 
-use tracing::{debug, error, info};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
 
 use crate::AppState;
 use crate::storage::DashboardStore;
@@ -21,9 +23,12 @@ pub async fn cleanup_task(state: Arc<AppState>) {
         let active_ids = state.broadcast_hub.active_dashboard_ids();
 
         match state.store.cleanup_expired(&active_ids).await {
-            Ok(count) => {
-                if count > 0 {
-                    info!("Cleaned up {} expired dashboards", count);
+            Ok(expired) => {
+                for &id in &expired {
+                    release_dashboard(&state, id).await;
+                }
+                if !expired.is_empty() {
+                    info!("Cleaned up {} expired dashboards", expired.len());
                 }
             }
             Err(e) => {
@@ -32,3 +37,42 @@ pub async fn cleanup_task(state: Arc<AppState>) {
         }
     }
 }
+
+/// Drop what the server holds for a deleted dashboard: its broadcast channel,
+/// any in-flight WASM build and the built WASM files
+pub async fn release_dashboard(state: &AppState, id: Uuid) {
+    state.broadcast_hub.remove_dashboard(id);
+    state.wasm_compiler.cancel(id);
+    if let Err(e) = state.wasm_compiler.delete_wasm(id).await {
+        error!("Failed to delete WASM for dashboard {}: {}", id, e);
+    }
+}
+
+/// Background task that records WASM compile status changes and pushes them to clients
+pub async fn compile_status_task(state: Arc<AppState>) {
+    let mut events = state.wasm_compiler.subscribe();
+
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(n)) => {
+                warn!("Compile status task missed {} events", n);
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        // The dashboard may have been deleted meanwhile
+        if let Err(e) = state
+            .store
+            .update_wasm_status(event.id, event.status.clone(), event.error.clone())
+            .await
+        {
+            debug!("Failed to record wasm_status for {}: {}", event.id, e);
+        }
+
+        state
+            .broadcast_hub
+            .notify_wasm_status(event.id, event.status, event.error);
+    }
+}
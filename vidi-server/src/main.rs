@@ -25,7 +25,8 @@ use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use crate::api::stream::BroadcastHub;
-use crate::config::Config;
+use crate::config::{Command, Config, WasmMode};
+use crate::storage::migrations::MIGRATIONS;
 use crate::storage::sqlite::SqliteStore;
 use crate::wasm_compiler::WasmCompiler;
 
@@ -50,7 +51,13 @@ async fn main() -> anyhow::Result<()> {
 
     // Parse CLI args
     let config = Config::parse();
-    info!("Starting vidi-server on {}:{}", config.host, config.port);
+    if let Some(Command::Migrate { status }) = config.command {
+        return migrate(&config.db_path, status).await;
+    }
+    info!(
+        "Starting vidi-server on {}:{} (wasm mode: {:?})",
+        config.host, config.port, config.wasm_mode
+    );
 
     // Initialize database
     let store = SqliteStore::new(&config.db_path).await?;
@@ -66,15 +73,28 @@ async fn main() -> anyhow::Result<()> {
     let wasm_compiler = Arc::new(WasmCompiler::new(
         PathBuf::from(&config.wasm_dir),
         workspace_root,
-        1, // max concurrent compilations
+        config.compile_workers,
+        config.wasm_cache_mb * 1024 * 1024,
     ));
 
-    // Verify WASM toolchain on startup
-    if let Err(e) = wasm_compiler.verify_toolchain().await {
-        tracing::warn!(
-            "WASM toolchain verification failed: {}. Per-dashboard WASM compilation may not work.",
-            e
-        );
+    match config.wasm_mode {
+        WasmMode::Viewer => {
+            if !wasm_compiler.viewer_exists() {
+                tracing::warn!(
+                    "Prebuilt WASM viewer not found in {}. Run `just wasm-build` to build it.",
+                    config.wasm_dir
+                );
+            }
+        }
+        WasmMode::Baked => {
+            // Verify WASM toolchain on startup
+            if let Err(e) = wasm_compiler.verify_toolchain().await {
+                tracing::warn!(
+                    "WASM toolchain verification failed: {}. Per-dashboard WASM compilation may not work.",
+                    e
+                );
+            }
+        }
     }
 
     // Build app state
@@ -91,15 +111,29 @@ async fn main() -> anyhow::Result<()> {
         lifecycle::cleanup_task(cleanup_state).await;
     });
 
+    // Start the compile queue and its status tracking (baked mode only)
+    if config.wasm_mode == WasmMode::Baked {
+        state.wasm_compiler.start_workers();
+        let status_state = Arc::clone(&state);
+        tokio::spawn(async move {
+            lifecycle::compile_status_task(status_state).await;
+        });
+    }
+
     // Build router
     let app = Router::new()
         // Portal routes
         .route("/", get(api::portal::index))
         .route("/d/{id}", get(api::portal::dashboard_view))
+        .route("/compare", get(api::portal::compare_view))
         // API routes
-        .nest("/api/v1", api::dashboard::router())
+        .nest(
+            "/api/v1",
+            api::dashboard::router().merge(api::compare::router()),
+        )
         // WebSocket route
         .route("/ws/v1/dashboards/{id}", get(api::stream::ws_handler))
+        .route("/ws/v1/compare", get(api::compare::ws_handler))
         // Static files
         .nest_service("/static", ServeDir::new(&config.static_dir))
         .nest_service("/wasm", ServeDir::new(&config.wasm_dir))
@@ -135,3 +169,29 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// `vidi-server migrate`: upgrade the database schema, or show its state
+async fn migrate(db_path: &str, status: bool) -> anyhow::Result<()> {
+    let store = SqliteStore::new(db_path).await?;
+
+    if status {
+        let applied = store.applied_migrations().await?;
+        for migration in MIGRATIONS {
+            let state = match applied.iter().find(|a| a.version == migration.version) {
+                Some(a) => format!("applied {}", a.applied_at.format("%Y-%m-%d %H:%M:%S")),
+                None => "pending".to_string(),
+            };
+            println!("{:03}_{:<16} {}", migration.version, migration.name, state);
+        }
+        return Ok(());
+    }
+
+    let applied = store.run_migrations().await?;
+    if applied.is_empty() {
+        println!("{}: schema is up to date", db_path);
+    }
+    for migration in applied {
+        println!("applied {:03}_{}", migration.version, migration.name);
+    }
+    Ok(())
+}
@@ -0,0 +1,296 @@
+//! Run comparison: several dashboards merged into one, with plots of the same
+//! title overlaid as one layer group per run
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use vidi::prelude::{Color, Dashboard, Geometry2D, GridCell, Plot, PlotMeta, Tab};
+use vidi::render::components::PlotId;
+
+use super::DashboardMeta;
+
+/// Query parameters selecting the runs to compare
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct CompareQuery {
+    /// Comma-separated dashboard IDs
+    pub ids: Option<String>,
+
+    /// Compare every dashboard of this experiment (used when `ids` is absent)
+    pub xp_name: Option<String>,
+}
+
+/// A compared dashboard and the name its layers carry in legends
+#[derive(Clone, Debug, Serialize)]
+pub struct RunInfo {
+    pub id: Uuid,
+    pub name: String,
+}
+
+impl RunInfo {
+    /// Name runs by experiment when those differ, by short ID otherwise
+    pub fn name_all(metas: &[&DashboardMeta]) -> Vec<RunInfo> {
+        let mut xp_names: Vec<Option<&String>> = metas.iter().map(|m| m.xp_name.as_ref()).collect();
+        xp_names.sort();
+        xp_names.dedup();
+        let distinct = xp_names.len() == metas.len() && xp_names.iter().all(|n| n.is_some());
+
+        metas
+            .iter()
+            .map(|meta| {
+                let short_id = meta.id.simple().to_string()[..8].to_string();
+                let name = match meta.xp_name.as_deref() {
+                    Some(xp) if distinct => xp.to_string(),
+                    Some(xp) if !xp.is_empty() => format!("{} #{}", xp, short_id),
+                    _ => short_id,
+                };
+                RunInfo { id: meta.id, name }
+            })
+            .collect()
+    }
+}
+
+/// Where a source plot ended up in the comparison
+#[derive(Clone, Debug)]
+enum PlotTarget {
+    /// Overlaid with other runs; the target layer of each source layer
+    Overlay { plot_id: u64, layers: Vec<usize> },
+    /// Copied as its own plot with the same layers
+    Copy { plot_id: u64 },
+}
+
+/// Comparison dashboard built from several runs
+#[derive(Clone, Debug, Serialize)]
+pub struct Comparison {
+    pub runs: Vec<RunInfo>,
+    pub dashboard: Dashboard,
+
+    /// (run index, source plot ID) to the plot it was merged into
+    #[serde(skip)]
+    targets: HashMap<(usize, u64), PlotTarget>,
+}
+
+/// Plots of all runs sharing a tab and title
+struct Group<'a> {
+    tab: Option<&'a str>,
+    title: Option<&'a str>,
+    members: Vec<(usize, &'a Plot)>,
+}
+
+impl Comparison {
+    /// Merge `dashboards` (one per run, same order as `runs`).
+    ///
+    /// 2D and 3D graphs with the same title in the same tab are overlaid, each
+    /// run's layers colored alike and labelled with the run name. Other plots
+    /// are placed side by side with the run name in their title.
+    pub fn build(runs: Vec<RunInfo>, dashboards: &[Dashboard]) -> Self {
+        let mut groups: Vec<Group> = Vec::new();
+        let mut index: HashMap<(Option<&str>, &str), usize> = HashMap::new();
+
+        for (run, dashboard) in dashboards.iter().enumerate() {
+            let untabbed = dashboard.plots.iter().map(|p| (None, p));
+            let tabbed = dashboard
+                .tabs
+                .iter()
+                .flat_map(|t| t.plots.iter().map(move |p| (Some(t.name.as_str()), p)));
+
+            for (tab, plot) in untabbed.chain(tabbed) {
+                let meta = plot.meta();
+                let title = meta.title.as_deref().or(meta.key.as_deref());
+                let group = match title {
+                    Some(title) => *index.entry((tab, title)).or_insert_with(|| {
+                        groups.push(Group {
+                            tab,
+                            title: Some(title),
+                            members: Vec::new(),
+                        });
+                        groups.len() - 1
+                    }),
+                    // Untitled plots have nothing to match on
+                    None => {
+                        groups.push(Group {
+                            tab,
+                            title: None,
+                            members: Vec::new(),
+                        });
+                        groups.len() - 1
+                    }
+                };
+                groups[group].members.push((run, plot));
+            }
+        }
+
+        let first = dashboards.first().cloned().unwrap_or_default();
+        // Only settings that don't refer to the source's own plots carry over;
+        // its grid layout names areas its plots were placed in
+        let mut dashboard = Dashboard {
+            background: first.background,
+            columns: first.columns,
+            ..Dashboard::default()
+        };
+        let mut targets = HashMap::new();
+        let mut next_id = 1u64;
+
+        for group in &groups {
+            let merged = merge_group(group, &runs, &mut next_id, &mut targets);
+            match group.tab {
+                None => dashboard.plots.extend(merged),
+                Some(name) => {
+                    let tab = match dashboard.tabs.iter().position(|t| t.name == name) {
+                        Some(i) => &mut dashboard.tabs[i],
+                        None => {
+                            let mut tab = Tab::new(name);
+                            tab.columns = first
+                                .tabs
+                                .iter()
+                                .find(|t| t.name == name)
+                                .and_then(|t| t.columns);
+                            dashboard.tabs.push(tab);
+                            dashboard.tabs.last_mut().unwrap()
+                        }
+                    };
+                    tab.plots.extend(merged);
+                }
+            }
+        }
+
+        // Runs mixing tabbed and untabbed plots: keep the untabbed ones reachable
+        if !dashboard.tabs.is_empty() && !dashboard.plots.is_empty() {
+            let mut tab = Tab::new("Other");
+            tab.plots = std::mem::take(&mut dashboard.plots);
+            dashboard.tabs.push(tab);
+        }
+
+        Self {
+            runs,
+            dashboard,
+            targets,
+        }
+    }
+
+    /// Plot and layer in the comparison receiving updates to a run's plot layer
+    pub fn target(&self, run: usize, plot_id: u64, layer_idx: usize) -> Option<(u64, usize)> {
+        match self.targets.get(&(run, plot_id))? {
+            PlotTarget::Overlay { plot_id, layers } => Some((*plot_id, *layers.get(layer_idx)?)),
+            PlotTarget::Copy { plot_id } => Some((*plot_id, layer_idx)),
+        }
+    }
+}
+
+fn merge_group(
+    group: &Group,
+    runs: &[RunInfo],
+    next_id: &mut u64,
+    targets: &mut HashMap<(usize, u64), PlotTarget>,
+) -> Vec<Plot> {
+    let Some(&(_, template)) = group.members.first() else {
+        return Vec::new();
+    };
+    let overlay = group.members.iter().all(|(_, p)| {
+        matches!(
+            (template, p),
+            (Plot::Graph2D(_), Plot::Graph2D(_)) | (Plot::Graph3D(_), Plot::Graph3D(_))
+        )
+    });
+    let run_name = |run: usize| runs.get(run).map_or("", |r| r.name.as_str());
+
+    if !overlay {
+        return group
+            .members
+            .iter()
+            .map(|&(run, plot)| {
+                let mut plot = plot.clone();
+                let plot_id = take_id(next_id);
+                targets.insert((run, plot.id().0), PlotTarget::Copy { plot_id });
+                plot.set_id(PlotId(plot_id));
+                let meta = detach(&mut plot);
+                meta.title = Some(match group.title {
+                    Some(title) => format!("{} ({})", title, run_name(run)),
+                    None => run_name(run).to_string(),
+                });
+                plot
+            })
+            .collect();
+    }
+
+    let plot_id = take_id(next_id);
+    let mut merged = template.clone();
+    merged.set_id(PlotId(plot_id));
+    detach(&mut merged);
+
+    let label = |run: usize, own: &Option<String>| match own {
+        Some(own) => format!("{}: {}", run_name(run), own),
+        None => run_name(run).to_string(),
+    };
+
+    match &mut merged {
+        Plot::Graph2D(graph) => {
+            graph.layers.clear();
+            for &(run, plot) in &group.members {
+                let Plot::Graph2D(source) = plot else {
+                    continue;
+                };
+                let mut layers = Vec::new();
+                let mut named = false;
+                for layer in &source.layers {
+                    let mut layer = layer.clone();
+                    layer.style.color = run_color(run, layer.style.color);
+                    // One legend entry per run for its unlabelled layers, which
+                    // share the run's color; bands go with their line
+                    if layer.label.is_some()
+                        || !(named || matches!(layer.geometry, Geometry2D::FillBetween))
+                    {
+                        named |= layer.label.is_none();
+                        layer.label = Some(label(run, &layer.label));
+                    }
+                    layers.push(graph.layers.len());
+                    graph.layers.push(layer);
+                }
+                targets.insert((run, source.id.0), PlotTarget::Overlay { plot_id, layers });
+            }
+        }
+        Plot::Graph3D(graph) => {
+            graph.layers.clear();
+            for &(run, plot) in &group.members {
+                let Plot::Graph3D(source) = plot else {
+                    continue;
+                };
+                let mut layers = Vec::new();
+                let mut named = false;
+                for layer in &source.layers {
+                    let mut layer = layer.clone();
+                    layer.style.color = run_color(run, layer.style.color);
+                    if layer.label.is_some() || !named {
+                        named |= layer.label.is_none();
+                        layer.label = Some(label(run, &layer.label));
+                    }
+                    layers.push(graph.layers.len());
+                    graph.layers.push(layer);
+                }
+                targets.insert((run, source.id.0), PlotTarget::Overlay { plot_id, layers });
+            }
+        }
+        _ => {}
+    }
+    vec![merged]
+}
+
+/// Palette color of a run, keeping the layer's alpha
+fn run_color(run: usize, color: Color) -> Color {
+    Color::palette(run).with_a(color.a)
+}
+
+fn take_id(next_id: &mut u64) -> u64 {
+    let id = *next_id;
+    *next_id += 1;
+    id
+}
+
+/// Drop what ties a plot to its source run's dashboard: its key, which may
+/// be reused by other runs, and its grid placement
+fn detach(plot: &mut Plot) -> &mut PlotMeta {
+    let meta = plot.meta_mut();
+    meta.key = None;
+    meta.cell = GridCell::default();
+    meta
+}
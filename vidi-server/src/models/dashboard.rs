@@ -185,6 +185,10 @@ pub struct CreateDashboardRequest {
     /// User identifier
     pub user: Option<String>,
 
+    /// Author recorded in the version history (default: `user`)
+    #[serde(default)]
+    pub author: Option<String>,
+
     /// Tags for filtering
     #[serde(default)]
     pub tags: Vec<String>,
@@ -254,6 +258,29 @@ fn default_limit() -> u32 {
     50
 }
 
+/// Query parameters for vector exports
+#[derive(Clone, Debug, Deserialize)]
+pub struct ExportQuery {
+    /// Page width in pixels (points for PDF)
+    #[serde(default = "default_export_width")]
+    pub width: u32,
+
+    /// Page height in pixels (points for PDF)
+    #[serde(default = "default_export_height")]
+    pub height: u32,
+
+    /// Tab to export instead of the dashboard's active tab
+    pub tab: Option<usize>,
+}
+
+fn default_export_width() -> u32 {
+    1280
+}
+
+fn default_export_height() -> u32 {
+    960
+}
+
 /// Summary of a dashboard for list responses
 #[derive(Clone, Debug, Serialize)]
 pub struct DashboardSummary {
@@ -1,9 +1,15 @@
 //! WebSocket message types for real-time streaming
 
 use serde::{Deserialize, Serialize};
-use vidi::prelude::{Dashboard, Plot};
+use vidi::prelude::{Annotation, Dashboard, Plot};
+
+use super::WasmStatus;
+
+/// Update command used internally and via REST API to push updates
+pub use vidi::prelude::UpdateCommand;
 
 /// Messages sent from server to clients
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ServerMessage {
@@ -24,12 +30,26 @@ pub enum ServerMessage {
         points: Vec<f32>,
     },
 
+    /// Add an annotation to a 2D or candlestick plot
+    AddAnnotation {
+        seq: u64,
+        plot_id: u64,
+        annotation: Annotation,
+    },
+
     /// Update an entire plot
     UpdatePlot { seq: u64, plot_id: u64, plot: Plot },
 
     /// Full dashboard refresh
     RefreshAll { seq: u64, dashboard: Dashboard },
 
+    /// Baked WASM compilation status changed
+    WasmStatus {
+        seq: u64,
+        status: WasmStatus,
+        error: Option<String>,
+    },
+
     /// Error message
     Error { seq: u64, message: String },
 
@@ -51,49 +71,10 @@ pub enum ClientMessage {
     GetState,
 }
 
-/// Update command used internally and via REST API to push updates
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "snake_case")]
-pub enum UpdateCommand {
-    /// Append points to a 2D layer
-    AppendPoints2D {
-        plot_id: u64,
-        layer_idx: usize,
-        points: Vec<[f32; 2]>,
-    },
-
-    /// Append points to a 3D layer
-    AppendPoints3D {
-        plot_id: u64,
-        layer_idx: usize,
-        points: Vec<[f32; 3]>,
-    },
-
-    /// Replace all points in a 2D layer
-    ReplaceTrace2D {
-        plot_id: u64,
-        layer_idx: usize,
-        points: Vec<[f32; 2]>,
-    },
-
-    /// Replace all points in a 3D layer
-    ReplaceTrace3D {
-        plot_id: u64,
-        layer_idx: usize,
-        points: Vec<[f32; 3]>,
-    },
-
-    /// Update an entire plot
-    UpdatePlot { plot_id: u64, plot: Plot },
-
-    /// Replace the entire dashboard
-    RefreshAll { dashboard: Dashboard },
-}
-
-impl UpdateCommand {
-    /// Convert to a server message with sequence number
-    pub fn to_server_message(&self, seq: u64) -> ServerMessage {
-        match self {
+impl ServerMessage {
+    /// Convert an update command to a server message with sequence number
+    pub fn from_update(cmd: &UpdateCommand, seq: u64) -> Self {
+        match cmd {
             UpdateCommand::AppendPoints2D {
                 plot_id,
                 layer_idx,
@@ -134,6 +115,14 @@ impl UpdateCommand {
                 layer_idx: *layer_idx,
                 points: points.iter().flat_map(|p| [p[0], p[1], p[2]]).collect(),
             },
+            UpdateCommand::AddAnnotation {
+                plot_id,
+                annotation,
+            } => ServerMessage::AddAnnotation {
+                seq,
+                plot_id: *plot_id,
+                annotation: annotation.clone(),
+            },
             UpdateCommand::UpdatePlot { plot_id, plot } => ServerMessage::UpdatePlot {
                 seq,
                 plot_id: *plot_id,
@@ -1,7 +1,13 @@
 //! Data models for the server
 
+pub mod compare;
 pub mod dashboard;
 pub mod messages;
+pub mod search;
+pub mod version;
 
+pub use compare::*;
 pub use dashboard::*;
 pub use messages::*;
+pub use search::*;
+pub use version::*;
@@ -0,0 +1,329 @@
+//! Dashboard search queries and the text indexed for them
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use vidi::prelude::{Dashboard, Distribution, Plot, Radial};
+
+use crate::error::{Result, ServerError};
+use crate::models::DashboardSummary;
+
+/// Plot type names accepted by the `plot_types` filter
+pub const PLOT_TYPES: &[&str] = &[
+    "graph2d",
+    "graph3d",
+    "histogram",
+    "pdf",
+    "boxplot",
+    "ecdf",
+    "field",
+    "pie",
+    "radar",
+    "candlestick",
+    "heatmap",
+];
+
+/// Query parameters for searching dashboards
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct SearchQuery {
+    /// Free text matched against names, users, tags, plot titles,
+    /// descriptions, axis labels and tab names (words match by prefix)
+    pub q: Option<String>,
+
+    /// Comma-separated tags
+    pub tags: Option<String>,
+
+    /// Whether dashboards need all the tags or any of them
+    #[serde(default)]
+    pub tag_mode: TagMode,
+
+    /// Comma-separated plot types (see [`PLOT_TYPES`]); dashboards containing
+    /// any of them match
+    pub plot_types: Option<String>,
+
+    /// Filter by experiment name
+    pub xp_name: Option<String>,
+
+    /// Filter by user
+    pub user: Option<String>,
+
+    /// Date bounds, RFC 3339 or `YYYY-MM-DD` (inclusive, whole days)
+    pub created_after: Option<String>,
+    pub created_before: Option<String>,
+    pub updated_after: Option<String>,
+    pub updated_before: Option<String>,
+
+    /// Maximum number of results, at most [`MAX_LIMIT`]
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+
+    /// Offset for pagination
+    #[serde(default)]
+    pub offset: u32,
+}
+
+fn default_limit() -> u32 {
+    50
+}
+
+/// Most results returned by one search request
+pub const MAX_LIMIT: u32 = 100;
+
+/// How multiple tags combine
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMode {
+    #[default]
+    All,
+    Any,
+}
+
+/// A search query checked and split into its parts
+#[derive(Clone, Debug, Default)]
+pub struct SearchFilter {
+    /// Lowercased words of the free text query, each with a letter or digit
+    pub words: Vec<String>,
+    pub tags: Vec<String>,
+    pub tag_mode: TagMode,
+    pub plot_types: Vec<String>,
+    pub xp_name: Option<String>,
+    pub user: Option<String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub updated_after: Option<DateTime<Utc>>,
+    pub updated_before: Option<DateTime<Utc>>,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+impl TryFrom<SearchQuery> for SearchFilter {
+    type Error = ServerError;
+
+    fn try_from(query: SearchQuery) -> Result<Self> {
+        let plot_types = split_list(query.plot_types.as_deref())
+            .into_iter()
+            .map(|t| t.to_lowercase())
+            .collect::<Vec<_>>();
+        if let Some(unknown) = plot_types
+            .iter()
+            .find(|t| !PLOT_TYPES.contains(&t.as_str()))
+        {
+            return Err(ServerError::BadRequest(format!(
+                "Unknown plot type '{}' (expected one of: {})",
+                unknown,
+                PLOT_TYPES.join(", ")
+            )));
+        }
+
+        Ok(Self {
+            words: query
+                .q
+                .as_deref()
+                .unwrap_or_default()
+                .split_whitespace()
+                // Bare punctuation has nothing to match against
+                .filter(|w| w.chars().any(char::is_alphanumeric))
+                .map(str::to_lowercase)
+                .collect(),
+            tags: split_list(query.tags.as_deref()),
+            tag_mode: query.tag_mode,
+            plot_types,
+            xp_name: query.xp_name,
+            user: query.user,
+            created_after: parse_bound(query.created_after.as_deref(), false)?,
+            created_before: parse_bound(query.created_before.as_deref(), true)?,
+            updated_after: parse_bound(query.updated_after.as_deref(), false)?,
+            updated_before: parse_bound(query.updated_before.as_deref(), true)?,
+            limit: query.limit.min(MAX_LIMIT),
+            offset: query.offset,
+        })
+    }
+}
+
+fn split_list(list: Option<&str>) -> Vec<String> {
+    list.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Parse a date bound; a bare date covers the whole day
+fn parse_bound(value: Option<&str>, end_of_day: bool) -> Result<Option<DateTime<Utc>>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(Some(time.with_timezone(&Utc)));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| ServerError::BadRequest(format!("Invalid date '{}'", value)))?;
+    let time = if end_of_day {
+        date.and_hms_milli_opt(23, 59, 59, 999)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    };
+    Ok(time.map(|t| t.and_utc()))
+}
+
+/// One page of search results
+#[derive(Clone, Debug, Serialize)]
+pub struct SearchResults {
+    /// Number of matching dashboards across all pages
+    pub total: u64,
+    pub limit: u32,
+    pub offset: u32,
+    pub results: Vec<SearchHit>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub summary: DashboardSummary,
+
+    /// Matching text with the hits in `[brackets]`, for free text queries
+    pub snippet: Option<String>,
+}
+
+/// Searchable text extracted from a dashboard
+#[derive(Clone, Debug, Default)]
+pub struct SearchDocument {
+    pub titles: Vec<String>,
+    pub descriptions: Vec<String>,
+    pub labels: Vec<String>,
+    pub tabs: Vec<String>,
+    /// Distinct plot types, named as in [`PLOT_TYPES`]
+    pub plot_types: Vec<&'static str>,
+}
+
+impl SearchDocument {
+    pub fn new(dashboard: &Dashboard) -> Self {
+        let mut doc = Self {
+            tabs: dashboard.tabs.iter().map(|t| t.name.clone()).collect(),
+            ..Self::default()
+        };
+
+        let plots = dashboard
+            .plots
+            .iter()
+            .chain(dashboard.tabs.iter().flat_map(|t| &t.plots));
+        for plot in plots {
+            let meta = plot.meta();
+            doc.titles.extend(meta.title.clone());
+            doc.descriptions.extend(meta.description.clone());
+            doc.labels
+                .extend(axis_labels(plot).into_iter().flatten().cloned());
+
+            let plot_type = plot_type(plot);
+            if !doc.plot_types.contains(&plot_type) {
+                doc.plot_types.push(plot_type);
+            }
+        }
+        doc
+    }
+}
+
+fn plot_type(plot: &Plot) -> &'static str {
+    match plot {
+        Plot::Graph2D(_) => "graph2d",
+        Plot::Graph3D(_) => "graph3d",
+        Plot::Distribution(Distribution::Histogram { .. }) => "histogram",
+        Plot::Distribution(Distribution::Pdf { .. }) => "pdf",
+        Plot::Distribution(Distribution::BoxPlot { .. }) => "boxplot",
+        Plot::Distribution(Distribution::ECDF { .. }) => "ecdf",
+        Plot::Field(_) => "field",
+        Plot::Radial(Radial::Pie { .. }) => "pie",
+        Plot::Radial(Radial::Radar { .. }) => "radar",
+        Plot::Candlestick(_) => "candlestick",
+        Plot::Heatmap(_) => "heatmap",
+    }
+}
+
+fn axis_labels(plot: &Plot) -> Vec<Option<&String>> {
+    match plot {
+        Plot::Graph2D(g) => vec![g.x_label.as_ref(), g.y_label.as_ref()],
+        Plot::Graph3D(g) => vec![g.x_label.as_ref(), g.y_label.as_ref(), g.z_label.as_ref()],
+        Plot::Distribution(
+            Distribution::Histogram {
+                x_label, y_label, ..
+            }
+            | Distribution::Pdf {
+                x_label, y_label, ..
+            }
+            | Distribution::BoxPlot {
+                x_label, y_label, ..
+            }
+            | Distribution::ECDF {
+                x_label, y_label, ..
+            },
+        ) => vec![x_label.as_ref(), y_label.as_ref()],
+        Plot::Candlestick(c) => vec![c.x_label.as_ref(), c.y_label.as_ref()],
+        Plot::Field(_) | Plot::Radial(_) | Plot::Heatmap(_) => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(query: SearchQuery) -> Result<SearchFilter> {
+        SearchFilter::try_from(query)
+    }
+
+    #[test]
+    fn splits_and_normalizes_the_query() {
+        let filter = filter(SearchQuery {
+            q: Some("  Loss  -  Val/Acc ".into()),
+            tags: Some("a, ,b,".into()),
+            plot_types: Some("Graph2D,pie".into()),
+            limit: 20,
+            ..SearchQuery::default()
+        })
+        .unwrap();
+        assert_eq!(filter.words, vec!["loss", "val/acc"]);
+        assert_eq!(filter.tags, vec!["a", "b"]);
+        assert_eq!(filter.plot_types, vec!["graph2d", "pie"]);
+        assert_eq!(filter.limit, 20);
+    }
+
+    #[test]
+    fn caps_the_limit() {
+        let query = |limit| SearchQuery {
+            limit,
+            ..SearchQuery::default()
+        };
+        assert_eq!(filter(query(u32::MAX)).unwrap().limit, MAX_LIMIT);
+        assert_eq!(filter(query(MAX_LIMIT)).unwrap().limit, MAX_LIMIT);
+    }
+
+    #[test]
+    fn rejects_unknown_plot_types_and_bad_dates() {
+        let unknown = SearchQuery {
+            plot_types: Some("graph2d,sankey".into()),
+            ..SearchQuery::default()
+        };
+        assert!(matches!(filter(unknown), Err(ServerError::BadRequest(_))));
+
+        let bad_date = SearchQuery {
+            created_after: Some("yesterday".into()),
+            ..SearchQuery::default()
+        };
+        assert!(matches!(filter(bad_date), Err(ServerError::BadRequest(_))));
+    }
+
+    #[test]
+    fn bare_dates_cover_whole_days() {
+        let filter = filter(SearchQuery {
+            created_after: Some("2024-03-01".into()),
+            created_before: Some("2024-03-01".into()),
+            updated_after: Some("2024-03-01T12:00:00+02:00".into()),
+            ..SearchQuery::default()
+        })
+        .unwrap();
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        assert_eq!(filter.created_after, Some(at("2024-03-01T00:00:00Z")));
+        assert_eq!(filter.created_before, Some(at("2024-03-01T23:59:59.999Z")));
+        assert_eq!(filter.updated_after, Some(at("2024-03-01T10:00:00Z")));
+        assert_eq!(filter.updated_before, None);
+    }
+}
@@ -0,0 +1,467 @@
+//! Dashboard version history and structural diffs
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+use vidi::prelude::{Dashboard, Plot};
+
+use crate::error::Result;
+
+/// A stored snapshot of a dashboard
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DashboardVersion {
+    pub dashboard_id: Uuid,
+
+    /// Version number, starting at 1 and increasing with every change
+    pub version: u32,
+
+    /// Who made the change, if known
+    pub author: Option<String>,
+
+    /// When the version was recorded
+    pub created_at: DateTime<Utc>,
+
+    /// The dashboard as it was at this version
+    pub dashboard: Dashboard,
+}
+
+/// Version entry for history list responses
+#[derive(Clone, Debug, Serialize)]
+pub struct VersionSummary {
+    pub version: u32,
+    pub author: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub plot_count: usize,
+}
+
+impl From<&DashboardVersion> for VersionSummary {
+    fn from(version: &DashboardVersion) -> Self {
+        Self {
+            version: version.version,
+            author: version.author.clone(),
+            created_at: version.created_at,
+            plot_count: version.dashboard.plots.len()
+                + version
+                    .dashboard
+                    .tabs
+                    .iter()
+                    .map(|t| t.plots.len())
+                    .sum::<usize>(),
+        }
+    }
+}
+
+/// Query parameters for restoring a version
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct RestoreQuery {
+    /// Author recorded for the new version created by the restore
+    pub author: Option<String>,
+}
+
+/// Query parameters for diffing two versions
+#[derive(Clone, Debug, Deserialize)]
+pub struct DiffQuery {
+    /// Older version
+    pub from: u32,
+
+    /// Newer version (default: latest)
+    pub to: Option<u32>,
+}
+
+/// Kind of change between two versions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Change {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Structural difference between two versions of a dashboard
+#[derive(Clone, Debug, Serialize)]
+pub struct DashboardDiff {
+    pub from: u32,
+    pub to: u32,
+
+    /// Changed dashboard-level fields (background, columns, ...)
+    pub fields: Vec<String>,
+
+    pub tabs_added: Vec<String>,
+    pub tabs_removed: Vec<String>,
+
+    /// Plots that were added, removed or changed; unchanged plots are omitted
+    pub plots: Vec<PlotDiff>,
+}
+
+/// Change to a single plot
+#[derive(Clone, Debug, Serialize)]
+pub struct PlotDiff {
+    pub change: Change,
+    pub plot_id: u64,
+    pub key: Option<String>,
+    pub title: Option<String>,
+
+    /// Tab holding the plot (in the newer version unless removed)
+    pub tab: Option<String>,
+
+    /// Plot type, e.g. `Graph2D`
+    pub kind: String,
+
+    /// Changed plot fields other than layers
+    pub fields: Vec<String>,
+
+    /// Added, removed or changed layers
+    pub layers: Vec<LayerDiff>,
+}
+
+/// Change to a single layer of a 2D or 3D graph
+#[derive(Clone, Debug, Serialize)]
+pub struct LayerDiff {
+    pub index: usize,
+    pub change: Change,
+
+    /// Changed layer fields (`xy`, `style`, ...)
+    pub fields: Vec<String>,
+}
+
+/// How plots are matched across versions: by key when set, else by ID
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum PlotIdentity {
+    Key(String),
+    Id(u64),
+}
+
+/// A plot flattened out of its tab, with its JSON form for comparison
+struct PlotEntry<'a> {
+    plot: &'a Plot,
+    tab: Option<&'a str>,
+    kind: String,
+    body: Map<String, Value>,
+}
+
+impl DashboardDiff {
+    /// Compare two versions, matching plots by key (or ID when unkeyed)
+    pub fn between(from: &DashboardVersion, to: &DashboardVersion) -> Result<Self> {
+        let old = &from.dashboard;
+        let new = &to.dashboard;
+
+        let old_tabs: Vec<&str> = old.tabs.iter().map(|t| t.name.as_str()).collect();
+        let new_tabs: Vec<&str> = new.tabs.iter().map(|t| t.name.as_str()).collect();
+
+        let mut fields = object_diff(
+            &as_object(serde_json::to_value(old)?),
+            &as_object(serde_json::to_value(new)?),
+            &["plots", "tabs", "active_tab"],
+        );
+        // Per-tab column counts are the only tab setting besides the plots
+        let old_columns: HashMap<&str, Option<usize>> = old
+            .tabs
+            .iter()
+            .map(|t| (t.name.as_str(), t.columns))
+            .collect();
+        if new.tabs.iter().any(|t| {
+            old_columns
+                .get(t.name.as_str())
+                .is_some_and(|c| *c != t.columns)
+        }) {
+            fields.push("tabs.columns".to_string());
+        }
+
+        let old_plots = plot_entries(old)?;
+        let new_plots = plot_entries(new)?;
+        let old_index: HashMap<PlotIdentity, usize> = old_plots
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (identity(e.plot), i))
+            .collect();
+
+        let mut plots = Vec::new();
+        let mut matched = vec![false; old_plots.len()];
+        for entry in &new_plots {
+            match old_index.get(&identity(entry.plot)) {
+                Some(&i) => {
+                    matched[i] = true;
+                    if let Some(diff) = plot_diff(&old_plots[i], entry) {
+                        plots.push(diff);
+                    }
+                }
+                None => plots.push(whole_plot(entry, Change::Added)),
+            }
+        }
+        for (entry, _) in old_plots.iter().zip(&matched).filter(|(_, m)| !**m) {
+            plots.push(whole_plot(entry, Change::Removed));
+        }
+
+        Ok(Self {
+            from: from.version,
+            to: to.version,
+            fields,
+            tabs_added: new_tabs
+                .iter()
+                .filter(|t| !old_tabs.contains(t))
+                .map(|t| t.to_string())
+                .collect(),
+            tabs_removed: old_tabs
+                .iter()
+                .filter(|t| !new_tabs.contains(t))
+                .map(|t| t.to_string())
+                .collect(),
+            plots,
+        })
+    }
+}
+
+fn identity(plot: &Plot) -> PlotIdentity {
+    match &plot.meta().key {
+        Some(key) => PlotIdentity::Key(key.clone()),
+        None => PlotIdentity::Id(plot.id().0),
+    }
+}
+
+/// All plots of a dashboard, untabbed ones first
+fn plot_entries(dashboard: &Dashboard) -> Result<Vec<PlotEntry<'_>>> {
+    let untabbed = dashboard.plots.iter().map(|p| (p, None));
+    let tabbed = dashboard
+        .tabs
+        .iter()
+        .flat_map(|t| t.plots.iter().map(move |p| (p, Some(t.name.as_str()))));
+
+    untabbed
+        .chain(tabbed)
+        .map(|(plot, tab)| {
+            // Plots serialize externally tagged: {"Graph2D": {...}}
+            let (kind, body) = match serde_json::to_value(plot)? {
+                Value::Object(map) => match map.into_iter().next() {
+                    Some((kind, body)) => (kind, as_object(body)),
+                    None => (String::new(), Map::new()),
+                },
+                _ => (String::new(), Map::new()),
+            };
+            // Distributions and radial charts nest one enum level deeper
+            let (kind, body) = match body.iter().next() {
+                Some((inner, Value::Object(fields)))
+                    if matches!(kind.as_str(), "Distribution" | "Radial") =>
+                {
+                    (format!("{}::{}", kind, inner), fields.clone())
+                }
+                _ => (kind, body),
+            };
+            Ok(PlotEntry {
+                plot,
+                tab,
+                kind,
+                body,
+            })
+        })
+        .collect()
+}
+
+fn whole_plot(entry: &PlotEntry, change: Change) -> PlotDiff {
+    PlotDiff {
+        change,
+        plot_id: entry.plot.id().0,
+        key: entry.plot.meta().key.clone(),
+        title: entry.plot.meta().title.clone(),
+        tab: entry.tab.map(str::to_string),
+        kind: entry.kind.clone(),
+        fields: Vec::new(),
+        layers: Vec::new(),
+    }
+}
+
+/// Difference between two matched plots, `None` when they are identical
+fn plot_diff(old: &PlotEntry, new: &PlotEntry) -> Option<PlotDiff> {
+    let mut fields = Vec::new();
+    let mut layers = Vec::new();
+
+    if old.kind != new.kind {
+        fields.push("type".to_string());
+    } else {
+        fields = object_diff(&old.body, &new.body, &["id", "layers"]);
+        layers = layer_diffs(old.body.get("layers"), new.body.get("layers"));
+    }
+    if old.tab != new.tab {
+        fields.push("tab".to_string());
+    }
+
+    if fields.is_empty() && layers.is_empty() {
+        return None;
+    }
+    Some(PlotDiff {
+        fields,
+        layers,
+        ..whole_plot(new, Change::Changed)
+    })
+}
+
+/// Layers are matched by position
+fn layer_diffs(old: Option<&Value>, new: Option<&Value>) -> Vec<LayerDiff> {
+    let empty = Vec::new();
+    let old = old.and_then(Value::as_array).unwrap_or(&empty);
+    let new = new.and_then(Value::as_array).unwrap_or(&empty);
+
+    (0..old.len().max(new.len()))
+        .filter_map(|index| {
+            let (change, fields) = match (old.get(index), new.get(index)) {
+                (Some(_), None) => (Change::Removed, Vec::new()),
+                (None, Some(_)) => (Change::Added, Vec::new()),
+                (Some(a), Some(b)) if a != b => (
+                    Change::Changed,
+                    object_diff(&as_object(a.clone()), &as_object(b.clone()), &[]),
+                ),
+                _ => return None,
+            };
+            Some(LayerDiff {
+                index,
+                change,
+                fields,
+            })
+        })
+        .collect()
+}
+
+/// Sorted names of the fields that differ between two objects
+fn object_diff(old: &Map<String, Value>, new: &Map<String, Value>, skip: &[&str]) -> Vec<String> {
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    keys.into_iter()
+        .filter(|k| !skip.contains(&k.as_str()) && old.get(*k) != new.get(*k))
+        .cloned()
+        .collect()
+}
+
+fn as_object(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec2;
+    use vidi::prelude::dash;
+
+    fn version(version: u32, dashboard: Dashboard) -> DashboardVersion {
+        DashboardVersion {
+            dashboard_id: Uuid::nil(),
+            version,
+            author: None,
+            created_at: Utc::now(),
+            dashboard,
+        }
+    }
+
+    fn diff(old: Dashboard, new: Dashboard) -> DashboardDiff {
+        DashboardDiff::between(&version(1, old), &version(2, new)).unwrap()
+    }
+
+    fn points(n: usize) -> Vec<Vec2> {
+        (0..n).map(|i| Vec2::new(i as f32, i as f32)).collect()
+    }
+
+    fn find<'a>(diff: &'a DashboardDiff, key: &str) -> &'a PlotDiff {
+        diff.plots
+            .iter()
+            .find(|p| p.key.as_deref() == Some(key))
+            .unwrap()
+    }
+
+    #[test]
+    fn identical_versions_have_no_changes() {
+        let dashboard = dash()
+            .add_2d(|p| p.line(points(3), None).title("loss"))
+            .build();
+        let diff = diff(dashboard.clone(), dashboard);
+        assert!(diff.fields.is_empty());
+        assert!(diff.plots.is_empty());
+        assert!(diff.tabs_added.is_empty() && diff.tabs_removed.is_empty());
+    }
+
+    #[test]
+    fn keyed_plots_match_across_rebuilds() {
+        let old = dash()
+            .add_2d(|p| p.line(points(3), None).key("loss"))
+            .add_2d(|p| p.line(points(3), None).key("accuracy"))
+            .build();
+        let new = dash()
+            .add_2d(|p| p.line(points(4), None).scatter(points(2), None).key("loss"))
+            .add_2d(|p| p.line(points(3), None).key("lr"))
+            .build();
+        let diff = diff(old, new);
+        assert_eq!(diff.plots.len(), 3);
+
+        let loss = find(&diff, "loss");
+        assert_eq!(loss.change, Change::Changed);
+        assert_eq!(loss.kind, "Graph2D");
+        assert_eq!(loss.layers.len(), 2);
+        assert_eq!(loss.layers[0].change, Change::Changed);
+        assert_eq!(loss.layers[0].fields, vec!["xy".to_string()]);
+        assert_eq!(loss.layers[1].change, Change::Added);
+
+        assert_eq!(find(&diff, "lr").change, Change::Added);
+        assert_eq!(find(&diff, "accuracy").change, Change::Removed);
+    }
+
+    #[test]
+    fn unkeyed_plots_match_by_id() {
+        let old = dash().add_2d(|p| p.line(points(3), None)).build();
+
+        let mut edited = old.clone();
+        edited.plots[0].meta_mut().title = Some("loss".into());
+        let changed = diff(old.clone(), edited);
+        assert_eq!(changed.plots.len(), 1);
+        assert_eq!(changed.plots[0].change, Change::Changed);
+        assert_eq!(changed.plots[0].title.as_deref(), Some("loss"));
+        assert!(!changed.plots[0].fields.is_empty());
+        assert!(changed.plots[0].layers.is_empty());
+
+        // A rebuilt plot gets a fresh ID, so it reads as a replacement
+        let rebuilt = dash().add_2d(|p| p.line(points(3), None)).build();
+        let replaced = diff(old, rebuilt);
+        let changes: Vec<Change> = replaced.plots.iter().map(|p| p.change).collect();
+        assert_eq!(changes, vec![Change::Added, Change::Removed]);
+    }
+
+    #[test]
+    fn tabs_and_dashboard_fields() {
+        let old = dash()
+            .add_tab("train", |t| {
+                t.add_2d(|p| p.line(points(3), None).key("loss"))
+            })
+            .add_tab("eval", |t| t)
+            .build();
+        let new = dash()
+            .columns(2)
+            .add_tab("train", |t| t)
+            .add_tab("test", |t| {
+                t.add_2d(|p| p.line(points(3), None).key("loss"))
+            })
+            .build();
+        let diff = diff(old, new);
+
+        assert_eq!(diff.fields, vec!["columns".to_string()]);
+        assert_eq!(diff.tabs_added, vec!["test".to_string()]);
+        assert_eq!(diff.tabs_removed, vec!["eval".to_string()]);
+
+        let loss = find(&diff, "loss");
+        assert_eq!(loss.change, Change::Changed);
+        assert_eq!(loss.tab.as_deref(), Some("test"));
+        assert_eq!(loss.fields, vec!["tab".to_string()]);
+    }
+
+    #[test]
+    fn changed_plot_type_is_reported_as_type() {
+        let old = dash().add_2d(|p| p.key("chart")).build();
+        let new = dash()
+            .add_distribution(|d| d.histogram(vec![1.0, 2.0]).key("chart"))
+            .build();
+        let diff = diff(old, new);
+        let chart = find(&diff, "chart");
+        assert_eq!(chart.kind, "Distribution::Histogram");
+        assert_eq!(chart.fields, vec!["type".to_string()]);
+    }
+}
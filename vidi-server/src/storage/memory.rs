@@ -6,22 +6,41 @@ use async_trait::async_trait;
 use chrono::Utc;
 use dashmap::DashMap;
 use uuid::Uuid;
+use vidi::prelude::Dashboard;
 
 use crate::error::{Result, ServerError};
-use crate::models::{DashboardRecord, DashboardSummary, ListQuery, UpdateMetaRequest, WasmStatus};
+use crate::models::{
+    DashboardRecord, DashboardSummary, DashboardVersion, ListQuery, SearchDocument, SearchFilter,
+    SearchHit, SearchResults, TagMode, UpdateMetaRequest, VersionSummary, WasmStatus,
+};
 use crate::storage::DashboardStore;
 
 /// In-memory dashboard store for testing
 pub struct MemoryStore {
     dashboards: DashMap<Uuid, DashboardRecord>,
+    versions: DashMap<Uuid, Vec<DashboardVersion>>,
 }
 
 impl MemoryStore {
     pub fn new() -> Self {
         Self {
             dashboards: DashMap::new(),
+            versions: DashMap::new(),
         }
     }
+
+    /// Record a new version of a dashboard's data, numbered after the latest one
+    fn add_version(&self, id: Uuid, author: Option<String>, dashboard: &Dashboard) {
+        let mut versions = self.versions.entry(id).or_default();
+        let version = DashboardVersion {
+            dashboard_id: id,
+            version: versions.last().map_or(1, |v| v.version + 1),
+            author,
+            created_at: Utc::now(),
+            dashboard: dashboard.clone(),
+        };
+        versions.push(version);
+    }
 }
 
 impl Default for MemoryStore {
@@ -32,8 +51,13 @@ impl Default for MemoryStore {
 
 #[async_trait]
 impl DashboardStore for MemoryStore {
-    async fn create(&self, record: DashboardRecord) -> Result<DashboardRecord> {
+    async fn create(
+        &self,
+        record: DashboardRecord,
+        author: Option<String>,
+    ) -> Result<DashboardRecord> {
         let id = record.meta.id;
+        self.add_version(id, author, &record.dashboard);
         self.dashboards.insert(id, record.clone());
         Ok(record)
     }
@@ -106,12 +130,43 @@ impl DashboardStore for MemoryStore {
         Ok(results.into_iter().skip(start).take(end.min(len)).collect())
     }
 
-    async fn replace(&self, id: Uuid, mut record: DashboardRecord) -> Result<DashboardRecord> {
+    async fn search(&self, filter: &SearchFilter) -> Result<SearchResults> {
+        let mut matches: Vec<DashboardSummary> = self
+            .dashboards
+            .iter()
+            .filter(|entry| search_matches(entry.value(), filter))
+            .map(|entry| DashboardSummary::from(entry.value()))
+            .collect();
+        matches.sort_by_key(|s| std::cmp::Reverse(s.updated_at));
+
+        Ok(SearchResults {
+            total: matches.len() as u64,
+            limit: filter.limit,
+            offset: filter.offset,
+            results: matches
+                .into_iter()
+                .skip(filter.offset as usize)
+                .take(filter.limit as usize)
+                .map(|summary| SearchHit {
+                    summary,
+                    snippet: None,
+                })
+                .collect(),
+        })
+    }
+
+    async fn replace(
+        &self,
+        id: Uuid,
+        mut record: DashboardRecord,
+        author: Option<String>,
+    ) -> Result<DashboardRecord> {
         if !self.dashboards.contains_key(&id) {
             return Err(ServerError::NotFound(id.to_string()));
         }
         record.meta.id = id;
         record.meta.updated_at = Utc::now();
+        self.add_version(id, author, &record.dashboard);
         self.dashboards.insert(id, record.clone());
         Ok(record)
     }
@@ -148,6 +203,7 @@ impl DashboardStore for MemoryStore {
     }
 
     async fn delete(&self, id: Uuid) -> Result<bool> {
+        self.versions.remove(&id);
         Ok(self.dashboards.remove(&id).is_some())
     }
 
@@ -158,8 +214,8 @@ impl DashboardStore for MemoryStore {
         Ok(())
     }
 
-    async fn cleanup_expired(&self, active_ids: &[Uuid]) -> Result<u64> {
-        let mut deleted = 0u64;
+    async fn cleanup_expired(&self, active_ids: &[Uuid]) -> Result<Vec<Uuid>> {
+        let mut deleted = Vec::new();
 
         let expired_ids: Vec<Uuid> = self
             .dashboards
@@ -178,8 +234,9 @@ impl DashboardStore for MemoryStore {
             .collect();
 
         for id in expired_ids {
+            self.versions.remove(&id);
             if self.dashboards.remove(&id).is_some() {
-                deleted += 1;
+                deleted.push(id);
             }
         }
 
@@ -207,4 +264,77 @@ impl DashboardStore for MemoryStore {
             Ok(None)
         }
     }
+
+    async fn list_versions(&self, id: Uuid) -> Result<Vec<VersionSummary>> {
+        Ok(self
+            .versions
+            .get(&id)
+            .map(|v| v.iter().map(VersionSummary::from).collect())
+            .unwrap_or_default())
+    }
+
+    async fn get_version(&self, id: Uuid, version: u32) -> Result<Option<DashboardVersion>> {
+        Ok(self
+            .versions
+            .get(&id)
+            .and_then(|v| v.iter().find(|v| v.version == version).cloned()))
+    }
+}
+
+fn search_matches(record: &DashboardRecord, filter: &SearchFilter) -> bool {
+    let meta = &record.meta;
+    if filter.xp_name.is_some() && meta.xp_name != filter.xp_name {
+        return false;
+    }
+    if filter.user.is_some() && meta.user != filter.user {
+        return false;
+    }
+
+    let has_tag = |tag: &String| meta.tags.contains(tag);
+    let tags_match = match filter.tag_mode {
+        TagMode::All => filter.tags.iter().all(has_tag),
+        TagMode::Any => filter.tags.is_empty() || filter.tags.iter().any(has_tag),
+    };
+    if !tags_match {
+        return false;
+    }
+
+    let in_range = |time, after: Option<_>, before: Option<_>| {
+        after.is_none_or(|a| time >= a) && before.is_none_or(|b| time <= b)
+    };
+    if !in_range(meta.created_at, filter.created_after, filter.created_before)
+        || !in_range(meta.updated_at, filter.updated_after, filter.updated_before)
+    {
+        return false;
+    }
+
+    let doc = SearchDocument::new(&record.dashboard);
+    if !filter.plot_types.is_empty()
+        && !doc
+            .plot_types
+            .iter()
+            .any(|t| filter.plot_types.iter().any(|f| f == t))
+    {
+        return false;
+    }
+
+    // Every query word has to prefix some word of the indexed text
+    let text = [
+        meta.xp_name.iter().cloned().collect::<Vec<_>>(),
+        meta.user.iter().cloned().collect(),
+        meta.tags.clone(),
+        doc.titles,
+        doc.descriptions,
+        doc.labels,
+        doc.tabs,
+    ]
+    .concat()
+    .join(" ")
+    .to_lowercase();
+    let tokens: Vec<&str> = text.split(|c: char| !c.is_alphanumeric()).collect();
+    filter.words.iter().all(|word| {
+        word.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .all(|w| tokens.iter().any(|t| t.starts_with(w)))
+    })
 }
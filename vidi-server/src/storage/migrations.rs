@@ -0,0 +1,321 @@
+//! Ordered schema migrations for the SQLite store
+//!
+//! Migrations run once each, in order, and are recorded in `schema_migrations`.
+//! Databases created before migrations were recorded are upgraded the same
+//! way: a migration whose change is already in place is recorded without
+//! running it again.
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::{Row, SqlitePool};
+use tracing::{info, warn};
+
+/// A schema change, kept as a SQL file in `vidi-server/migrations`
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    sql: &'static str,
+    /// Query returning a non-zero count when the change is already in place
+    /// (only needed for changes that cannot be re-run, like `ADD COLUMN`)
+    applied_if: Option<&'static str>,
+}
+
+/// All migrations, in the order they run
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial",
+        sql: include_str!("../../migrations/001_initial.sql"),
+        applied_if: None,
+    },
+    Migration {
+        version: 2,
+        name: "wasm_status",
+        sql: include_str!("../../migrations/002_wasm_status.sql"),
+        applied_if: Some(
+            "SELECT COUNT(*) FROM pragma_table_info('dashboards') WHERE name = 'wasm_status'",
+        ),
+    },
+    Migration {
+        version: 3,
+        name: "versions",
+        sql: include_str!("../../migrations/003_versions.sql"),
+        applied_if: None,
+    },
+    Migration {
+        version: 4,
+        name: "search",
+        sql: include_str!("../../migrations/004_search.sql"),
+        applied_if: None,
+    },
+];
+
+impl Migration {
+    fn checksum(&self) -> String {
+        Sha256::digest(self.sql.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+/// A migration recorded as applied to a database
+#[derive(Clone, Debug)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub applied_at: DateTime<Utc>,
+    checksum: String,
+}
+
+/// Apply all pending migrations, returning the ones that were applied
+pub async fn run(pool: &SqlitePool) -> anyhow::Result<Vec<&'static Migration>> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            checksum TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    let applied = applied(pool).await?;
+    check_applied(&applied)?;
+
+    let mut newly_applied = Vec::new();
+    for migration in MIGRATIONS {
+        if applied.iter().any(|a| a.version == migration.version) {
+            continue;
+        }
+
+        let mut tx = pool.begin().await?;
+        let in_place = match migration.applied_if {
+            Some(query) => {
+                sqlx::query_scalar::<_, i64>(query)
+                    .fetch_one(&mut *tx)
+                    .await?
+                    > 0
+            }
+            None => false,
+        };
+        if !in_place {
+            sqlx::raw_sql(migration.sql)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "Migration {:03}_{} failed: {}",
+                        migration.version,
+                        migration.name,
+                        e
+                    )
+                })?;
+        }
+        sqlx::query(
+            "INSERT INTO schema_migrations (version, name, checksum, applied_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(migration.version)
+        .bind(migration.name)
+        .bind(migration.checksum())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        info!(
+            "Applied migration {:03}_{}{}",
+            migration.version,
+            migration.name,
+            if in_place { " (already in place)" } else { "" }
+        );
+        newly_applied.push(migration);
+    }
+    Ok(newly_applied)
+}
+
+/// Migrations recorded in the database, oldest first
+pub async fn applied(pool: &SqlitePool) -> anyhow::Result<Vec<AppliedMigration>> {
+    let exists: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations'",
+    )
+    .fetch_one(pool)
+    .await?;
+    if exists == 0 {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query("SELECT * FROM schema_migrations ORDER BY version")
+        .fetch_all(pool)
+        .await?;
+    rows.into_iter()
+        .map(|row| {
+            let applied_at: String = row.get("applied_at");
+            Ok(AppliedMigration {
+                version: row.get("version"),
+                name: row.get("name"),
+                applied_at: DateTime::parse_from_rfc3339(&applied_at)?.with_timezone(&Utc),
+                checksum: row.get("checksum"),
+            })
+        })
+        .collect()
+}
+
+/// Refuse databases from a newer server, warn about edited migrations
+fn check_applied(applied: &[AppliedMigration]) -> anyhow::Result<()> {
+    for record in applied {
+        match MIGRATIONS.iter().find(|m| m.version == record.version) {
+            None => anyhow::bail!(
+                "Database has migration {:03}_{} which this server does not know; \
+                 it was created by a newer vidi-server",
+                record.version,
+                record.name
+            ),
+            Some(migration) if migration.checksum() != record.checksum => warn!(
+                "Migration {:03}_{} changed since it was applied",
+                record.version, record.name
+            ),
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+    use vidi::prelude::Dashboard;
+
+    use super::*;
+    use crate::models::{SearchFilter, WasmStatus};
+    use crate::storage::sqlite::SqliteStore;
+    use crate::storage::{DashboardStore, TempDb};
+
+    /// Schema written by servers before migrations were recorded
+    const LEGACY_SCHEMA: &str = r#"
+        CREATE TABLE dashboards (
+            id TEXT PRIMARY KEY,
+            xp_name TEXT,
+            user TEXT,
+            tags TEXT NOT NULL DEFAULT '[]',
+            permanent INTEGER NOT NULL DEFAULT 0,
+            ttl INTEGER,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            last_accessed_at TEXT NOT NULL,
+            dashboard_json TEXT NOT NULL,
+            wasm_status TEXT NOT NULL DEFAULT 'pending',
+            wasm_error TEXT
+        );
+        CREATE INDEX idx_dashboards_xp_name ON dashboards(xp_name);
+        CREATE INDEX idx_dashboards_user ON dashboards(user);
+        CREATE INDEX idx_dashboards_permanent ON dashboards(permanent);
+        CREATE INDEX idx_dashboards_updated_at ON dashboards(updated_at);
+    "#;
+
+    async fn create_legacy(db: &TempDb, schema: &str) -> Uuid {
+        let pool = SqlitePool::connect(&format!("sqlite:{}?mode=rwc", db.path()))
+            .await
+            .unwrap();
+        sqlx::raw_sql(schema).execute(&pool).await.unwrap();
+
+        let id = Uuid::new_v4();
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            r#"
+            INSERT INTO dashboards (
+                id, xp_name, user, tags, permanent, ttl,
+                created_at, updated_at, last_accessed_at, dashboard_json
+            ) VALUES (?, 'legacy-run', 'ada', '["old"]', 1, NULL, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(&now)
+        .bind(&now)
+        .bind(&now)
+        .bind(serde_json::to_string(&Dashboard::default()).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool.close().await;
+        id
+    }
+
+    fn versions(applied: &[AppliedMigration]) -> Vec<i64> {
+        applied.iter().map(|a| a.version).collect()
+    }
+
+    #[tokio::test]
+    async fn upgrades_legacy_database() {
+        let db = TempDb::new();
+        let id = create_legacy(&db, LEGACY_SCHEMA).await;
+
+        let store = SqliteStore::new(db.path()).await.unwrap();
+        let newly_applied = store.run_migrations().await.unwrap();
+        assert_eq!(newly_applied.len(), MIGRATIONS.len());
+        assert_eq!(
+            versions(&store.applied_migrations().await.unwrap()),
+            vec![1, 2, 3, 4]
+        );
+
+        // Existing data survives and is backfilled into history and search
+        let record = store.get(id).await.unwrap().unwrap();
+        assert_eq!(record.meta.xp_name.as_deref(), Some("legacy-run"));
+        assert_eq!(record.meta.tags, vec!["old".to_string()]);
+        assert_eq!(store.list_versions(id).await.unwrap().len(), 1);
+        let results = store
+            .search(&SearchFilter {
+                words: vec!["legacy".into()],
+                limit: 10,
+                ..SearchFilter::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(results.total, 1);
+
+        // Running again is a no-op
+        assert!(store.run_migrations().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn adds_columns_missing_from_initial_schema() {
+        let db = TempDb::new();
+        let initial = MIGRATIONS[0].sql;
+        let id = create_legacy(&db, initial).await;
+
+        let store = SqliteStore::new(db.path()).await.unwrap();
+        store.run_migrations().await.unwrap();
+
+        let record = store.get(id).await.unwrap().unwrap();
+        assert_eq!(record.meta.wasm_status, WasmStatus::Pending);
+        store
+            .update_wasm_status(id, WasmStatus::Failed, Some("boom".into()))
+            .await
+            .unwrap();
+        let record = store.get(id).await.unwrap().unwrap();
+        assert_eq!(record.meta.wasm_status, WasmStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn refuses_newer_database() {
+        let db = TempDb::new();
+        let store = SqliteStore::new(db.path()).await.unwrap();
+        store.run_migrations().await.unwrap();
+
+        let pool = SqlitePool::connect(&format!("sqlite:{}", db.path()))
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO schema_migrations (version, name, checksum, applied_at) VALUES (99, 'future', '', ?)",
+        )
+        .bind(Utc::now().to_rfc3339())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert!(run(&pool).await.is_err());
+    }
+}
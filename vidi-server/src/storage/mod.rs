@@ -1,19 +1,27 @@
 //! Dashboard storage backends
 
 pub mod memory;
+pub mod migrations;
 pub mod sqlite;
 
 use async_trait::async_trait;
 use uuid::Uuid;
 
 use crate::error::Result;
-use crate::models::{DashboardRecord, DashboardSummary, ListQuery, UpdateMetaRequest, WasmStatus};
+use crate::models::{
+    DashboardRecord, DashboardSummary, DashboardVersion, ListQuery, SearchFilter, SearchResults,
+    UpdateMetaRequest, VersionSummary, WasmStatus,
+};
 
 /// Trait for dashboard storage backends
 #[async_trait]
 pub trait DashboardStore: Send + Sync {
-    /// Create a new dashboard
-    async fn create(&self, record: DashboardRecord) -> Result<DashboardRecord>;
+    /// Create a new dashboard, recording its data as the first version
+    async fn create(
+        &self,
+        record: DashboardRecord,
+        author: Option<String>,
+    ) -> Result<DashboardRecord>;
 
     /// Get a dashboard by ID
     async fn get(&self, id: Uuid) -> Result<Option<DashboardRecord>>;
@@ -21,8 +29,16 @@ pub trait DashboardStore: Send + Sync {
     /// List dashboards with optional filters
     async fn list(&self, query: &ListQuery) -> Result<Vec<DashboardSummary>>;
 
-    /// Replace dashboard data (full update)
-    async fn replace(&self, id: Uuid, record: DashboardRecord) -> Result<DashboardRecord>;
+    /// Search dashboards by text and structured filters
+    async fn search(&self, filter: &SearchFilter) -> Result<SearchResults>;
+
+    /// Replace dashboard data (full update), recording it as a new version
+    async fn replace(
+        &self,
+        id: Uuid,
+        record: DashboardRecord,
+        author: Option<String>,
+    ) -> Result<DashboardRecord>;
 
     /// Update dashboard metadata only
     async fn update_meta(&self, id: Uuid, update: UpdateMetaRequest) -> Result<DashboardRecord>;
@@ -33,8 +49,9 @@ pub trait DashboardStore: Send + Sync {
     /// Touch a dashboard (update last_accessed_at, extend TTL)
     async fn touch(&self, id: Uuid) -> Result<()>;
 
-    /// Cleanup expired dashboards, skipping those with active connections
-    async fn cleanup_expired(&self, active_ids: &[Uuid]) -> Result<u64>;
+    /// Cleanup expired dashboards, skipping those with active connections,
+    /// returning the IDs of the deleted ones
+    async fn cleanup_expired(&self, active_ids: &[Uuid]) -> Result<Vec<Uuid>>;
 
     /// Update WASM compilation status
     async fn update_wasm_status(
@@ -46,4 +63,32 @@ pub trait DashboardStore: Send + Sync {
 
     /// Get dashboard JSON (for WASM compilation)
     async fn get_dashboard_json(&self, id: Uuid) -> Result<Option<String>>;
+
+    /// List the recorded versions of a dashboard, oldest first
+    async fn list_versions(&self, id: Uuid) -> Result<Vec<VersionSummary>>;
+
+    /// Get a single version of a dashboard
+    async fn get_version(&self, id: Uuid, version: u32) -> Result<Option<DashboardVersion>>;
+}
+
+/// Database file removed when the test ends
+#[cfg(test)]
+pub(crate) struct TempDb(std::path::PathBuf);
+
+#[cfg(test)]
+impl TempDb {
+    pub(crate) fn new() -> Self {
+        Self(std::env::temp_dir().join(format!("vidi-test-{}.db", Uuid::new_v4())))
+    }
+
+    pub(crate) fn path(&self) -> &str {
+        self.0.to_str().unwrap()
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDb {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
 }
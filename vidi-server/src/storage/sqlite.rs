@@ -2,14 +2,17 @@
 
 use async_trait::async_trait;
 use chrono::Utc;
-use sqlx::{Row, SqlitePool, sqlite::SqlitePoolOptions};
+use sqlx::{Row, SqliteConnection, SqlitePool, sqlite::SqlitePoolOptions};
 use uuid::Uuid;
+use vidi::prelude::Dashboard;
 
 use crate::error::{Result, ServerError};
 use crate::models::{
-    DashboardMeta, DashboardRecord, DashboardSummary, ListQuery, UpdateMetaRequest, WasmStatus,
+    DashboardMeta, DashboardRecord, DashboardSummary, DashboardVersion, ListQuery, SearchDocument,
+    SearchFilter, SearchHit, SearchResults, TagMode, UpdateMetaRequest, VersionSummary, WasmStatus,
 };
 use crate::storage::DashboardStore;
+use crate::storage::migrations::{self, AppliedMigration, Migration};
 
 pub struct SqliteStore {
     pool: SqlitePool,
@@ -26,65 +29,120 @@ impl SqliteStore {
         Ok(Self { pool })
     }
 
-    pub async fn run_migrations(&self) -> anyhow::Result<()> {
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS dashboards (
-                id TEXT PRIMARY KEY,
-                xp_name TEXT,
-                user TEXT,
-                tags TEXT NOT NULL DEFAULT '[]',
-                permanent INTEGER NOT NULL DEFAULT 0,
-                ttl INTEGER,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                last_accessed_at TEXT NOT NULL,
-                dashboard_json TEXT NOT NULL,
-                wasm_status TEXT NOT NULL DEFAULT 'pending',
-                wasm_error TEXT
-            )
-            "#,
+    /// Bring the schema up to date, returning the migrations that were applied
+    pub async fn run_migrations(&self) -> anyhow::Result<Vec<&'static Migration>> {
+        let applied = migrations::run(&self.pool).await?;
+
+        // Index dashboards stored before search existed
+        let rows = sqlx::query(
+            "SELECT * FROM dashboards WHERE id NOT IN (SELECT dashboard_id FROM dashboard_search)",
         )
-        .execute(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
+        let mut conn = self.pool.acquire().await?;
+        for row in rows {
+            let record = self.row_to_record(row)?;
+            Self::index_search(&mut conn, &record).await?;
+        }
 
-        // Create indices for common queries
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_dashboards_xp_name ON dashboards(xp_name)")
-            .execute(&self.pool)
-            .await?;
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_dashboards_user ON dashboards(user)")
-            .execute(&self.pool)
-            .await?;
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_dashboards_permanent ON dashboards(permanent)")
-            .execute(&self.pool)
+        Ok(applied)
+    }
+
+    /// Migrations recorded in the database, oldest first
+    pub async fn applied_migrations(&self) -> anyhow::Result<Vec<AppliedMigration>> {
+        migrations::applied(&self.pool).await
+    }
+
+    /// Replace the search index entry of a dashboard
+    async fn index_search(conn: &mut SqliteConnection, record: &DashboardRecord) -> Result<()> {
+        let doc = SearchDocument::new(&record.dashboard);
+        let id = record.meta.id.to_string();
+
+        sqlx::query("DELETE FROM dashboard_search WHERE dashboard_id = ?")
+            .bind(&id)
+            .execute(&mut *conn)
             .await?;
         sqlx::query(
-            "CREATE INDEX IF NOT EXISTS idx_dashboards_updated_at ON dashboards(updated_at)",
+            r#"
+            INSERT INTO dashboard_search (
+                dashboard_id, xp_name, user, tags, titles, descriptions, labels, tabs, plot_types
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
         )
-        .execute(&self.pool)
+        .bind(&id)
+        .bind(&record.meta.xp_name)
+        .bind(&record.meta.user)
+        .bind(record.meta.tags.join(" "))
+        .bind(doc.titles.join("; "))
+        .bind(doc.descriptions.join("; "))
+        .bind(doc.labels.join("; "))
+        .bind(doc.tabs.join("; "))
+        .bind(doc.plot_types.join(" "))
+        .execute(&mut *conn)
         .await?;
 
-        // Migration: Add wasm_status and wasm_error columns if they don't exist
-        // Check if wasm_status column exists by trying to select it
-        let has_wasm_status = sqlx::query("SELECT wasm_status FROM dashboards LIMIT 1")
-            .fetch_optional(&self.pool)
-            .await
-            .is_ok();
+        Ok(())
+    }
 
-        if !has_wasm_status {
-            let _ = sqlx::query(
-                "ALTER TABLE dashboards ADD COLUMN wasm_status TEXT NOT NULL DEFAULT 'pending'",
+    /// Record a new version of a dashboard's data, numbered after the latest one
+    async fn insert_version(
+        conn: &mut SqliteConnection,
+        id: Uuid,
+        author: Option<String>,
+        dashboard: &Dashboard,
+    ) -> Result<()> {
+        let dashboard_json = serde_json::to_string(dashboard)?;
+        let plot_count =
+            dashboard.plots.len() + dashboard.tabs.iter().map(|t| t.plots.len()).sum::<usize>();
+
+        // Numbering in the same statement keeps concurrent writers from colliding
+        sqlx::query(
+            r#"
+            INSERT INTO dashboard_versions (
+                dashboard_id, version, author, created_at, plot_count, dashboard_json
             )
-            .execute(&self.pool)
-            .await;
-            let _ = sqlx::query("ALTER TABLE dashboards ADD COLUMN wasm_error TEXT")
-                .execute(&self.pool)
-                .await;
-        }
+            SELECT ?, COALESCE(MAX(version), 0) + 1, ?, ?, ?, ?
+            FROM dashboard_versions WHERE dashboard_id = ?
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(&author)
+        .bind(Utc::now().to_rfc3339())
+        .bind(plot_count as i64)
+        .bind(&dashboard_json)
+        .bind(id.to_string())
+        .execute(conn)
+        .await?;
 
         Ok(())
     }
 
+    /// Read a dashboard through the given connection, e.g. inside a transaction
+    async fn fetch_record(
+        &self,
+        conn: &mut SqliteConnection,
+        id: Uuid,
+    ) -> Result<Option<DashboardRecord>> {
+        let row = sqlx::query("SELECT * FROM dashboards WHERE id = ?")
+            .bind(id.to_string())
+            .fetch_optional(conn)
+            .await?;
+
+        row.map(|r| self.row_to_record(r)).transpose()
+    }
+
+    fn row_to_version_summary(&self, row: &sqlx::sqlite::SqliteRow) -> Result<VersionSummary> {
+        let created_at_str: String = row.get("created_at");
+        Ok(VersionSummary {
+            version: row.get::<i64, _>("version") as u32,
+            author: row.get("author"),
+            created_at: chrono::DateTime::parse_from_rfc3339(&created_at_str)
+                .map_err(|e| ServerError::Internal(e.to_string()))?
+                .with_timezone(&Utc),
+            plot_count: row.get::<i64, _>("plot_count") as usize,
+        })
+    }
+
     fn row_to_record(&self, row: sqlx::sqlite::SqliteRow) -> Result<DashboardRecord> {
         let id_str: String = row.get("id");
         let tags_json: String = row.get("tags");
@@ -125,10 +183,15 @@ impl SqliteStore {
 
 #[async_trait]
 impl DashboardStore for SqliteStore {
-    async fn create(&self, record: DashboardRecord) -> Result<DashboardRecord> {
+    async fn create(
+        &self,
+        record: DashboardRecord,
+        author: Option<String>,
+    ) -> Result<DashboardRecord> {
         let tags_json = serde_json::to_string(&record.meta.tags)?;
         let dashboard_json = serde_json::to_string(&record.dashboard)?;
 
+        let mut tx = self.pool.begin().await?;
         sqlx::query(
             r#"
             INSERT INTO dashboards (
@@ -150,22 +213,18 @@ impl DashboardStore for SqliteStore {
         .bind(&dashboard_json)
         .bind(record.meta.wasm_status.as_str())
         .bind(&record.meta.wasm_error)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
+        Self::insert_version(&mut tx, record.meta.id, author, &record.dashboard).await?;
+        Self::index_search(&mut tx, &record).await?;
+        tx.commit().await?;
 
         Ok(record)
     }
 
     async fn get(&self, id: Uuid) -> Result<Option<DashboardRecord>> {
-        let row = sqlx::query("SELECT * FROM dashboards WHERE id = ?")
-            .bind(id.to_string())
-            .fetch_optional(&self.pool)
-            .await?;
-
-        match row {
-            Some(r) => Ok(Some(self.row_to_record(r)?)),
-            None => Ok(None),
-        }
+        let mut conn = self.pool.acquire().await?;
+        self.fetch_record(&mut conn, id).await
     }
 
     async fn list(&self, query: &ListQuery) -> Result<Vec<DashboardSummary>> {
@@ -220,11 +279,125 @@ impl DashboardStore for SqliteStore {
         Ok(results)
     }
 
-    async fn replace(&self, id: Uuid, record: DashboardRecord) -> Result<DashboardRecord> {
+    async fn search(&self, filter: &SearchFilter) -> Result<SearchResults> {
+        let mut conditions: Vec<String> = vec![];
+        let mut params: Vec<String> = vec![];
+
+        let from = match match_expression(filter) {
+            Some(expression) => {
+                conditions.push("dashboard_search MATCH ?".into());
+                params.push(expression);
+                "dashboard_search JOIN dashboards d ON d.id = dashboard_search.dashboard_id"
+            }
+            None => "dashboards d",
+        };
+
+        if let Some(xp_name) = &filter.xp_name {
+            conditions.push("d.xp_name = ?".into());
+            params.push(xp_name.clone());
+        }
+
+        if let Some(user) = &filter.user {
+            conditions.push("d.user = ?".into());
+            params.push(user.clone());
+        }
+
+        if !filter.tags.is_empty() {
+            match filter.tag_mode {
+                TagMode::All => {
+                    for tag in &filter.tags {
+                        conditions.push(
+                            "EXISTS (SELECT 1 FROM json_each(d.tags) WHERE value = ?)".into(),
+                        );
+                        params.push(tag.clone());
+                    }
+                }
+                TagMode::Any => {
+                    let placeholders = vec!["?"; filter.tags.len()].join(", ");
+                    conditions.push(format!(
+                        "EXISTS (SELECT 1 FROM json_each(d.tags) WHERE value IN ({}))",
+                        placeholders
+                    ));
+                    params.extend(filter.tags.iter().cloned());
+                }
+            }
+        }
+
+        let bounds = [
+            ("d.created_at", ">=", filter.created_after),
+            ("d.created_at", "<=", filter.created_before),
+            ("d.updated_at", ">=", filter.updated_after),
+            ("d.updated_at", "<=", filter.updated_before),
+        ];
+        for (column, op, bound) in bounds {
+            if let Some(bound) = bound {
+                conditions.push(format!("julianday({}) {} julianday(?)", column, op));
+                params.push(bound.to_rfc3339());
+            }
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        };
+
+        let count_sql = format!("SELECT COUNT(*) AS total FROM {}{}", from, where_clause);
+        let mut q = sqlx::query(&count_sql);
+        for param in &params {
+            q = q.bind(param);
+        }
+        let total: i64 = q.fetch_one(&self.pool).await?.get("total");
+
+        // Rank by relevance for text queries, most recent first otherwise
+        let (snippet, order) = if filter.words.is_empty() {
+            ("NULL", "d.updated_at DESC")
+        } else {
+            (
+                "snippet(dashboard_search, -1, '[', ']', '...', 12)",
+                "bm25(dashboard_search)",
+            )
+        };
+        let sql = format!(
+            "SELECT d.*, {} AS snippet FROM {}{} ORDER BY {} LIMIT {} OFFSET {}",
+            snippet, from, where_clause, order, filter.limit, filter.offset
+        );
+        let mut q = sqlx::query(&sql);
+        for param in &params {
+            q = q.bind(param);
+        }
+
+        let rows = q.fetch_all(&self.pool).await?;
+        let mut results = vec![];
+
+        for row in rows {
+            let snippet: Option<String> = row.get("snippet");
+            let record = self.row_to_record(row)?;
+            results.push(SearchHit {
+                summary: DashboardSummary::from(&record),
+                snippet,
+            });
+        }
+
+        Ok(SearchResults {
+            total: total as u64,
+            limit: filter.limit,
+            offset: filter.offset,
+            results,
+        })
+    }
+
+    async fn replace(
+        &self,
+        id: Uuid,
+        record: DashboardRecord,
+        author: Option<String>,
+    ) -> Result<DashboardRecord> {
         let tags_json = serde_json::to_string(&record.meta.tags)?;
         let dashboard_json = serde_json::to_string(&record.dashboard)?;
         let now = Utc::now();
 
+        let mut tx = self.pool.begin().await?;
         let result = sqlx::query(
             r#"
             UPDATE dashboards SET
@@ -246,17 +419,21 @@ impl DashboardStore for SqliteStore {
         .bind(now.to_rfc3339())
         .bind(&dashboard_json)
         .bind(id.to_string())
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
         if result.rows_affected() == 0 {
             return Err(ServerError::NotFound(id.to_string()));
         }
-
-        // Return updated record
-        self.get(id)
+        Self::insert_version(&mut tx, id, author, &record.dashboard).await?;
+        let updated = self
+            .fetch_record(&mut tx, id)
             .await?
-            .ok_or_else(|| ServerError::NotFound(id.to_string()))
+            .ok_or_else(|| ServerError::NotFound(id.to_string()))?;
+        Self::index_search(&mut tx, &updated).await?;
+        tx.commit().await?;
+
+        Ok(updated)
     }
 
     async fn update_meta(&self, id: Uuid, update: UpdateMetaRequest) -> Result<DashboardRecord> {
@@ -278,6 +455,7 @@ impl DashboardStore for SqliteStore {
         let tags_json = serde_json::to_string(&new_tags)?;
         let now = Utc::now();
 
+        let mut tx = self.pool.begin().await?;
         sqlx::query(
             r#"
             UPDATE dashboards SET
@@ -297,12 +475,17 @@ impl DashboardStore for SqliteStore {
         .bind(new_ttl.map(|t| t as i64))
         .bind(now.to_rfc3339())
         .bind(id.to_string())
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
-        self.get(id)
+        let updated = self
+            .fetch_record(&mut tx, id)
             .await?
-            .ok_or_else(|| ServerError::NotFound(id.to_string()))
+            .ok_or_else(|| ServerError::NotFound(id.to_string()))?;
+        Self::index_search(&mut tx, &updated).await?;
+        tx.commit().await?;
+
+        Ok(updated)
     }
 
     async fn delete(&self, id: Uuid) -> Result<bool> {
@@ -310,6 +493,14 @@ impl DashboardStore for SqliteStore {
             .bind(id.to_string())
             .execute(&self.pool)
             .await?;
+        sqlx::query("DELETE FROM dashboard_versions WHERE dashboard_id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM dashboard_search WHERE dashboard_id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
 
         Ok(result.rows_affected() > 0)
     }
@@ -325,7 +516,7 @@ impl DashboardStore for SqliteStore {
         Ok(())
     }
 
-    async fn cleanup_expired(&self, active_ids: &[Uuid]) -> Result<u64> {
+    async fn cleanup_expired(&self, active_ids: &[Uuid]) -> Result<Vec<Uuid>> {
         let now = Utc::now();
 
         // Build query to find expired dashboards not in active list
@@ -338,7 +529,7 @@ impl DashboardStore for SqliteStore {
         .fetch_all(&self.pool)
         .await?;
 
-        let mut deleted = 0u64;
+        let mut deleted = Vec::new();
 
         for row in rows {
             let id_str: String = row.get("id");
@@ -354,11 +545,11 @@ impl DashboardStore for SqliteStore {
             if let Ok(last_accessed) = chrono::DateTime::parse_from_rfc3339(&last_accessed_str) {
                 let expiry = last_accessed + chrono::Duration::seconds(ttl);
                 if now > expiry.with_timezone(&Utc) {
-                    sqlx::query("DELETE FROM dashboards WHERE id = ?")
-                        .bind(&id_str)
-                        .execute(&self.pool)
-                        .await?;
-                    deleted += 1;
+                    let id = Uuid::parse_str(&id_str)
+                        .map_err(|e| ServerError::Internal(e.to_string()))?;
+                    if self.delete(id).await? {
+                        deleted.push(id);
+                    }
                 }
             }
         }
@@ -390,4 +581,269 @@ impl DashboardStore for SqliteStore {
 
         Ok(row.map(|r| r.get("dashboard_json")))
     }
+
+    async fn list_versions(&self, id: Uuid) -> Result<Vec<VersionSummary>> {
+        let rows = sqlx::query(
+            "SELECT version, author, created_at, plot_count FROM dashboard_versions \
+             WHERE dashboard_id = ? ORDER BY version ASC",
+        )
+        .bind(id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| self.row_to_version_summary(row))
+            .collect()
+    }
+
+    async fn get_version(&self, id: Uuid, version: u32) -> Result<Option<DashboardVersion>> {
+        let row =
+            sqlx::query("SELECT * FROM dashboard_versions WHERE dashboard_id = ? AND version = ?")
+                .bind(id.to_string())
+                .bind(version as i64)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let summary = self.row_to_version_summary(&row)?;
+        let dashboard_json: String = row.get("dashboard_json");
+
+        Ok(Some(DashboardVersion {
+            dashboard_id: id,
+            version: summary.version,
+            author: summary.author,
+            created_at: summary.created_at,
+            dashboard: serde_json::from_str(&dashboard_json)?,
+        }))
+    }
+}
+
+/// FTS5 query for the text and plot type parts of a filter, if it has any
+///
+/// Every term is a quoted string, so FTS5 operators and column filters typed
+/// by users (`NEAR`, `-`, `:`, `^`, ...) are matched as plain text.
+fn match_expression(filter: &SearchFilter) -> Option<String> {
+    // Free text covers every column but the plot types, which have their own filter
+    let mut terms: Vec<String> = vec![];
+    let words: Vec<String> = filter
+        .words
+        .iter()
+        .map(|w| format!("{}*", quoted(w)))
+        .collect();
+    if !words.is_empty() {
+        terms.push(format!(
+            "{{xp_name user tags titles descriptions labels tabs}} : ({})",
+            words.join(" ")
+        ));
+    }
+    if !filter.plot_types.is_empty() {
+        let types: Vec<String> = filter.plot_types.iter().map(|t| quoted(t)).collect();
+        terms.push(format!("plot_types : ({})", types.join(" OR ")));
+    }
+
+    (!terms.is_empty()).then(|| terms.join(" AND "))
+}
+
+/// An FTS5 string, with embedded quotes doubled
+fn quoted(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec2;
+    use vidi::prelude::dash;
+
+    use super::*;
+    use crate::models::SearchQuery;
+    use crate::storage::TempDb;
+
+    async fn store(db: &TempDb) -> SqliteStore {
+        let store = SqliteStore::new(db.path()).await.unwrap();
+        store.run_migrations().await.unwrap();
+        store
+    }
+
+    fn record(title: &str, tags: &[&str]) -> DashboardRecord {
+        let dashboard = dash()
+            .add_2d(|p| p.line(vec![Vec2::ZERO, Vec2::ONE], None).title(title))
+            .build();
+        let tags = tags.iter().map(|t| t.to_string()).collect();
+        DashboardRecord::new(dashboard).with_meta(DashboardMeta::new().with_tags(tags))
+    }
+
+    fn filter(q: &str) -> SearchFilter {
+        SearchFilter::try_from(SearchQuery {
+            q: Some(q.into()),
+            limit: 10,
+            ..SearchQuery::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn match_expression_quotes_every_term() {
+        assert_eq!(match_expression(&SearchFilter::default()), None);
+
+        let text = match_expression(&filter("Loss a\"b")).unwrap();
+        assert_eq!(
+            text,
+            "{xp_name user tags titles descriptions labels tabs} : (\"loss\"* \"a\"\"b\"*)"
+        );
+
+        let types = match_expression(&SearchFilter {
+            plot_types: vec!["graph2d".into(), "pie".into()],
+            ..SearchFilter::default()
+        })
+        .unwrap();
+        assert_eq!(types, "plot_types : (\"graph2d\" OR \"pie\")");
+    }
+
+    #[tokio::test]
+    async fn search_treats_fts_syntax_as_text() {
+        let db = TempDb::new();
+        let store = store(&db).await;
+        store
+            .create(record("Training loss", &["nightly"]), None)
+            .await
+            .unwrap();
+
+        let total = |q: &'static str| {
+            let store = &store;
+            async move { store.search(&filter(q)).await.unwrap().total }
+        };
+        // Operators and column filters are plain text, bare punctuation is ignored
+        for q in [
+            "train", "(loss", "loss)", "-loss", "^loss", "loss*", "\"loss\"", "\"", "- * :",
+        ] {
+            assert_eq!(total(q).await, 1, "query {:?}", q);
+        }
+        for q in [
+            "titles:loss",
+            "NEAR",
+            "loss AND",
+            "OR loss",
+            "NOT",
+            "{tags}",
+        ] {
+            assert_eq!(total(q).await, 0, "query {:?}", q);
+        }
+    }
+
+    #[tokio::test]
+    async fn search_combines_filters() {
+        let db = TempDb::new();
+        let store = store(&db).await;
+        store
+            .create(record("Loss", &["a", "b"]), None)
+            .await
+            .unwrap();
+        store.create(record("Reward", &["b"]), None).await.unwrap();
+
+        let tags = |tags: &str, tag_mode| SearchFilter {
+            tags: tags.split(',').map(str::to_string).collect(),
+            tag_mode,
+            limit: 10,
+            ..SearchFilter::default()
+        };
+        let total = |filter: SearchFilter| {
+            let store = &store;
+            async move { store.search(&filter).await.unwrap().total }
+        };
+        assert_eq!(total(tags("a,b", TagMode::All)).await, 1);
+        assert_eq!(total(tags("a,b", TagMode::Any)).await, 2);
+        assert_eq!(
+            total(SearchFilter {
+                words: vec!["reward".into()],
+                ..tags("b", TagMode::All)
+            })
+            .await,
+            1
+        );
+        assert_eq!(
+            total(SearchFilter {
+                plot_types: vec!["pie".into()],
+                ..tags("b", TagMode::All)
+            })
+            .await,
+            0
+        );
+
+        let page = store
+            .search(&SearchFilter {
+                limit: 1,
+                ..SearchFilter::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!((page.total, page.results.len()), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn writes_update_history_and_index_together() {
+        let db = TempDb::new();
+        let store = store(&db).await;
+        let created = store
+            .create(record("Loss", &[]), Some("ada".into()))
+            .await
+            .unwrap();
+        let id = created.meta.id;
+
+        store
+            .replace(id, record("Reward", &[]), Some("grace".into()))
+            .await
+            .unwrap();
+        let versions = store.list_versions(id).await.unwrap();
+        let authors: Vec<_> = versions.iter().map(|v| v.author.as_deref()).collect();
+        assert_eq!(authors, vec![Some("ada"), Some("grace")]);
+        assert_eq!(store.search(&filter("loss")).await.unwrap().total, 0);
+        assert_eq!(store.search(&filter("reward")).await.unwrap().total, 1);
+
+        // A failed replace leaves neither a version nor an index entry behind
+        let missing = Uuid::new_v4();
+        assert!(
+            store
+                .replace(missing, record("Lost", &[]), None)
+                .await
+                .is_err()
+        );
+        assert!(store.list_versions(missing).await.unwrap().is_empty());
+        assert_eq!(store.search(&filter("lost")).await.unwrap().total, 0);
+    }
+
+    #[tokio::test]
+    async fn cleanup_returns_the_expired_ids() {
+        let db = TempDb::new();
+        let store = store(&db).await;
+        let expired = |title| {
+            let mut record = record(title, &[]);
+            record.meta = DashboardMeta::new().with_ttl(60);
+            record.meta.last_accessed_at = Utc::now() - chrono::Duration::hours(1);
+            record
+        };
+        let stale = store.create(expired("stale"), None).await.unwrap().meta.id;
+        let watched = store
+            .create(expired("watched"), None)
+            .await
+            .unwrap()
+            .meta
+            .id;
+        let kept = store
+            .create(record("kept", &[]), None)
+            .await
+            .unwrap()
+            .meta
+            .id;
+
+        assert_eq!(
+            store.cleanup_expired(&[watched]).await.unwrap(),
+            vec![stale]
+        );
+        assert!(store.get(stale).await.unwrap().is_none());
+        assert!(store.list_versions(stale).await.unwrap().is_empty());
+        assert!(store.get(watched).await.unwrap().is_some());
+        assert!(store.get(kept).await.unwrap().is_some());
+    }
 }
@@ -1,17 +1,61 @@
 //! WASM Compilation Service
 //!
 //! Compiles per-dashboard WASM binaries with baked-in dashboard configuration.
-
-use std::path::PathBuf;
+//! Only used when the server runs with `--wasm-mode baked`; the default viewer
+//! mode serves one prebuilt bundle that loads dashboard JSON at runtime.
+//!
+//! Compilations go through a job queue served by `max_concurrent` workers:
+//! - every job gets its own build directory for the input JSON and bindgen output
+//! - every worker gets its own cargo target directory, so concurrent builds never
+//!   overwrite each other's artifacts while still building incrementally
+//! - artifacts are cached by a hash of the dashboard JSON, so identical
+//!   dashboards reuse a previous build; the cache is kept under a size limit
+//!   by removing the least recently used builds
+//! - a job is cancelled (and its child processes killed) when its dashboard is
+//!   deleted or replaced
+//! - status changes are published as [`CompileEvent`]s
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
 use tokio::process::Command;
-use tracing::{error, info, warn};
+use tokio::sync::{broadcast, mpsc, watch};
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::error::{Result, ServerError};
+use crate::models::WasmStatus;
+
+const EVENT_CAPACITY: usize = 256;
+
+/// A compilation status change for a dashboard
+#[derive(Clone, Debug)]
+pub struct CompileEvent {
+    pub id: Uuid,
+    pub status: WasmStatus,
+    pub error: Option<String>,
+}
+
+/// A queued compilation
+struct Job {
+    id: Uuid,
+    /// Distinguishes this job from earlier jobs for the same dashboard
+    job_id: Uuid,
+    hash: String,
+    dashboard_json: String,
+    cancel: watch::Receiver<bool>,
+}
+
+/// Bookkeeping for a queued or running job
+struct ActiveJob {
+    job_id: Uuid,
+    cancel: watch::Sender<bool>,
+}
 
 /// WASM Compiler service
 pub struct WasmCompiler {
@@ -19,12 +63,19 @@ pub struct WasmCompiler {
     wasm_out_dir: PathBuf,
     /// Path to the vidi workspace root
     vidi_workspace: PathBuf,
-    /// Path to the dashboard template crate
-    template_crate: PathBuf,
-    /// Concurrency limiter (only one compilation at a time by default)
-    compile_semaphore: Arc<tokio::sync::Semaphore>,
-    /// Currently compiling dashboards (to prevent duplicate compilations)
-    compiling: Arc<Mutex<std::collections::HashSet<Uuid>>>,
+    /// Number of queue workers
+    max_concurrent: usize,
+    /// Size limit of the build cache in bytes (0 for no limit)
+    cache_limit: u64,
+    /// Held while reading from or pruning the build cache
+    cache_lock: tokio::sync::Mutex<()>,
+    /// Job queue (taken by `start_workers`)
+    queue_tx: mpsc::UnboundedSender<Job>,
+    queue_rx: Mutex<Option<mpsc::UnboundedReceiver<Job>>>,
+    /// Queued or running jobs by dashboard
+    jobs: Mutex<HashMap<Uuid, ActiveJob>>,
+    /// Status change notifications
+    events: broadcast::Sender<CompileEvent>,
 }
 
 impl WasmCompiler {
@@ -34,23 +85,71 @@ impl WasmCompiler {
     /// * `wasm_out_dir` - Directory where compiled WASM files are stored
     /// * `vidi_workspace` - Path to the vidi workspace root
     /// * `max_concurrent` - Maximum concurrent compilations (default: 1)
-    pub fn new(wasm_out_dir: PathBuf, vidi_workspace: PathBuf, max_concurrent: usize) -> Self {
-        let template_crate = vidi_workspace.join("vidi-server/dashboard-template");
+    /// * `cache_limit` - Size limit of the build cache in bytes (0 for no limit)
+    pub fn new(
+        wasm_out_dir: PathBuf,
+        vidi_workspace: PathBuf,
+        max_concurrent: usize,
+        cache_limit: u64,
+    ) -> Self {
+        let (queue_tx, queue_rx) = mpsc::unbounded_channel();
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
 
         Self {
             wasm_out_dir,
             vidi_workspace,
-            template_crate,
-            compile_semaphore: Arc::new(tokio::sync::Semaphore::new(max_concurrent)),
-            compiling: Arc::new(Mutex::new(std::collections::HashSet::new())),
+            max_concurrent: max_concurrent.max(1),
+            cache_limit,
+            cache_lock: tokio::sync::Mutex::new(()),
+            queue_tx,
+            queue_rx: Mutex::new(Some(queue_rx)),
+            jobs: Mutex::new(HashMap::new()),
+            events,
+        }
+    }
+
+    /// Spawn the queue workers (call once, from within the tokio runtime)
+    pub fn start_workers(self: &Arc<Self>) {
+        let Some(rx) = self.queue_rx.lock().take() else {
+            warn!("WASM compiler workers already started");
+            return;
+        };
+        let rx = Arc::new(tokio::sync::Mutex::new(rx));
+
+        for slot in 0..self.max_concurrent {
+            let compiler = Arc::clone(self);
+            let rx = Arc::clone(&rx);
+            tokio::spawn(async move {
+                loop {
+                    let Some(job) = rx.lock().await.recv().await else {
+                        break;
+                    };
+                    compiler.run_job(slot, job).await;
+                }
+            });
         }
     }
 
+    /// Subscribe to compilation status changes
+    pub fn subscribe(&self) -> broadcast::Receiver<CompileEvent> {
+        self.events.subscribe()
+    }
+
     /// Check if WASM exists for a dashboard
     pub fn wasm_exists(&self, id: Uuid) -> bool {
         self.wasm_path(id).exists()
     }
 
+    /// Check if the prebuilt generic viewer bundle exists
+    pub fn viewer_exists(&self) -> bool {
+        self.wasm_out_dir.join("vidi.js").exists()
+    }
+
+    /// Directory holding the prebuilt generic viewer bundle
+    pub fn viewer_dir(&self) -> &Path {
+        &self.wasm_out_dir
+    }
+
     /// Get the output directory for a dashboard's WASM
     pub fn wasm_dir(&self, id: Uuid) -> PathBuf {
         self.wasm_out_dir.join(id.to_string())
@@ -61,57 +160,204 @@ impl WasmCompiler {
         self.wasm_dir(id).join("vidi.js")
     }
 
-    /// Check if a dashboard is currently being compiled
+    /// Check if a dashboard has a queued or running compilation
     pub fn is_compiling(&self, id: Uuid) -> bool {
-        self.compiling.lock().contains(&id)
+        self.jobs.lock().contains_key(&id)
     }
 
-    /// Compile WASM for a dashboard
-    ///
-    /// This method:
-    /// 1. Writes the dashboard JSON to the template crate
-    /// 2. Runs cargo build for wasm32-unknown-unknown
-    /// 3. Runs wasm-bindgen to generate JS bindings
-    /// 4. Copies output to the dashboard's wasm directory
-    pub async fn compile_dashboard(&self, id: Uuid, dashboard_json: &str) -> Result<()> {
-        // Check if already compiling
+    /// Queue a compilation for a dashboard, cancelling any earlier job for it
+    pub fn enqueue(&self, id: Uuid, dashboard_json: String) -> Result<()> {
+        let (cancel_tx, cancel_rx) = watch::channel(false);
+        let job = Job {
+            id,
+            job_id: Uuid::new_v4(),
+            hash: content_hash(&dashboard_json),
+            dashboard_json,
+            cancel: cancel_rx,
+        };
+
+        let previous = self.jobs.lock().insert(
+            id,
+            ActiveJob {
+                job_id: job.job_id,
+                cancel: cancel_tx,
+            },
+        );
+        if let Some(previous) = previous {
+            debug!("Superseding queued WASM compilation for dashboard {}", id);
+            let _ = previous.cancel.send(true);
+        }
+
+        self.queue_tx
+            .send(job)
+            .map_err(|_| ServerError::Internal("WASM compile queue is closed".into()))?;
+        self.emit(id, WasmStatus::Pending, None);
+        Ok(())
+    }
+
+    /// Cancel a dashboard's queued or running compilation
+    pub fn cancel(&self, id: Uuid) {
+        if let Some(job) = self.jobs.lock().remove(&id) {
+            info!("Cancelling WASM compilation for dashboard {}", id);
+            let _ = job.cancel.send(true);
+        }
+    }
+
+    fn emit(&self, id: Uuid, status: WasmStatus, error: Option<String>) {
+        // No subscribers is fine
+        let _ = self.events.send(CompileEvent { id, status, error });
+    }
+
+    /// Whether `job` is still the current job for its dashboard
+    fn is_current(&self, job: &Job) -> bool {
+        self.jobs
+            .lock()
+            .get(&job.id)
+            .is_some_and(|active| active.job_id == job.job_id)
+    }
+
+    async fn run_job(&self, slot: usize, job: Job) {
+        if *job.cancel.borrow() || !self.is_current(&job) {
+            return;
+        }
+        self.emit(job.id, WasmStatus::Compiling, None);
+
+        let job_dir = self.wasm_out_dir.join(".jobs").join(job.job_id.to_string());
+        let mut cancel = job.cancel.clone();
+        let result = tokio::select! {
+            result = self.compile_job(slot, &job, &job_dir) => Some(result),
+            // Dropping the compile future kills its child processes
+            _ = cancel.wait_for(|cancelled| *cancelled) => None,
+        };
+        let _ = tokio::fs::remove_dir_all(&job_dir).await;
+
+        let Some(result) = result else {
+            info!("WASM compilation for dashboard {} cancelled", job.id);
+            return;
+        };
+
+        // Only the current job may publish results (it may have been
+        // superseded or cancelled while finishing)
         {
-            let mut compiling = self.compiling.lock();
-            if compiling.contains(&id) {
-                return Err(ServerError::Internal(format!(
-                    "Dashboard {} is already being compiled",
-                    id
-                )));
+            let mut jobs = self.jobs.lock();
+            if jobs
+                .get(&job.id)
+                .is_none_or(|active| active.job_id != job.job_id)
+            {
+                return;
             }
-            compiling.insert(id);
+            jobs.remove(&job.id);
         }
 
-        // Acquire semaphore permit for concurrency limiting
-        let _permit = self
-            .compile_semaphore
-            .acquire()
-            .await
-            .map_err(|e| ServerError::Internal(format!("Semaphore error: {}", e)))?;
+        match result {
+            Ok(()) => {
+                info!("WASM compilation complete for dashboard {}", job.id);
+                self.emit(job.id, WasmStatus::Ready, None);
+            }
+            Err(e) => {
+                error!("WASM compilation failed for dashboard {}: {}", job.id, e);
+                self.emit(job.id, WasmStatus::Failed, Some(e.to_string()));
+            }
+        }
+    }
 
-        let result = self.do_compile(id, dashboard_json).await;
+    /// Produce `wasm_dir(id)` from the cache, building into it first if needed
+    async fn compile_job(&self, slot: usize, job: &Job, job_dir: &Path) -> Result<()> {
+        let cache_dir = self.wasm_out_dir.join(".cache");
+        let cached = cache_dir.join(&job.hash);
 
-        // Remove from compiling set
-        self.compiling.lock().remove(&id);
+        {
+            let _cache = self.cache_lock.lock().await;
+            if cached.join("vidi.js").exists() {
+                info!(
+                    "Reusing cached WASM {} for dashboard {}",
+                    &job.hash[..12],
+                    job.id
+                );
+                mark_used(&cached);
+                return self.install(&cached, job.id).await;
+            }
+        }
+
+        self.do_compile(slot, job, job_dir).await?;
 
-        result
+        let _cache = self.cache_lock.lock().await;
+        // Publish the build into the cache atomically
+        tokio::fs::create_dir_all(&cache_dir)
+            .await
+            .map_err(|e| ServerError::Internal(format!("Failed to create cache dir: {}", e)))?;
+        if let Err(e) = tokio::fs::rename(job_dir.join("out"), &cached).await {
+            // Another job may have cached the same content concurrently
+            if !cached.join("vidi.js").exists() {
+                return Err(ServerError::Internal(format!(
+                    "Failed to cache WASM output: {}",
+                    e
+                )));
+            }
+        }
+        mark_used(&cached);
+        self.install(&cached, job.id).await?;
+
+        // Installed bundles are links or copies, so evicting never breaks them
+        if self.cache_limit > 0 {
+            let limit = self.cache_limit;
+            let _ =
+                tokio::task::spawn_blocking(move || prune_cache(&cache_dir, limit, &cached)).await;
+        }
+        Ok(())
     }
 
-    async fn do_compile(&self, id: Uuid, dashboard_json: &str) -> Result<()> {
-        info!("Starting WASM compilation for dashboard {}", id);
+    /// Link (or copy) a cached build into a dashboard's WASM directory
+    async fn install(&self, cached: &Path, id: Uuid) -> Result<()> {
+        let out_dir = self.wasm_dir(id);
+        self.delete_wasm(id).await?;
+        tokio::fs::create_dir_all(&out_dir)
+            .await
+            .map_err(|e| ServerError::Internal(format!("Failed to create output dir: {}", e)))?;
 
-        // Step 1: Write dashboard JSON to template crate's build directory
-        let json_path = self.template_crate.join("dashboard.json");
-        tokio::fs::write(&json_path, dashboard_json)
+        let mut entries = tokio::fs::read_dir(cached)
+            .await
+            .map_err(|e| ServerError::Internal(format!("Failed to read cached WASM: {}", e)))?;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| ServerError::Internal(format!("Failed to read cached WASM: {}", e)))?
+        {
+            let dest = out_dir.join(entry.file_name());
+            if tokio::fs::hard_link(entry.path(), &dest).await.is_err() {
+                tokio::fs::copy(entry.path(), &dest)
+                    .await
+                    .map_err(|e| ServerError::Internal(format!("Failed to install WASM: {}", e)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Build a job into `<job_dir>/out`
+    ///
+    /// This method:
+    /// 1. Writes the dashboard JSON to the job directory
+    /// 2. Runs cargo build for wasm32-unknown-unknown in the worker's target dir
+    /// 3. Runs wasm-bindgen to generate JS bindings into the job directory
+    /// 4. Optionally runs wasm-opt
+    async fn do_compile(&self, slot: usize, job: &Job, job_dir: &Path) -> Result<()> {
+        info!("Starting WASM compilation for dashboard {}", job.id);
+
+        // Step 1: Write dashboard JSON where the template's build script reads it
+        tokio::fs::create_dir_all(job_dir)
+            .await
+            .map_err(|e| ServerError::Internal(format!("Failed to create job dir: {}", e)))?;
+        let json_path = job_dir.join("dashboard.json");
+        tokio::fs::write(&json_path, &job.dashboard_json)
             .await
             .map_err(|e| ServerError::Internal(format!("Failed to write dashboard JSON: {}", e)))?;
 
         // Step 2: Build the template crate for wasm32
-        info!("Building WASM for dashboard {}...", id);
+        info!("Building WASM for dashboard {}...", job.id);
+        let target_dir = self
+            .vidi_workspace
+            .join("target")
+            .join(format!("wasm-worker-{}", slot));
         let build_output = Command::new("cargo")
             .args([
                 "build",
@@ -121,16 +367,20 @@ impl WasmCompiler {
                 "-p",
                 "dashboard-template",
             ])
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .env("VIDI_DASHBOARD_JSON", &json_path)
             .current_dir(&self.vidi_workspace)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            .kill_on_drop(true)
             .output()
             .await
             .map_err(|e| ServerError::Internal(format!("Failed to run cargo build: {}", e)))?;
 
         if !build_output.status.success() {
             let stderr = String::from_utf8_lossy(&build_output.stderr);
-            error!("WASM build failed for {}: {}", id, stderr);
+            error!("WASM build failed for {}: {}", job.id, stderr);
             return Err(ServerError::Internal(format!(
                 "WASM build failed: {}",
                 stderr
@@ -138,36 +388,25 @@ impl WasmCompiler {
         }
 
         // Step 3: Run wasm-bindgen
-        let wasm_input = self
-            .vidi_workspace
-            .join("target/wasm32-unknown-unknown/release/dashboard_template.wasm");
-
-        let out_dir = self.wasm_dir(id);
-        tokio::fs::create_dir_all(&out_dir)
-            .await
-            .map_err(|e| ServerError::Internal(format!("Failed to create output dir: {}", e)))?;
+        let wasm_input = target_dir.join("wasm32-unknown-unknown/release/dashboard_template.wasm");
+        let out_dir = job_dir.join("out");
 
-        info!("Running wasm-bindgen for dashboard {}...", id);
+        info!("Running wasm-bindgen for dashboard {}...", job.id);
         let bindgen_output = Command::new("wasm-bindgen")
-            .args([
-                wasm_input.to_str().unwrap(),
-                "--target",
-                "web",
-                "--out-dir",
-                out_dir.to_str().unwrap(),
-                "--out-name",
-                "vidi",
-                "--no-typescript",
-            ])
+            .arg(&wasm_input)
+            .args(["--target", "web", "--out-dir"])
+            .arg(&out_dir)
+            .args(["--out-name", "vidi", "--no-typescript"])
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            .kill_on_drop(true)
             .output()
             .await
             .map_err(|e| ServerError::Internal(format!("Failed to run wasm-bindgen: {}", e)))?;
 
         if !bindgen_output.status.success() {
             let stderr = String::from_utf8_lossy(&bindgen_output.stderr);
-            error!("wasm-bindgen failed for {}: {}", id, stderr);
+            error!("wasm-bindgen failed for {}: {}", job.id, stderr);
             return Err(ServerError::Internal(format!(
                 "wasm-bindgen failed: {}",
                 stderr
@@ -177,25 +416,23 @@ impl WasmCompiler {
         // Step 4: Optionally run wasm-opt (if available)
         let bg_wasm = out_dir.join("vidi_bg.wasm");
         if let Ok(opt_output) = Command::new("wasm-opt")
-            .args([
-                "-Oz",
-                "-o",
-                bg_wasm.to_str().unwrap(),
-                bg_wasm.to_str().unwrap(),
-            ])
+            .arg("-Oz")
+            .arg("-o")
+            .arg(&bg_wasm)
+            .arg(&bg_wasm)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
+            .kill_on_drop(true)
             .output()
             .await
         {
             if opt_output.status.success() {
-                info!("wasm-opt optimization applied for dashboard {}", id);
+                info!("wasm-opt optimization applied for dashboard {}", job.id);
             } else {
                 warn!("wasm-opt failed, continuing without optimization");
             }
         }
 
-        info!("WASM compilation complete for dashboard {}", id);
         Ok(())
     }
 
@@ -258,3 +495,71 @@ impl WasmCompiler {
         Ok(())
     }
 }
+
+/// Record a cache entry as just used, so pruning keeps it longest
+fn mark_used(cached: &Path) {
+    let touched = std::fs::File::open(cached).and_then(|dir| dir.set_modified(SystemTime::now()));
+    if let Err(e) = touched {
+        debug!("Failed to touch cached WASM {}: {}", cached.display(), e);
+    }
+}
+
+/// Remove the least recently used builds until the cache fits in `limit`
+/// bytes, never removing `keep`
+fn prune_cache(cache_dir: &Path, limit: u64, keep: &Path) {
+    let Ok(entries) = std::fs::read_dir(cache_dir) else {
+        return;
+    };
+    let mut builds: Vec<(SystemTime, u64, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let used = entry.metadata().ok()?.modified().ok()?;
+            let path = entry.path();
+            Some((used, dir_size(&path), path))
+        })
+        .collect();
+    let mut total: u64 = builds.iter().map(|(_, size, _)| size).sum();
+    builds.sort_by_key(|(used, ..)| *used);
+
+    for (_, size, path) in builds {
+        if total <= limit {
+            break;
+        }
+        if path == keep {
+            continue;
+        }
+        match std::fs::remove_dir_all(&path) {
+            Ok(()) => {
+                info!("Evicted cached WASM {}", path.display());
+                total -= size;
+            }
+            Err(e) => warn!("Failed to evict cached WASM {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Total size of the files directly inside `dir`
+fn dir_size(dir: &Path) -> u64 {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.metadata().ok())
+                .filter(|meta| meta.is_file())
+                .map(|meta| meta.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Cache key for a dashboard build: the JSON plus the server version that built it
+fn content_hash(dashboard_json: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.update(dashboard_json.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
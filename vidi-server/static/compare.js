@@ -0,0 +1,257 @@
+// Vidi XP - Run Comparison
+
+const API_BASE = '/api/v1';
+
+// Runs to compare come from the query string: ?ids=a,b,c or ?xp_name=name
+const params = new URLSearchParams(window.location.search);
+const selection = params.get('ids')
+  ? `ids=${encodeURIComponent(params.get('ids'))}`
+  : params.get('xp_name')
+    ? `xp_name=${encodeURIComponent(params.get('xp_name'))}`
+    : null;
+
+// State
+let ws = null;
+let wasmModule = null;
+let jsDashboard = null;
+let updateCount = 0;
+let lastSeq = 0;
+let reconnectAttempts = 0;
+const MAX_RECONNECT_ATTEMPTS = 5;
+const RECONNECT_DELAY = 2000;
+
+// DOM Elements
+const compareTitle = document.getElementById('compare-title');
+const compareRuns = document.getElementById('compare-runs');
+const picker = document.getElementById('compare-picker');
+const pickerGroups = document.getElementById('picker-groups');
+const canvasContainer = document.getElementById('canvas-container');
+const loadingOverlay = document.getElementById('loading-overlay');
+const errorOverlay = document.getElementById('error-overlay');
+const errorMessage = document.getElementById('error-message');
+const connectionStatus = document.getElementById('connection-status');
+const connectionText = document.getElementById('connection-text');
+const updateCountEl = document.getElementById('update-count');
+const seqNumberEl = document.getElementById('seq-number');
+
+// Initialize
+document.addEventListener('DOMContentLoaded', async () => {
+  setupEventListeners();
+
+  if (!selection) {
+    canvasContainer.style.display = 'none';
+    picker.style.display = 'block';
+    await showPicker();
+    return;
+  }
+
+  try {
+    const response = await fetch(`${API_BASE}/compare?${selection}`);
+    if (!response.ok) {
+      const body = await response.json().catch(() => ({}));
+      throw new Error(body.error || 'Failed to load comparison');
+    }
+    const comparison = await response.json();
+    showRuns(comparison.runs);
+
+    const module = await import('/wasm/vidi.js');
+    await module.default();
+    wasmModule = module;
+
+    jsDashboard = new wasmModule.JsDashboard(JSON.stringify(comparison.dashboard), 'dashboard-canvas');
+    jsDashboard.start();
+    loadingOverlay.style.display = 'none';
+
+    connectWebSocket();
+  } catch (error) {
+    showError(error.message);
+  }
+});
+
+// Setup event listeners
+function setupEventListeners() {
+  document.getElementById('btn-refresh').addEventListener('click', () => {
+    if (ws && ws.readyState === WebSocket.OPEN) {
+      ws.send(JSON.stringify({ type: 'get_state' }));
+    } else {
+      location.reload();
+    }
+  });
+
+  document.getElementById('btn-fullscreen').addEventListener('click', () => {
+    if (!document.fullscreenElement) {
+      canvasContainer.requestFullscreen().catch(err => {
+        console.error('Failed to enter fullscreen:', err);
+      });
+    } else {
+      document.exitFullscreen();
+    }
+  });
+
+  document.getElementById('picker-compare').addEventListener('click', () => {
+    const ids = [...pickerGroups.querySelectorAll('input:checked')].map(box => box.value);
+    if (ids.length > 0) {
+      window.location.search = `?ids=${ids.join(',')}`;
+    }
+  });
+}
+
+// List dashboards grouped by experiment, to pick runs from
+async function showPicker() {
+  compareTitle.textContent = 'Pick runs';
+  const response = await fetch(`${API_BASE}/dashboards?limit=500`);
+  const dashboards = response.ok ? await response.json() : [];
+
+  const groups = new Map();
+  for (const d of dashboards) {
+    const name = d.xp_name || '';
+    if (!groups.has(name)) groups.set(name, []);
+    groups.get(name).push(d);
+  }
+
+  pickerGroups.innerHTML = '';
+  if (groups.size === 0) {
+    pickerGroups.textContent = 'No dashboards yet';
+    return;
+  }
+
+  for (const [name, runs] of groups) {
+    const group = document.createElement('div');
+    group.className = 'compare-picker__group';
+
+    const header = document.createElement('div');
+    header.className = 'compare-picker__header';
+    header.textContent = name || '(no experiment)';
+    if (name) {
+      const all = document.createElement('a');
+      all.className = 'btn';
+      all.href = `/compare?xp_name=${encodeURIComponent(name)}`;
+      all.textContent = `ALL ${runs.length}`;
+      header.appendChild(all);
+    }
+    group.appendChild(header);
+
+    for (const run of runs) {
+      const label = document.createElement('label');
+      label.className = 'compare-picker__run';
+      const box = document.createElement('input');
+      box.type = 'checkbox';
+      box.value = run.id;
+      const text = document.createElement('span');
+      const created = new Date(run.created_at).toLocaleString();
+      text.textContent = `${run.id.slice(0, 8)}  ${run.user || ''}  ${created}  ${run.plot_count} plots`;
+      label.append(box, text);
+      group.appendChild(label);
+    }
+    pickerGroups.appendChild(group);
+  }
+}
+
+// Show the compared runs as links to their own dashboards
+function showRuns(runs) {
+  compareTitle.textContent = `Compare ${runs.length} runs`;
+  compareRuns.innerHTML = '';
+  for (const run of runs) {
+    const link = document.createElement('a');
+    link.className = 'tag tag--xp';
+    link.href = `/d/${run.id}`;
+    link.textContent = run.name;
+    compareRuns.appendChild(link);
+  }
+}
+
+// Connect to WebSocket for updates from all compared runs
+function connectWebSocket() {
+  if (ws && ws.readyState === WebSocket.OPEN) {
+    return;
+  }
+
+  const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
+  ws = new WebSocket(`${protocol}//${window.location.host}/ws/v1/compare?${selection}`);
+
+  ws.onopen = () => {
+    reconnectAttempts = 0;
+    updateConnectionStatus(true);
+
+    // Catch up on anything missed while disconnected
+    if (lastSeq > 0) {
+      ws.send(JSON.stringify({ type: 'sync', last_seq: lastSeq }));
+    }
+  };
+
+  ws.onmessage = (event) => {
+    try {
+      handleServerMessage(JSON.parse(event.data));
+    } catch (error) {
+      console.error('Failed to parse WebSocket message:', error);
+    }
+  };
+
+  ws.onclose = () => {
+    updateConnectionStatus(false);
+    if (reconnectAttempts < MAX_RECONNECT_ATTEMPTS) {
+      reconnectAttempts++;
+      setTimeout(connectWebSocket, RECONNECT_DELAY * reconnectAttempts);
+    }
+  };
+
+  ws.onerror = (error) => {
+    console.error('WebSocket error:', error);
+  };
+}
+
+// Handle incoming server messages (plot IDs are already the comparison's)
+function handleServerMessage(msg) {
+  if (msg.seq !== undefined) {
+    lastSeq = msg.seq;
+    seqNumberEl.textContent = lastSeq;
+  }
+  if (!jsDashboard) return;
+
+  switch (msg.type) {
+    case 'connected':
+      break;
+
+    case 'append_points':
+      jsDashboard.append_points(msg.plot_id, msg.layer_idx, new Float32Array(msg.points));
+      incrementUpdateCount();
+      break;
+
+    case 'replace_trace':
+      jsDashboard.replace_trace(msg.plot_id, msg.layer_idx, new Float32Array(msg.points));
+      incrementUpdateCount();
+      break;
+
+    case 'refresh_all':
+      jsDashboard.set_dashboard(JSON.stringify(msg.dashboard));
+      incrementUpdateCount();
+      break;
+
+    default:
+      console.warn('Unknown message type:', msg.type);
+  }
+}
+
+// Update connection status indicator
+function updateConnectionStatus(connected) {
+  if (connected) {
+    connectionStatus.classList.add('status-dot--connected');
+    connectionText.textContent = 'Connected';
+  } else {
+    connectionStatus.classList.remove('status-dot--connected');
+    connectionText.textContent = 'Disconnected';
+  }
+}
+
+// Increment update counter
+function incrementUpdateCount() {
+  updateCount++;
+  updateCountEl.textContent = `Updates: ${updateCount}`;
+}
+
+// Show error overlay
+function showError(message) {
+  loadingOverlay.style.display = 'none';
+  errorMessage.textContent = message;
+  errorOverlay.style.display = 'flex';
+}
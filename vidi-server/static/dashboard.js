@@ -13,7 +13,9 @@ let jsDashboard = null;
 let updateCount = 0;
 let lastSeq = 0;
 let reconnectAttempts = 0;
-let useLegacyWasm = false; // Track if using legacy single WASM
+let useBakedWasm = false; // Track if using a per-dashboard compiled bundle (config baked in)
+let compileWaiter = null; // Pending baked compilation {resolve, reject}
+const LIVE_UPDATE_TYPES = ['append_points', 'replace_trace', 'add_annotation', 'update_plot', 'refresh_all'];
 const MAX_RECONNECT_ATTEMPTS = 5;
 const RECONNECT_DELAY = 2000;
 
@@ -44,20 +46,20 @@ document.addEventListener('DOMContentLoaded', async () => {
       xp_name: dashboard.xp_name
     });
 
+    // Connect WebSocket for updates (also pushes compile status)
+    connectWebSocket();
+
     // Load WASM module
     await loadWasm();
 
-    // Initialize dashboard with data (only needed for legacy WASM)
-    // Per-dashboard WASM auto-starts with baked-in config
-    if (useLegacyWasm) {
+    // Initialize dashboard with data (only needed for the shared viewer)
+    // Baked per-dashboard WASM auto-starts with its own config
+    if (!useBakedWasm) {
       initDashboard(dashboard.dashboard);
     } else {
       console.log('Per-dashboard WASM auto-started with baked-in config');
     }
 
-    // Connect WebSocket for updates
-    connectWebSocket();
-
     // Hide loading overlay
     loadingOverlay.style.display = 'none';
   } catch (error) {
@@ -97,14 +99,16 @@ async function loadDashboardMeta() {
   return response.json();
 }
 
-// Load WASM module (per-dashboard)
+// Load WASM module (shared viewer or per-dashboard baked bundle)
 async function loadWasm() {
   try {
-    // First check WASM compilation status
     const statusResponse = await fetch(`${API_BASE}/dashboards/${dashboardId}/wasm-status`);
-    if (statusResponse.ok) {
-      const status = await statusResponse.json();
+    if (!statusResponse.ok) {
+      throw new Error('Failed to check WASM status');
+    }
+    const status = await statusResponse.json();
 
+    if (status.mode === 'baked') {
       if (status.status === 'compiling') {
         // Wait for compilation with polling
         await waitForWasmCompilation();
@@ -115,29 +119,28 @@ async function loadWasm() {
         await fetch(`${API_BASE}/dashboards/${dashboardId}/recompile`, { method: 'POST' });
         await waitForWasmCompilation();
       }
+    } else if (!status.wasm_ready) {
+      throw new Error(status.error || 'WASM viewer is not available');
     }
 
-    // Import the per-dashboard WASM module
-    const wasmPath = `/wasm/${dashboardId}/vidi.js`;
-    wasmModule = await import(wasmPath);
-
-    // For per-dashboard WASM with baked-in config, we just need to init
-    // The module auto-starts via wasm_bindgen(start)
+    wasmModule = await import(status.wasm_url);
     await wasmModule.default();
 
-    useLegacyWasm = false;
-    console.log('Per-dashboard WASM module loaded');
+    // The shared viewer is started with the dashboard JSON by initDashboard();
+    // baked bundles auto-start via wasm_bindgen(start)
+    useBakedWasm = status.mode === 'baked';
+    console.log(`WASM module loaded (${status.mode} mode)`);
   } catch (error) {
-    console.error('Failed to load per-dashboard WASM:', error);
+    console.error('Failed to load dashboard WASM:', error);
 
-    // Fallback to legacy single WASM if per-dashboard not available
+    // Fall back to the shared viewer if a baked bundle is not available
     try {
-      console.log('Falling back to legacy single WASM...');
+      console.log('Falling back to shared WASM viewer...');
       const wasmPath = '/wasm/vidi.js';
       wasmModule = await import(wasmPath);
       await wasmModule.default();
-      useLegacyWasm = true;
-      console.log('Legacy WASM module loaded');
+      useBakedWasm = false;
+      console.log('Shared WASM viewer loaded');
     } catch (fallbackError) {
       throw new Error('Failed to load WASM module. Make sure WASM files are built.');
     }
@@ -145,34 +148,62 @@ async function loadWasm() {
 }
 
 // Wait for WASM compilation to complete
+// Status changes are pushed over the WebSocket; polling is a slow fallback
 async function waitForWasmCompilation() {
-  const maxAttempts = 120; // 2 minutes max
-  const pollInterval = 1000; // 1 second
+  const timeout = 10 * 60 * 1000; // 10 minutes max
+  const pollInterval = 5000; // 5 seconds
+  const started = Date.now();
 
-  for (let i = 0; i < maxAttempts; i++) {
-    const response = await fetch(`${API_BASE}/dashboards/${dashboardId}/wasm-status`);
-    if (!response.ok) {
-      throw new Error('Failed to check WASM status');
+  const loadingText = document.querySelector('.loading-overlay__text');
+  const ticker = setInterval(() => {
+    if (loadingText) {
+      const secs = Math.round((Date.now() - started) / 1000);
+      loadingText.textContent = `Compiling dashboard... (${secs}s)`;
     }
+  }, 1000);
 
-    const status = await response.json();
+  try {
+    await new Promise((resolve, reject) => {
+      compileWaiter = { resolve, reject };
+
+      const poll = async () => {
+        if (!compileWaiter) return;
+        try {
+          const response = await fetch(`${API_BASE}/dashboards/${dashboardId}/wasm-status`);
+          if (!response.ok) {
+            throw new Error('Failed to check WASM status');
+          }
+          handleWasmStatus(await response.json());
+        } catch (error) {
+          reject(error);
+          return;
+        }
+        if (Date.now() - started > timeout) {
+          reject(new Error('WASM compilation timed out'));
+        } else if (compileWaiter) {
+          setTimeout(poll, pollInterval);
+        }
+      };
+      poll();
+    });
+  } finally {
+    compileWaiter = null;
+    clearInterval(ticker);
+  }
+}
 
-    if (status.status === 'ready' && status.wasm_ready) {
-      return; // Compilation complete
+// Apply a WASM compile status (from polling or a `wasm_status` push)
+function handleWasmStatus(status) {
+  if (compileWaiter) {
+    if (status.status === 'ready') {
+      compileWaiter.resolve();
     } else if (status.status === 'failed') {
-      throw new Error(`WASM compilation failed: ${status.error || 'Unknown error'}`);
-    }
-
-    // Update loading message
-    const loadingText = document.querySelector('.loading-overlay__text');
-    if (loadingText) {
-      loadingText.textContent = `Compiling dashboard... (${i + 1}s)`;
+      compileWaiter.reject(new Error(`WASM compilation failed: ${status.error || 'Unknown error'}`));
     }
-
-    await new Promise(resolve => setTimeout(resolve, pollInterval));
+  } else if (useBakedWasm && wasmModule && status.status === 'ready') {
+    // A baked dashboard was rebuilt (e.g. after a replace): load the new bundle
+    location.reload();
   }
-
-  throw new Error('WASM compilation timed out');
 }
 
 // Initialize the dashboard with data
@@ -249,6 +280,16 @@ function handleServerMessage(msg) {
     seqNumberEl.textContent = lastSeq;
   }
 
+  // A replace or restore adds a version to the history
+  if (msg.type === 'refresh_all') {
+    VidiHistory.reload();
+  }
+
+  // While an old version is on screen, live updates would draw over it
+  if (VidiHistory.isBrowsing() && LIVE_UPDATE_TYPES.includes(msg.type)) {
+    return;
+  }
+
   switch (msg.type) {
     case 'connected':
       console.log('Connected to dashboard:', msg.dashboard_id);
@@ -268,6 +309,13 @@ function handleServerMessage(msg) {
       }
       break;
 
+    case 'add_annotation':
+      if (jsDashboard) {
+        jsDashboard.add_annotation(msg.plot_id, JSON.stringify(msg.annotation));
+        incrementUpdateCount();
+      }
+      break;
+
     case 'update_plot':
       if (jsDashboard) {
         jsDashboard.update_plot(msg.plot_id, JSON.stringify(msg.plot));
@@ -282,6 +330,10 @@ function handleServerMessage(msg) {
       }
       break;
 
+    case 'wasm_status':
+      handleWasmStatus(msg);
+      break;
+
     case 'error':
       console.error('Server error:', msg.message);
       break;
@@ -0,0 +1,172 @@
+// Vidi XP - Version History Panel
+
+const STORAGE_KEY_AUTHOR = 'vidi_author';
+
+const VidiHistory = {
+  open: false,
+  versions: [],
+  // Index into `versions` of the version on screen
+  index: -1,
+
+  init() {
+    this.panel = document.getElementById('history-panel');
+    this.position = document.getElementById('history-position');
+    this.meta = document.getElementById('history-meta');
+    this.note = document.getElementById('history-note');
+    this.diff = document.getElementById('history-diff');
+    this.btnPrev = document.getElementById('history-prev');
+    this.btnNext = document.getElementById('history-next');
+    this.btnLatest = document.getElementById('history-latest');
+    this.btnRestore = document.getElementById('history-restore');
+
+    document.getElementById('btn-history').addEventListener('click', () => this.toggle());
+    this.btnPrev.addEventListener('click', () => this.show(this.index - 1));
+    this.btnNext.addEventListener('click', () => this.show(this.index + 1));
+    this.btnLatest.addEventListener('click', () => this.show(this.versions.length - 1));
+    this.btnRestore.addEventListener('click', () => this.restore());
+
+    // Arrow keys step through versions while the panel is open
+    document.addEventListener('keydown', (e) => {
+      if (!this.open) return;
+      if (e.key === 'ArrowLeft') this.show(this.index - 1);
+      if (e.key === 'ArrowRight') this.show(this.index + 1);
+    });
+  },
+
+  // True when an older version is on screen instead of the live dashboard
+  isBrowsing() {
+    return this.open && this.index >= 0 && this.index < this.versions.length - 1;
+  },
+
+  async toggle() {
+    this.open = !this.open;
+    this.panel.classList.toggle('history-panel--open', this.open);
+    if (this.open) {
+      await this.reload();
+      await this.show(this.versions.length - 1);
+    } else if (this.index !== this.versions.length - 1) {
+      // Leave the live dashboard on screen when closing
+      await this.show(this.versions.length - 1);
+    }
+  },
+
+  // Refresh the version list, keeping the current position
+  async reload() {
+    if (!this.open) return;
+    const wasLatest = this.index === this.versions.length - 1;
+    const response = await fetch(`${API_BASE}/dashboards/${dashboardId}/versions`);
+    if (!response.ok) {
+      this.note.textContent = 'Failed to load version history';
+      return;
+    }
+    this.versions = await response.json();
+    if (wasLatest) {
+      this.index = this.versions.length - 1;
+    }
+    this.renderNav();
+  },
+
+  async show(index) {
+    if (index < 0 || index >= this.versions.length) return;
+    this.index = index;
+    this.renderNav();
+
+    const version = this.versions[index];
+    const author = version.author || 'unknown';
+    this.meta.textContent = `${author} - ${new Date(version.created_at).toLocaleString()}`;
+
+    try {
+      const response = await fetch(`${API_BASE}/dashboards/${dashboardId}/versions/${version.version}`);
+      if (!response.ok) throw new Error('Failed to load version');
+      const data = await response.json();
+
+      if (jsDashboard) {
+        jsDashboard.set_dashboard(JSON.stringify(data.dashboard));
+        this.note.textContent = this.isBrowsing() ? 'Live updates are paused while browsing' : '';
+      } else {
+        // Baked bundles carry their own dashboard and can't swap it at runtime
+        this.note.textContent = 'Preview needs the shared viewer; showing changes only';
+      }
+
+      await this.renderDiff(index);
+    } catch (error) {
+      this.note.textContent = error.message;
+    }
+  },
+
+  // Changes of the version on screen relative to the one before it
+  async renderDiff(index) {
+    this.diff.innerHTML = '';
+    if (index === 0) {
+      this.diff.innerHTML = '<li>First version</li>';
+      return;
+    }
+
+    const from = this.versions[index - 1].version;
+    const to = this.versions[index].version;
+    const response = await fetch(`${API_BASE}/dashboards/${dashboardId}/diff?from=${from}&to=${to}`);
+    if (!response.ok) throw new Error('Failed to load changes');
+    const diff = await response.json();
+
+    const items = [];
+    diff.tabs_added.forEach(t => items.push(['added', `Tab "${t}"`]));
+    diff.tabs_removed.forEach(t => items.push(['removed', `Tab "${t}"`]));
+    diff.fields.forEach(f => items.push(['changed', `Dashboard ${f}`]));
+    diff.plots.forEach(p => {
+      const name = p.title || p.key || `${p.kind} #${p.plot_id}`;
+      const details = [
+        ...p.fields,
+        ...p.layers.map(l => `layer ${l.index} ${l.change}`),
+      ];
+      const suffix = details.length ? ` (${details.join(', ')})` : '';
+      items.push([p.change, `${name}${suffix}`]);
+    });
+    if (items.length === 0) {
+      items.push(['changed', 'No changes']);
+    }
+
+    items.forEach(([change, text]) => {
+      const li = document.createElement('li');
+      li.className = `history-diff__${change}`;
+      li.textContent = `${change.toUpperCase()} ${text}`;
+      this.diff.appendChild(li);
+    });
+  },
+
+  async restore() {
+    const version = this.versions[this.index];
+    if (!version || !this.isBrowsing()) return;
+
+    const saved = localStorage.getItem(STORAGE_KEY_AUTHOR) || '';
+    const author = prompt(`Restore version ${version.version}? Your name:`, saved);
+    if (author === null) return;
+    localStorage.setItem(STORAGE_KEY_AUTHOR, author);
+
+    const query = author ? `?author=${encodeURIComponent(author)}` : '';
+    const response = await fetch(
+      `${API_BASE}/dashboards/${dashboardId}/versions/${version.version}/restore${query}`,
+      { method: 'POST' },
+    );
+    if (!response.ok) {
+      this.note.textContent = 'Restore failed';
+      return;
+    }
+
+    // The restore is the new latest version
+    this.index = this.versions.length - 1;
+    await this.reload();
+    await this.show(this.versions.length - 1);
+  },
+
+  renderNav() {
+    const total = this.versions.length;
+    const current = this.versions[this.index];
+    this.position.textContent = current ? `v${current.version} (${this.index + 1}/${total})` : '-';
+    this.btnPrev.disabled = this.index <= 0;
+    this.btnNext.disabled = this.index >= total - 1;
+    this.btnLatest.disabled = this.index >= total - 1;
+    this.btnRestore.disabled = !this.isBrowsing();
+  },
+};
+
+document.addEventListener('DOMContentLoaded', () => VidiHistory.init());